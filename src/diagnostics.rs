@@ -0,0 +1,423 @@
+use crate::lexer::{LexErr, Span, Token};
+use crate::parser::context::{Context, Mode, Width};
+use crate::parser::error::{EvalErr, ParseErr};
+use crate::parser::syntax::{Expr, Operation};
+
+/// Hint for any result that doesn't fit in the register
+const WIDER: &str = "a wider register may hold it, see --width";
+
+/// Hint for a result that doesn't fit where it could also be let through
+const WIDER_OR_MODE: &str = "a wider register may hold it, see --width, or keep going with --mode wrapping or saturating";
+
+/// An error along with the part of the input it points at, if any, the
+/// label that goes under it and a hint on what to do about it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Diagnostic {
+    pub message: String,
+    pub span: Option<Span>,
+    pub label: String,
+    pub help: Option<String>,
+}
+
+impl Diagnostic {
+    fn new(message: String, span: Option<Span>, label: &str) -> Self {
+        Diagnostic {
+            message,
+            span,
+            label: label.to_string(),
+            help: None,
+        }
+    }
+
+    fn with_help(self, help: &str) -> Self {
+        Diagnostic {
+            help: Some(help.to_string()),
+            ..self
+        }
+    }
+
+    /// Hints at a wider register, which only a fixed width outside fixed
+    /// point can be given, along with the modes that let the result through
+    /// if it could be and checked mode didn't
+    fn with_wider(self, ctx: &Context, or_mode: bool) -> Self {
+        match (ctx.width, ctx.fixed) {
+            (Width::Unbounded, _) | (_, Some(_)) => self,
+            _ if or_mode && ctx.mode == Mode::Checked => self.with_help(WIDER_OR_MODE),
+            _ => self.with_help(WIDER),
+        }
+    }
+
+    /// Writes out the message, then the line of the input the span starts
+    /// on with the span underlined and labelled, then the hint, eg.
+    ///
+    /// ```text
+    /// Error: Operation '1 / 0' divides by zero
+    ///   | 1 / 0
+    ///   |     ^ this divisor is zero
+    /// ```
+    pub fn render(&self, input: &str) -> String {
+        let mut rendered = format!("Error: {}", self.message);
+
+        if let Some(span) = self.span {
+            let chars: Vec<char> = input.chars().collect();
+            let start = span.start.min(chars.len());
+            let line_start = chars[..start].iter().rposition(|&c| c == '\n').map_or(0, |i| i + 1);
+            let line_end = chars[start..].iter().position(|&c| c == '\n').map_or(chars.len(), |i| start + i);
+
+            // Every character takes up one column, tabs included
+            let line: String = chars[line_start..line_end]
+                .iter()
+                .map(|&c| if c.is_whitespace() { ' ' } else { c })
+                .collect();
+            let width = span.end.min(line_end).saturating_sub(start).max(1);
+
+            rendered.push_str(&format!(
+                "\n  | {}\n  | {}{} {}",
+                line.trim_end(),
+                " ".repeat(start - line_start),
+                "^".repeat(width),
+                self.label
+            ));
+        }
+
+        if let Some(help) = &self.help {
+            rendered.push_str(&format!("\n  = help: {}", help));
+        }
+
+        rendered
+    }
+}
+
+/// An error that can point at the part of the input it came from, with
+/// hints that suit the context it came up in.
+pub trait Diagnose {
+    fn diagnose(&self, input: &str, ctx: &Context) -> Diagnostic;
+}
+
+/// Renders any error against the input and context it came from
+pub fn render<E: Diagnose>(error: &E, input: &str, ctx: &Context) -> String {
+    error.diagnose(input, ctx).render(input)
+}
+
+/// The text of the input a span covers
+fn text(input: &str, span: Span) -> String {
+    input.chars().skip(span.start).take(span.end - span.start).collect()
+}
+
+impl Diagnose for LexErr {
+    fn diagnose(&self, input: &str, ctx: &Context) -> Diagnostic {
+        let span = Some(self.span());
+
+        match self {
+            LexErr::InvalidCharacter { chr: '=', .. } => {
+                Diagnostic::new(self.to_string(), span, "not a symbol on its own").with_help("equality is written '=='")
+            }
+            LexErr::InvalidCharacter { chr: '!', .. } => {
+                Diagnostic::new(self.to_string(), span, "not a symbol on its own").with_help("inequality is written '!='")
+            }
+            LexErr::InvalidCharacter { chr, .. } if chr.is_ascii_alphabetic() => {
+                Diagnostic::new(self.to_string(), span, "doesn't start a known word")
+            }
+            LexErr::InvalidCharacter { .. } => Diagnostic::new(self.to_string(), span, "not part of any expression"),
+            LexErr::UnexpectedCharacter { .. } => Diagnostic::new(self.to_string(), span, "unexpected character"),
+            LexErr::InvalidDigit { radix, .. } => {
+                let label = format!("not a base {} digit", radix);
+                Diagnostic::new(self.to_string(), span, &label)
+            }
+            LexErr::InvalidGroup { span, min, max } => {
+                let label = match min == max {
+                    true => format!("expected {} digits", min),
+                    false => format!("expected {} to {} digits", min, max),
+                };
+                let diagnostic = Diagnostic::new(self.to_string(), Some(*span), &label);
+
+                // Arguments written without a space between them read as
+                // one number
+                match input.chars().nth(span.start.wrapping_sub(1)) {
+                    Some(',') => diagnostic.with_help("to separate arguments instead, put a space after the comma"),
+                    _ => diagnostic,
+                }
+            }
+            LexErr::LiteralOutOfRange { span, range, negated } => {
+                let label = format!("above {}", range.max);
+                let diagnostic = Diagnostic::new(self.to_string(), Some(*span), &label);
+
+                // The magnitude of the smallest value never fits on its own,
+                // since the minus in front of it is applied afterwards
+                let digits: String = text(input, *span).chars().filter(char::is_ascii_digit).collect();
+                let smallest = (-&range.min).to_string();
+
+                match *negated && digits == smallest {
+                    true => diagnostic.with_help(&format!(
+                        "the smallest value can be written as -{} - 1",
+                        &range.max
+                    )),
+                    false => diagnostic.with_wider(ctx, true),
+                }
+            }
+            LexErr::MissingDigits { radix, .. } => {
+                let label = format!("expected base {} digits after this", radix);
+                Diagnostic::new(self.to_string(), span, &label)
+            }
+            LexErr::LiteralTooWide { bits, .. } => {
+                let label = format!("more than {} Bits", bits);
+                Diagnostic::new(self.to_string(), span, &label)
+            }
+        }
+    }
+}
+
+impl Diagnose for ParseErr {
+    fn diagnose(&self, input: &str, _ctx: &Context) -> Diagnostic {
+        let message = self.to_string();
+
+        match self {
+            ParseErr::IncompleteSequence => {
+                let end = input.chars().count();
+                Diagnostic::new(message, Some(Span::new(end, end)), "expected another token here")
+            }
+            ParseErr::UnexpectedToken(t, span) => {
+                let message = format!("Token '{}' was unexpected", text(input, *span));
+                let diagnostic = Diagnostic::new(message, Some(*span), "unexpected token");
+
+                match t {
+                    Token::Sym(crate::lexer::Symbol::Comma) => {
+                        diagnostic.with_help("commas only separate the arguments of a function")
+                    }
+                    Token::Sym(crate::lexer::Symbol::ParenClose) => {
+                        diagnostic.with_help("no parenthesis was opened for it to close")
+                    }
+                    _ => diagnostic,
+                }
+            }
+            ParseErr::ArgumentCount { expected, span, .. } => {
+                let label = format!("expected {} argument(s)", expected);
+                Diagnostic::new(message, Some(*span), &label)
+            }
+            ParseErr::UnclosedParenthesis(span) => {
+                Diagnostic::new(message, Some(*span), "missing closing parenthesis opened here")
+            }
+            ParseErr::NoEmptyNodeFound | ParseErr::GeneralError(_) => {
+                Diagnostic::new(message, None, "")
+            }
+        }
+    }
+}
+
+/// The argument of a call at the given index, or the call itself if there
+/// is no such argument
+fn argument(expr: &Expr, index: usize) -> Option<Span> {
+    match expr {
+        Expr::Call(_, args, _) => args.get(index).and_then(Expr::span).or_else(|| expr.span()),
+        _ => expr.span(),
+    }
+}
+
+impl Diagnose for EvalErr {
+    fn diagnose(&self, _input: &str, ctx: &Context) -> Diagnostic {
+        let message = self.to_string();
+
+        match self {
+            EvalErr::EmptyExpression(span) => Diagnostic::new(message, *span, "this is missing an operand"),
+            EvalErr::IncompleteOperation(op) => {
+                Diagnostic::new(message, Some(op.span()), "this operation is missing an operand")
+            }
+            EvalErr::DivisionByZero(op) => Diagnostic::new(message, op.rhs().span(), "this divisor is zero"),
+            EvalErr::Overflow(op) => {
+                Diagnostic::new(message, Some(op.span()), "this result doesn't fit")
+                    .with_wider(ctx, true)
+            }
+            EvalErr::NegationOverflow(expr) => {
+                Diagnostic::new(message, expr.span(), "this negation doesn't fit").with_wider(ctx, false)
+            }
+            EvalErr::ComplementOverflow(expr) => Diagnostic::new(message, expr.span(), "this complement doesn't fit"),
+            EvalErr::LiteralOverflow(expr) => {
+                Diagnostic::new(message, expr.span(), "this literal doesn't fit").with_wider(ctx, false)
+            }
+            EvalErr::NegativeRoot(expr) => Diagnostic::new(message, argument(expr, 0), "this is negative"),
+            EvalErr::InvalidDegree(expr) => Diagnostic::new(message, argument(expr, 1), "this degree is not above zero"),
+            EvalErr::NotIntegral(expr) => Diagnostic::new(message, expr.span(), "only works on integers"),
+            EvalErr::NotComplex(expr) => Diagnostic::new(message, expr.span(), "imaginary number")
+                .with_help("imaginary numbers need --domain complex"),
+            EvalErr::NotReal(expr) => Diagnostic::new(message, expr.span(), "only works on real numbers"),
+            EvalErr::FunctionOverflow(expr) => {
+                Diagnostic::new(message, expr.span(), "this result doesn't fit").with_wider(ctx, false)
+            }
+            EvalErr::NotModular(expr) => Diagnostic::new(message, expr.span(), "needs a modulus")
+                .with_help("set one with --mod, or with 'mod <modulus>' in the interactive prompt"),
+            // The divisor, the base of a negative power, or the argument
+            // is what has no inverse
+            EvalErr::NotInvertible(expr) => {
+                let span = match &**expr {
+                    Expr::BinOp(op) if op.operation() == Operation::Div => op.rhs().span(),
+                    Expr::BinOp(op) => op.lhs().span(),
+                    expr => argument(expr, 0),
+                };
+
+                Diagnostic::new(message, span, "this has no inverse for the modulus")
+            }
+            EvalErr::ModulusOverflow(_) => Diagnostic::new(message, None, "").with_wider(ctx, false),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::render;
+    use crate::parser::context::{Context, Mode, RationalOutput};
+    use crate::{lexer, parser};
+
+    /// Renders whichever error comes up first for the input
+    fn diagnose(input: &str, ctx: &Context) -> String {
+        let tokens = match lexer::lex_in(input, ctx) {
+            Ok(tokens) => tokens,
+            Err(e) => return render(&e, input, ctx),
+        };
+
+        let expr = match parser::parse(&tokens) {
+            Ok(expr) => expr,
+            Err(e) => return render(&e, input, ctx),
+        };
+
+        match expr.evaluate(ctx) {
+            Ok(result) => panic!("Expected an error, got {}", result),
+            Err(e) => render(&e, input, ctx),
+        }
+    }
+
+    #[test]
+    fn test_lex_errors() {
+        let ctx = Context::new();
+
+        assert_eq!(
+            diagnose("1 = 2", &ctx),
+            "Error: Invalid character at 3: '='\n  | 1 = 2\n  |   ^ not a symbol on its own\n  = help: equality is written '=='"
+        );
+        assert_eq!(
+            diagnose("1 !2", &ctx),
+            "Error: Invalid character at 3: '!'\n  | 1 !2\n  |   ^ not a symbol on its own\n  = help: inequality is written '!='"
+        );
+        assert_eq!(
+            diagnose("1 =", &ctx),
+            "Error: Invalid character at 3: '='\n  | 1 =\n  |   ^ not a symbol on its own\n  = help: equality is written '=='"
+        );
+        assert_eq!(
+            diagnose("sqrt(4) + foo", &ctx),
+            "Error: Invalid character at 11: 'f'\n  | sqrt(4) + foo\n  |           ^ doesn't start a known word"
+        );
+        assert_eq!(
+            diagnose("0b1020", &ctx),
+            "Error: Invalid digit at 5: '2' is not a base 2 digit\n  | 0b1020\n  |     ^ not a base 2 digit"
+        );
+        assert_eq!(
+            diagnose("0xFF + 2", &Context::with_width("8".parse().unwrap())),
+            "Error: Literal at 1 is above the largest value 127\n  | 0xFF + 2\n  | ^^^^ above 127\n  = help: a wider register may hold it, see --width, or keep going with --mode wrapping or saturating"
+        );
+        assert_eq!(
+            diagnose("-128", &Context::with_width("8".parse().unwrap())),
+            "Error: Literal at 2 is above the largest value 127\n  | -128\n  |  ^^^ above 127\n  = help: the smallest value can be written as -127 - 1"
+        );
+        assert_eq!(
+            diagnose("128", &Context::with_width("8".parse().unwrap())),
+            "Error: Literal at 1 is above the largest value 127\n  | 128\n  | ^^^ above 127\n  = help: a wider register may hold it, see --width, or keep going with --mode wrapping or saturating"
+        );
+        assert_eq!(
+            diagnose("-129", &Context::with_width("8".parse().unwrap())),
+            "Error: Literal at 2 is above the largest value 127\n  | -129\n  |  ^^^ above 127\n  = help: a wider register may hold it, see --width, or keep going with --mode wrapping or saturating"
+        );
+        assert_eq!(
+            diagnose("0x + 1", &ctx),
+            "Error: Literal at 1 has no base 16 digits after its prefix\n  | 0x + 1\n  | ^^ expected base 16 digits after this"
+        );
+        assert_eq!(
+            diagnose("(1,000,3) + 1", &ctx),
+            "Error: Group of digits at 8 should have 3 digits\n  | (1,000,3) + 1\n  |        ^ expected 3 digits\n  = help: to separate arguments instead, put a space after the comma"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let ctx = Context::new();
+
+        assert_eq!(
+            diagnose("2 * (1 + 3", &ctx),
+            "Error: Parenthesis was never closed\n  | 2 * (1 + 3\n  |     ^ missing closing parenthesis opened here"
+        );
+        assert_eq!(
+            diagnose("1 + 2)", &ctx),
+            "Error: Token ')' was unexpected\n  | 1 + 2)\n  |      ^ unexpected token\n  = help: no parenthesis was opened for it to close"
+        );
+        assert_eq!(
+            diagnose("root(8)", &ctx),
+            "Error: Function 'root' takes 2 argument(s) but was given 1\n  | root(8)\n  | ^^^^^^^ expected 2 argument(s)"
+        );
+        assert_eq!(
+            diagnose("1 + sqrt", &ctx),
+            "Error: Another token was expected\n  | 1 + sqrt\n  |         ^ expected another token here"
+        );
+        assert_eq!(
+            diagnose("1 + 2, 3", &ctx),
+            "Error: Token ',' was unexpected\n  | 1 + 2, 3\n  |      ^ unexpected token\n  = help: commas only separate the arguments of a function"
+        );
+    }
+
+    #[test]
+    fn test_eval_errors() {
+        let ctx = Context::new();
+
+        assert_eq!(
+            diagnose("7 + 1 / (2 - 2)", &ctx),
+            "Error: Operation '1 / (2 - 2)' divides by zero\n  | 7 + 1 / (2 - 2)\n  |         ^^^^^^^ this divisor is zero"
+        );
+        assert_eq!(
+            diagnose("root(8, -1)", &ctx),
+            "Error: Root 'root(8, -1)' needs a degree above zero\n  | root(8, -1)\n  |         ^^ this degree is not above zero"
+        );
+        assert_eq!(
+            diagnose("1 +", &ctx),
+            "Error: Operation '1 + _' is missing an operand\n  | 1 +\n  | ^^^ this operation is missing an operand"
+        );
+        assert_eq!(diagnose("", &ctx), "Error: There is nothing to evaluate");
+        assert_eq!(
+            diagnose("sqrt()", &ctx),
+            "Error: There is nothing to evaluate\n  | sqrt()\n  | ^^^^^^ this is missing an operand"
+        );
+        assert_eq!(
+            diagnose("~", &ctx),
+            "Error: There is nothing to evaluate\n  | ~\n  | ^ this is missing an operand"
+        );
+
+        let narrow = Context::with_width("8".parse().unwrap());
+        assert_eq!(
+            diagnose("2 *\t100 - 1", &narrow),
+            "Error: Operation '2 * 100' overflowed\n  | 2 *\t100 - 1\n  | ^^^^^^^ this result doesn't fit\n  = help: a wider register may hold it, see --width, or keep going with --mode wrapping or saturating"
+                .replace('\t', " ")
+        );
+        assert_eq!(
+            diagnose("1\n+ 300", &Context::with_fixed("Q4.4".parse().unwrap())),
+            "Error: Literal '300' does not fit\n  | + 300\n  |   ^^^ this literal doesn't fit"
+        );
+
+        // Only a fixed width can be widened, and only checked mode stops
+        let big = Context::with_width("big".parse().unwrap());
+        assert_eq!(
+            diagnose("2 ^ 5000", &big),
+            "Error: Operation '2 ^ 5000' overflowed\n  | 2 ^ 5000\n  | ^^^^^^^^ this result doesn't fit"
+        );
+        let saturating = Context {
+            mode: Mode::Saturating,
+            rational: Some(RationalOutput::Fraction),
+            ..narrow.clone()
+        };
+        assert_eq!(
+            diagnose("1/100 + 1/99", &saturating),
+            "Error: Operation '1 / 100 + 1 / 99' overflowed\n  | 1/100 + 1/99\n  | ^^^^^^^^^^^^ this result doesn't fit\n  = help: a wider register may hold it, see --width"
+        );
+
+        let modular = Context::with_modulus("12".parse().unwrap());
+        assert_eq!(
+            diagnose("5 / 4", &modular),
+            "Error: Operation '5 / 4' needs a value with no factor in common with the modulus\n  | 5 / 4\n  |     ^ this has no inverse for the modulus"
+        );
+    }
+}
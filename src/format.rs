@@ -0,0 +1,199 @@
+use std::str::FromStr;
+
+/// How the digits of a number are grouped and what comes before its
+/// fraction, both in literals and in the results written out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NumberFormat {
+    /// `1,234,567.5`
+    EnUs,
+    /// `1.234.567,5`
+    DeDe,
+    /// `1 234 567,5`
+    Fr,
+    /// `12,34,567.5`, grouping lakhs and crores by 2 above the first 3
+    Indian,
+}
+
+impl FromStr for NumberFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::NumberFormat::*;
+
+        Ok(match s {
+            "en-US" | "en" => EnUs,
+            "de-DE" | "de" => DeDe,
+            "fr-FR" | "fr" => Fr,
+            "en-IN" | "indian" => Indian,
+            _ => return Err(format!("Unknown number format '{}'", s)),
+        })
+    }
+}
+
+impl NumberFormat {
+    /// The character written between groups of digits
+    pub fn group_separator(self) -> char {
+        match self {
+            NumberFormat::EnUs | NumberFormat::Indian => ',',
+            NumberFormat::DeDe => '.',
+            NumberFormat::Fr => ' ',
+        }
+    }
+
+    /// Whether a character separates groups of digits, which in French may
+    /// also be either of the spaces that don't break a line
+    pub fn is_group_separator(self, c: char) -> bool {
+        match self {
+            NumberFormat::Fr => c == ' ' || c == '\u{a0}' || c == '\u{202f}',
+            _ => c == self.group_separator(),
+        }
+    }
+
+    /// The character between the whole part and the fraction
+    pub fn decimal_separator(self) -> char {
+        match self {
+            NumberFormat::EnUs | NumberFormat::Indian => '.',
+            NumberFormat::DeDe | NumberFormat::Fr => ',',
+        }
+    }
+
+    /// How many digits the last group has, and how many each group before
+    /// it has, apart from the first which may have fewer
+    pub fn group_sizes(self) -> (usize, usize) {
+        match self {
+            NumberFormat::Indian => (3, 2),
+            _ => (3, 3),
+        }
+    }
+
+    /// Checks how many digits each group of a whole part has, where a single
+    /// group can have any number. Returns the index of the first group that
+    /// doesn't fit along with the fewest and most digits it could have had
+    pub fn check_groups(self, groups: &[usize]) -> Result<(), (usize, usize, usize)> {
+        let (last, inner) = self.group_sizes();
+
+        if groups.len() < 2 {
+            return Ok(());
+        }
+
+        for (i, &digits) in groups.iter().enumerate() {
+            let (min, max) = match i {
+                _ if i == groups.len() - 1 => (last, last),
+                0 => (1, inner),
+                _ => (inner, inner),
+            };
+
+            if digits < min || digits > max {
+                return Err((i, min, max));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Separates the digits of a whole part into groups
+    pub fn group(self, digits: &str) -> String {
+        let (last, inner) = self.group_sizes();
+        let mut groups = Vec::new();
+        let mut rest = digits;
+
+        if rest.len() > last {
+            let (head, tail) = rest.split_at(rest.len() - last);
+            groups.push(tail);
+            rest = head;
+
+            while rest.len() > inner {
+                let (head, tail) = rest.split_at(rest.len() - inner);
+                groups.push(tail);
+                rest = head;
+            }
+        }
+
+        groups.push(rest);
+        groups.reverse();
+        groups.join(&self.group_separator().to_string())
+    }
+
+    /// Writes every number in a result in this format, leaving alone the
+    /// digits of a fraction, including any that repeat, and of an exponent
+    pub fn format(self, s: &str) -> String {
+        let chars: Vec<char> = s.chars().collect();
+        let mut formatted = String::new();
+        let mut i = 0;
+
+        while i < chars.len() {
+            let exponent = chars[..i].ends_with(&['e']) || (i >= 2 && chars[i - 2] == 'e' && matches!(chars[i - 1], '+' | '-'));
+
+            if !chars[i].is_ascii_digit() || exponent {
+                formatted.push(chars[i]);
+                i += 1;
+                continue;
+            }
+
+            let start = i;
+            while i < chars.len() && chars[i].is_ascii_digit() {
+                i += 1;
+            }
+
+            let whole: String = chars[start..i].iter().collect();
+            formatted.push_str(&self.group(&whole));
+
+            if i + 1 < chars.len() && chars[i] == '.' && (chars[i + 1].is_ascii_digit() || chars[i + 1] == '(') {
+                formatted.push(self.decimal_separator());
+                i += 1;
+
+                while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '(' || chars[i] == ')') {
+                    formatted.push(chars[i]);
+                    i += 1;
+                }
+            }
+        }
+
+        formatted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::NumberFormat::{self, *};
+
+    #[test]
+    fn test_check_groups() {
+        assert_eq!(EnUs.check_groups(&[7]), Ok(()));
+        assert_eq!(EnUs.check_groups(&[1, 3, 3]), Ok(()));
+        assert_eq!(EnUs.check_groups(&[1, 1, 1]), Err((1, 3, 3)));
+        assert_eq!(EnUs.check_groups(&[4, 3]), Err((0, 1, 3)));
+        assert_eq!(EnUs.check_groups(&[3, 3, 2]), Err((2, 3, 3)));
+        assert_eq!(Indian.check_groups(&[2, 2, 3]), Ok(()));
+        assert_eq!(Indian.check_groups(&[1, 3, 3]), Err((1, 2, 2)));
+        assert_eq!(Indian.check_groups(&[3, 3]), Err((0, 1, 2)));
+        assert_eq!(Indian.check_groups(&[2, 4]), Err((1, 3, 3)));
+    }
+
+    #[test]
+    fn test_group() {
+        assert_eq!(EnUs.group("1234567"), "1,234,567");
+        assert_eq!(DeDe.group("123456"), "123.456");
+        assert_eq!(Fr.group("1234"), "1 234");
+        assert_eq!(Indian.group("1234567"), "12,34,567");
+        assert_eq!(Indian.group("123456789"), "12,34,56,789");
+        assert_eq!(Indian.group("123"), "123");
+        assert_eq!(EnUs.group("0"), "0");
+    }
+
+    #[test]
+    fn test_format() {
+        let format = |format: NumberFormat, s: &str| format.format(s);
+
+        assert_eq!(format(EnUs, "-1234.5678"), "-1,234.5678");
+        assert_eq!(format(DeDe, "1234.5"), "1.234,5");
+        assert_eq!(format(Fr, "1234.5"), "1 234,5");
+        assert_eq!(format(Indian, "-1234567"), "-12,34,567");
+        assert_eq!(format(DeDe, "1234+5678i"), "1.234+5.678i");
+        assert_eq!(format(EnUs, "12345/67890"), "12,345/67,890");
+        assert_eq!(format(DeDe, "1234.1(142857)"), "1.234,1(142857)");
+        assert_eq!(format(Fr, "142.(857142)"), "142,(857142)");
+        assert_eq!(format(EnUs, "1.5e-1000"), "1.5e-1000");
+        assert_eq!(format(Fr, "inf"), "inf");
+    }
+}
@@ -1,76 +1,257 @@
-use std::{collections::HashMap, error, fmt, slice};
+use crate::format::NumberFormat;
+use crate::parser::context::Context;
+use crate::types::{BigBinary, Bit, Decimal};
+use std::{collections::HashMap, error, fmt, iter::{Enumerate, Peekable, Zip}, slice, str::Chars};
 
 /// The white-listed set of non-digit symbols.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Symbol {
+    Abs2,
+    Ampersand,
     Asterisk,
+    BangEqual,
     Caret,
+    Comma,
+    Conj,
+    EqualEqual,
     FwdSlash,
+    Greater,
+    GreaterEqual,
+    GreaterGreater,
+    GreaterGreaterGreater,
+    Im,
+    Inv,
+    Less,
+    LessEqual,
+    LessLess,
     Minus,
     ParenClose,
     ParenOpen,
     Percent,
+    Pipe,
     Plus,
+    Re,
+    Root,
+    Rotl,
+    Rotr,
+    Sqrt,
+    Tilde,
+    Xor,
+}
+
+impl Symbol {
+    /// Whether the symbol names a function, whose arguments follow it in
+    /// parentheses
+    pub fn is_function(self) -> bool {
+        use self::Symbol::*;
+
+        matches!(self, Abs2 | Conj | Im | Inv | Re | Root | Sqrt)
+    }
+}
+
+impl fmt::Display for Symbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Symbol::*;
+
+        write!(f, "{}", match self {
+            Abs2 => "abs2",
+            Ampersand => "&",
+            Asterisk => "*",
+            BangEqual => "!=",
+            Caret => "^",
+            Comma => ",",
+            Conj => "conj",
+            EqualEqual => "==",
+            FwdSlash => "/",
+            Greater => ">",
+            GreaterEqual => ">=",
+            GreaterGreater => ">>",
+            GreaterGreaterGreater => ">>>",
+            Im => "im",
+            Inv => "inv",
+            Less => "<",
+            LessEqual => "<=",
+            LessLess => "<<",
+            Minus => "-",
+            ParenClose => ")",
+            ParenOpen => "(",
+            Percent => "%",
+            Pipe => "|",
+            Plus => "+",
+            Re => "re",
+            Root => "root",
+            Rotl => "rotl",
+            Rotr => "rotr",
+            Sqrt => "sqrt",
+            Tilde => "~",
+            Xor => "xor",
+        })
+    }
 }
 
 /// The set of possible tokens.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub enum Token {
-    Num(i64),
+    Num(BigBinary),
+    Decimal(Decimal),
+    /// A number with an `i` suffix, eg. `4i`
+    Imaginary(Box<Token>),
     Sym(Symbol),
 }
 
-/// Surprise, a holder of tokens
-#[derive(Clone, Debug, PartialEq)]
-pub struct TokenSequence(Vec<Token>);
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Token::Num(n) => write!(f, "{}", n),
+            Token::Decimal(d) => write!(f, "{}", d),
+            Token::Imaginary(t) => write!(f, "{}i", t),
+            Token::Sym(s) => s.fmt(f),
+        }
+    }
+}
+
+/// The characters of the input something was read from, counted from 0,
+/// from `start` up to but not including `end`
+#[derive(Clone, Copy, Default, PartialEq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+}
+
+impl Span {
+    pub fn new(start: usize, end: usize) -> Self {
+        Span { start, end }
+    }
+
+    /// The smallest span that covers both
+    pub fn to(self, other: Span) -> Span {
+        Span::new(self.start.min(other.start), self.end.max(other.end))
+    }
+}
+
+impl fmt::Debug for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Surprise, a holder of tokens, along with the span each was read from
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct TokenSequence(Vec<Token>, Vec<Span>);
 
 impl TokenSequence {
     pub fn new() -> Self {
-        TokenSequence(Vec::new())
+        TokenSequence(Vec::new(), Vec::new())
     }
 
+    /// Holds tokens that weren't read from any input, as if each were a
+    /// single character with nothing in between
     pub fn with_tokens(tokens: Vec<Token>) -> Self {
-        TokenSequence(tokens)
+        let spans = (0..tokens.len()).map(|i| Span::new(i, i + 1)).collect();
+        TokenSequence(tokens, spans)
     }
 
-    fn last(&self) -> Option<&Token> {
-        let len = self.0.len();
+    fn add(&mut self, t: Token, span: Span) {
+        self.0.push(t);
+        self.1.push(span);
+    }
 
-        if len == 0 {
-            None
-        } else {
-            Some(&self.0[len - 1])
-        }
+    pub fn iter(&self) -> slice::Iter<'_, Token> {
+        self.0.iter()
     }
 
-    fn add(&mut self, t: Token) {
-        self.0.push(t);
+    /// Every token along with the span it was read from
+    pub fn spanned(&self) -> Zip<slice::Iter<'_, Token>, slice::Iter<'_, Span>> {
+        self.0.iter().zip(self.1.iter())
     }
 
-    pub fn iter(&self) -> slice::Iter<Token> {
-        self.0.iter()
+    pub fn tokens(&self) -> &[Token] {
+        &self.0
+    }
+
+    pub fn spans(&self) -> &[Span] {
+        &self.1
     }
 
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+/// Bits a literal in base 2, 8 or 16 can have, as many as the widest
+/// register holds
+pub const MAX_LITERAL_BITS: usize = 128;
+
+/// The smallest and largest values a register holds, which an integer
+/// literal has to be within.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiteralRange {
+    pub min: BigBinary,
+    pub max: BigBinary,
 }
 
 /// The set of possible lexer errors.
 #[derive(Debug, PartialEq)]
 pub enum LexErr {
-    InvalidCharacter(char),
+    InvalidCharacter { position: usize, chr: char },
     UnexpectedCharacter { position: usize, chr: char },
+    /// A digit that the base of its literal doesn't have, eg. `0b12`
+    InvalidDigit { position: usize, chr: char, radix: u32 },
+    /// A `0x`, `0o` or `0b` prefix without any digits after it
+    MissingDigits { span: Span, radix: u32 },
+    /// A literal with more Bits than any register holds
+    LiteralTooWide { span: Span, bits: usize },
+    /// A group of digits with too few or too many for the number format
+    InvalidGroup { span: Span, min: usize, max: usize },
+    /// An integer literal above the largest value of the register, along
+    /// with whether a unary minus comes before it
+    LiteralOutOfRange { span: Span, range: LiteralRange, negated: bool },
+}
+
+impl LexErr {
+    /// The span of the offending character
+    pub fn span(&self) -> Span {
+        match self {
+            LexErr::InvalidCharacter { position, .. }
+            | LexErr::UnexpectedCharacter { position, .. }
+            | LexErr::InvalidDigit { position, .. } => Span::new(position - 1, *position),
+            LexErr::LiteralTooWide { span, .. }
+            | LexErr::MissingDigits { span, .. }
+            | LexErr::InvalidGroup { span, .. }
+            | LexErr::LiteralOutOfRange { span, .. } => *span,
+        }
+    }
 }
+
 impl fmt::Display for LexErr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use self::LexErr::*;
 
         match self {
-            InvalidCharacter(c) => write!(f, "Invalid character: '{}'", c),
+            InvalidCharacter { position, chr } => write!(f, "Invalid character at {}: '{}'", position, chr),
             UnexpectedCharacter { position, chr } => {
                 write!(f, "Unexpected character at {}: '{}'", position, chr)
             }
+            InvalidDigit { position, chr, radix } => {
+                write!(f, "Invalid digit at {}: '{}' is not a base {} digit", position, chr, radix)
+            }
+            MissingDigits { span, radix } => {
+                write!(f, "Literal at {} has no base {} digits after its prefix", span.start + 1, radix)
+            }
+            LiteralTooWide { span, bits } => write!(f, "Literal at {} needs more than {} Bits", span.start + 1, bits),
+            InvalidGroup { span, min, max } if min == max => {
+                write!(f, "Group of digits at {} should have {} digits", span.start + 1, min)
+            }
+            InvalidGroup { span, min, max } => {
+                write!(f, "Group of digits at {} should have {} to {} digits", span.start + 1, min, max)
+            }
+            LiteralOutOfRange { span, range, .. } => {
+                write!(f, "Literal at {} is above the largest value {}", span.start + 1, range.max)
+            }
         }
     }
 }
@@ -79,64 +260,435 @@ impl error::Error for LexErr {}
 
 type LexResult = Result<TokenSequence, LexErr>;
 
-/// Receives input text and attempts to generate a valid token stream.
+/// Characters of the input along with their positions
+type CharStream<'a> = Peekable<Enumerate<Chars<'a>>>;
+
+/// Skips a `_`, which can only go between two digits of the given base
+fn skip_separator(chars: &mut CharStream<'_>, (i, c): (usize, char), follows_digit: bool, radix: u32) -> Result<(), LexErr> {
+    chars.next();
+
+    match chars.peek() {
+        Some(&(_, c2)) if follows_digit && c2.is_digit(radix) => Ok(()),
+        _ => Err(LexErr::UnexpectedCharacter { position: i + 1, chr: c }),
+    }
+}
+
+/// Takes digits of the given base up to the next character that isn't one,
+/// leaving out any `_` between them
+fn take_digits(chars: &mut CharStream<'_>, radix: u32) -> Result<String, LexErr> {
+    let mut digits = String::new();
+
+    while let Some(&(i, c)) = chars.peek() {
+        if c == '_' {
+            skip_separator(chars, (i, c), !digits.is_empty(), radix)?;
+            continue;
+        }
+
+        if !c.is_digit(radix) {
+            break;
+        }
+
+        digits.push(c);
+        chars.next();
+    }
+
+    Ok(digits)
+}
+
+/// Reports whatever follows a character that needed digits after it, or
+/// the character itself at the end of the input
+fn missing_digits(chars: &mut CharStream<'_>, (i, c): (usize, char)) -> LexErr {
+    match chars.next() {
+        Some((i, chr)) => LexErr::UnexpectedCharacter { position: i + 1, chr },
+        None => LexErr::InvalidCharacter { position: i + 1, chr: c },
+    }
+}
+
+/// Takes the whole part of a decimal literal after its first digit, which
+/// has already been taken, leaving out the separators between groups of
+/// digits once each group is found to have as many as the format expects.
+/// A separator only groups digits when another digit follows it, and
+/// otherwise ends the literal, like the comma between arguments. Among a
+/// function's arguments, a comma also ends it unless a whole group of digits
+/// follows, so that "root(27,3)" has two of them
+fn take_whole(chars: &mut CharStream<'_>, (i, c): (usize, char), format: NumberFormat, arguments: bool) -> Result<String, LexErr> {
+    let mut num = c.to_string();
+    let mut groups = vec![(Span::new(i, i + 1), 1)];
+
+    while let Some(&(i2, c2)) = chars.peek() {
+        if c2 == '_' {
+            skip_separator(chars, (i2, c2), true, 10)?;
+            continue;
+        }
+
+        if format.is_group_separator(c2) {
+            let mut ahead = chars.clone();
+            ahead.next();
+
+            let (last, inner) = format.group_sizes();
+            let group = ahead.clone().take_while(|&(_, c3)| c3.is_ascii_digit()).count();
+            let separates = arguments && c2 == ',' && group != last && group != inner;
+
+            match ahead.peek() {
+                Some(&(i3, c3)) if c3.is_ascii_digit() && !separates => {
+                    chars.next();
+                    groups.push((Span::new(i3, i3), 0));
+                    continue;
+                }
+                // Spaces can follow one another, but nothing else can
+                Some(&(i3, c3)) if c3 == c2 && !c3.is_whitespace() => {
+                    return Err(LexErr::UnexpectedCharacter { position: i3 + 1, chr: c3 });
+                }
+                _ => break,
+            }
+        }
+
+        if !c2.is_ascii_digit() {
+            break;
+        }
+
+        num.push(c2);
+        chars.next();
+
+        if let Some((span, digits)) = groups.last_mut() {
+            span.end = i2 + 1;
+            *digits += 1;
+        }
+    }
+
+    let digits: Vec<usize> = groups.iter().map(|&(_, digits)| digits).collect();
+
+    match format.check_groups(&digits) {
+        Ok(()) => Ok(num),
+        Err((group, min, max)) => Err(LexErr::InvalidGroup { span: groups[group].0, min, max }),
+    }
+}
+
+/// Reads the digits after a `0x`, `0o` or `0b` prefix, which has already
+/// been taken, as an unsigned literal of no more than `MAX_LITERAL_BITS`
+fn lex_radix(chars: &mut CharStream<'_>, start: usize, prefix: (usize, char), radix: u32, len: usize) -> Result<Token, LexErr> {
+    let digits = take_digits(chars, radix)?;
+
+    // Anything that looks like a digit but isn't one of this base would
+    // otherwise be read as the start of the next token, apart from the "i"
+    // of an imaginary literal
+    if let Some(&(i, c)) = chars.peek() {
+        let imaginary = c == 'i' && !chars.clone().nth(1).is_some_and(|(_, c2)| c2.is_ascii_alphabetic());
+
+        if (c.is_ascii_digit() || (radix == 16 && c.is_ascii_alphabetic())) && !imaginary {
+            return Err(LexErr::InvalidDigit { position: i + 1, chr: c, radix });
+        }
+    }
+
+    if digits.is_empty() {
+        return Err(LexErr::MissingDigits {
+            span: Span::new(start, prefix.0 + 1),
+            radix,
+        });
+    }
+
+    let n = match BigBinary::from_radix(&digits, radix) {
+        Some(n) => n,
+        None => return Err(LexErr::InvalidCharacter { position: start + 1, chr: '0' }),
+    };
+
+    // The sign Bit the value was given doesn't count
+    if n.width() - 1 > MAX_LITERAL_BITS {
+        let end = chars.peek().map_or(len, |&(i, _)| i);
+
+        return Err(LexErr::LiteralTooWide {
+            span: Span::new(start, end),
+            bits: MAX_LITERAL_BITS,
+        });
+    }
+
+    Ok(Token::Num(n))
+}
+
+/// Makes a number imaginary if an "i" comes right after its digits, as long
+/// as it doesn't start a word
+fn imaginary(chars: &mut CharStream<'_>, token: Token) -> Token {
+    if let Some(&(_, 'i')) = chars.peek() {
+        let mut ahead = chars.clone();
+        ahead.next();
+
+        if !ahead.peek().is_some_and(|&(_, c2)| c2.is_ascii_alphabetic()) {
+            chars.next();
+            return Token::Imaginary(Box::new(token));
+        }
+    }
+
+    token
+}
+
+/// Receives input text and attempts to generate a valid token stream, with
+/// numbers written as in en-US.
 pub fn lex(s: &str) -> LexResult {
+    lex_with(s, NumberFormat::EnUs)
+}
+
+/// Receives input text and attempts to generate a valid token stream, with
+/// numbers written in the context's format and, unless a decimal literal
+/// makes floats of every value, integer literals that fit in its register.
+pub fn lex_in(s: &str, ctx: &Context) -> LexResult {
+    let tokens = lex_with(s, ctx.format.unwrap_or(NumberFormat::EnUs))?;
+
+    let decimal = |t: &Token| match t {
+        Token::Imaginary(t) => matches!(**t, Token::Decimal(_)),
+        t => matches!(t, Token::Decimal(_)),
+    };
+
+    // A minus is unary unless it follows something it could subtract from
+    let unary = |i: usize| {
+        let operand = |t: &Token| !matches!(t, Token::Sym(s) if *s != Symbol::ParenClose);
+
+        match &tokens.tokens()[..i] {
+            [.., before, Token::Sym(Symbol::Minus)] => !operand(before),
+            [.., Token::Sym(Symbol::Minus)] => true,
+            _ => false,
+        }
+    };
+
+    if let Some(range) = ctx.literal_range().filter(|_| !tokens.iter().any(decimal)) {
+        for (i, (t, span)) in tokens.spanned().enumerate() {
+            let n = match t {
+                Token::Imaginary(t) => match &**t {
+                    Token::Num(n) => n,
+                    _ => continue,
+                },
+                Token::Num(n) => n,
+                _ => continue,
+            };
+
+            // Literals are never negative, since a minus in front of one is
+            // an operation of its own
+            if n.compare(&range.max).2 == Bit::On {
+                return Err(LexErr::LiteralOutOfRange { span: *span, range, negated: unary(i) });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Receives input text and attempts to generate a valid token stream, with
+/// numbers written in the given format.
+pub fn lex_with(s: &str, format: NumberFormat) -> LexResult {
     use self::Symbol::*;
 
     let charmap = map! {
+        '&' => Ampersand,
         '*' => Asterisk,
         '^' => Caret,
+        ',' => Comma,
         '/' => FwdSlash,
+        '>' => Greater,
+        '<' => Less,
         '-' => Minus,
         ')' => ParenClose,
         '(' => ParenOpen,
         '%' => Percent,
-        '+' => Plus
+        '|' => Pipe,
+        '+' => Plus,
+        '~' => Tilde
+    };
+
+    // Symbols of two characters, which take priority over the first
+    // character on its own
+    let pairmap = map! {
+        ('!', '=') => BangEqual,
+        ('=', '=') => EqualEqual,
+        ('>', '=') => GreaterEqual,
+        ('>', '>') => GreaterGreater,
+        ('<', '=') => LessEqual,
+        ('<', '<') => LessLess
+    };
+
+    let wordmap = map! {
+        "abs2" => Abs2,
+        "conj" => Conj,
+        "im" => Im,
+        "inv" => Inv,
+        "re" => Re,
+        "root" => Root,
+        "rotl" => Rotl,
+        "rotr" => Rotr,
+        "sqrt" => Sqrt,
+        "xor" => Xor
     };
 
     let mut tokens = TokenSequence::new();
     let mut chars = s.chars().enumerate().peekable();
 
-    while let Some((_i1, c)) = chars.next() {
+    // Where the token read so far ends, which is wherever the next one
+    // could start
+    let len = s.chars().count();
+    let end = |chars: &mut CharStream<'_>| chars.peek().map_or(len, |&(i, _)| i);
+
+    // Whether each parenthesis still open holds the arguments of a function
+    let mut calls = Vec::new();
+
+    while let Some((i1, c)) = chars.next() {
         if c.is_whitespace() {
             continue;
         }
 
+        let next = chars.peek().map(|&(_, c2)| c2);
+
+        if let Some(symbol) = next.and_then(|c2| pairmap.get(&(c, c2))) {
+            chars.next();
+
+            // ">>>" is the only symbol of three
+            if *symbol == GreaterGreater && chars.peek().map(|&(_, c3)| c3) == Some('>') {
+                chars.next();
+                tokens.add(Token::Sym(GreaterGreaterGreater), Span::new(i1, end(&mut chars)));
+            } else {
+                tokens.add(Token::Sym(*symbol), Span::new(i1, end(&mut chars)));
+            }
+            continue;
+        }
+
         if let Some(symbol) = charmap.get(&c) {
-            tokens.add(Token::Sym(symbol.clone()));
+            match symbol {
+                ParenOpen => calls.push(matches!(tokens.tokens().last(), Some(Token::Sym(s)) if s.is_function())),
+                ParenClose => {
+                    calls.pop();
+                }
+                _ => {}
+            }
+
+            tokens.add(Token::Sym(*symbol), Span::new(i1, end(&mut chars)));
+            continue;
+        }
+
+        // Characters like '=' are only valid as the start of a pair, so one
+        // that isn't followed by its second character is itself at fault
+        if pairmap.keys().any(|&(first, _)| first == c) {
+            return Err(LexErr::InvalidCharacter { position: i1 + 1, chr: c });
+        }
+
+        if c.is_ascii_alphabetic() {
+            let mut word = c.to_string();
+
+            while let Some(&(_, c2)) = chars.peek() {
+                // Digits only belong to a word that has them, like "abs2",
+                // so that "xor3" still reads as "xor 3"
+                let continues = c2.is_ascii_alphabetic()
+                    || (c2.is_ascii_digit() && wordmap.keys().any(|w| w.starts_with(&format!("{}{}", word, c2))));
+
+                if !continues {
+                    break;
+                }
+
+                word.push(chars.next().unwrap().1);
+            }
+
+            match wordmap.get(word.as_str()) {
+                Some(symbol) => tokens.add(Token::Sym(*symbol), Span::new(i1, end(&mut chars))),
+                None => return Err(LexErr::InvalidCharacter { position: i1 + 1, chr: c }),
+            }
             continue;
         }
 
-        if c.is_digit(10) {
-            let mut num = c.to_string();
-            let mut comma_last = false;
+        // A "0x", "0o" or "0b" prefix makes a literal of base 16, 8 or 2,
+        // unless it starts a word instead, like the "xor" in "0xor 1"
+        let radix = match next {
+            Some('x') => 16,
+            Some('o') => 8,
+            Some('b') => 2,
+            _ => 10,
+        };
+
+        if c == '0' && radix != 10 {
+            let mut ahead = chars.clone();
+            let prefix = ahead.next().unwrap();
+            let word = ahead.peek().is_some_and(|&(_, c3)| {
+                wordmap.keys().any(|w| w.starts_with(&format!("{}{}", prefix.1, c3)))
+            });
+
+            if !word {
+                chars.next();
+
+                let token = lex_radix(&mut chars, i1, prefix, radix, len)?;
+                let token = imaginary(&mut chars, token);
+
+                tokens.add(token, Span::new(i1, end(&mut chars)));
+                continue;
+            }
+        }
+
+        if c.is_ascii_digit() {
+            let num = take_whole(&mut chars, (i1, c), format, calls.last() == Some(&true))?;
+
+            // A decimal separator or an exponent makes it a decimal literal,
+            // either of which needs digits after it, apart from a decimal
+            // separator that is also a symbol, like the comma between
+            // arguments
+            let mut fraction = None;
+            let mut exponent = None;
 
-            while let Some(&(i2, c2)) = chars.peek() {
-                if c2 == ',' {
-                    if comma_last {
-                        return Err(LexErr::UnexpectedCharacter {
-                            position: i2 + 1,
-                            chr: c2,
-                        });
+            if let Some(&point) = chars.peek().filter(|&&(_, c2)| c2 == format.decimal_separator()) {
+                let mut ahead = chars.clone();
+                ahead.next();
+
+                if !charmap.contains_key(&point.1) || ahead.peek().is_some_and(|&(_, c3)| c3.is_ascii_digit()) {
+                    chars.next();
+
+                    match take_digits(&mut chars, 10)? {
+                        digits if digits.is_empty() => return Err(missing_digits(&mut chars, point)),
+                        digits => fraction = Some(digits),
                     }
+                }
+            }
 
+            if let Some(&e @ (_, 'e')) | Some(&e @ (_, 'E')) = chars.peek() {
+                chars.next();
+
+                let mut last = e;
+                let mut digits = String::new();
+
+                if let Some(&sign @ (_, '+')) | Some(&sign @ (_, '-')) = chars.peek() {
                     chars.next();
-                    comma_last = true;
-                    continue;
+                    last = sign;
+                    digits.push(sign.1);
                 }
 
-                if !c2.is_digit(10) {
-                    break;
+                match take_digits(&mut chars, 10)? {
+                    more if more.is_empty() => return Err(missing_digits(&mut chars, last)),
+                    more => digits.push_str(&more),
                 }
 
-                num.push(chars.next().unwrap().1);
-                comma_last = false;
+                exponent = Some(digits);
             }
-            tokens.add(Token::Num(num.parse::<i64>().unwrap()));
+
+            let token = if fraction.is_none() && exponent.is_none() {
+                BigBinary::from_decimal(&num).map(Token::Num)
+            } else {
+                Decimal::from_parts(
+                    &num,
+                    &fraction.unwrap_or_default(),
+                    &exponent.unwrap_or_default(),
+                ).map(Token::Decimal)
+            };
+
+            // Every digit has already been checked, so only a value too
+            // long to build is left
+            let token = match token {
+                Some(token) => token,
+                None => {
+                    return Err(LexErr::LiteralTooWide {
+                        span: Span::new(i1, end(&mut chars)),
+                        bits: BigBinary::MAX_BITS,
+                    })
+                }
+            };
+
+            let token = imaginary(&mut chars, token);
+            tokens.add(token, Span::new(i1, end(&mut chars)));
             continue;
         }
 
-        return Err(LexErr::InvalidCharacter(c));
+        return Err(LexErr::InvalidCharacter { position: i1 + 1, chr: c });
     }
 
     Ok(tokens)
@@ -151,45 +703,50 @@ mod tests {
         use super::Symbol::*;
         use super::Token::*;
 
-        let assert = |s: &str, v: Vec<Token>| assert_eq!(lex(s).unwrap(), TokenSequence(v),);
+        let num = |n: i64| Num(BigBinary::from_int(n));
+
+        let assert = |s: &str, v: Vec<Token>| assert_eq!(lex(s).unwrap().tokens(), v);
 
         assert("", vec![]);
         assert("     \t\n    ", vec![]);
 
-        assert("123\t432      ", vec![Num(123), Num(432)]);
+        assert("123\t432      ", vec![num(123), num(432)]);
 
-        assert("1 + 2 3 ( *", vec![Num(123), Num(432)]);
+        assert(
+            "1 + 2 3 ( *",
+            vec![num(1), Sym(Plus), num(2), num(3), Sym(ParenOpen), Sym(Asterisk)],
+        );
 
         assert(
             "1234567 7890 5432",
-            vec![Num(1234567), Num(7890), Num(5432)],
+            vec![num(1234567), num(7890), num(5432)],
         );
 
-        assert("0 -0", vec![Num(0), Sym(Minus), Num(0)]);
+        assert("0 -0", vec![num(0), Sym(Minus), num(0)]);
 
-        assert("5 - -4", vec![Num(5), Sym(Minus), Sym(Minus), Num(4)]);
+        assert("5 - -4", vec![num(5), Sym(Minus), Sym(Minus), num(4)]);
 
-        assert("5 + 4", vec![Num(5), Sym(Plus), Num(4)]);
+        assert("5 + 4", vec![num(5), Sym(Plus), num(4)]);
 
         assert(
             "5 + 4*(-2/      0)",
             vec![
-                Num(5),
+                num(5),
                 Sym(Plus),
-                Num(4),
+                num(4),
                 Sym(Asterisk),
                 Sym(ParenOpen),
                 Sym(Minus),
-                Num(2),
+                num(2),
                 Sym(FwdSlash),
-                Num(0),
+                num(0),
                 Sym(ParenClose),
             ],
         );
 
         assert(
             "5 + -12,192,293",
-            vec![Num(5), Sym(Plus), Sym(Minus), Num(12_192_293)],
+            vec![num(5), Sym(Plus), Sym(Minus), num(12_192_293)],
         );
 
         assert(
@@ -205,6 +762,321 @@ mod tests {
                 Sym(Plus),
             ],
         );
+
+        assert(
+            "& | ~ << >> >>> xor rotl rotr",
+            vec![
+                Sym(Ampersand),
+                Sym(Pipe),
+                Sym(Tilde),
+                Sym(LessLess),
+                Sym(GreaterGreater),
+                Sym(GreaterGreaterGreater),
+                Sym(Xor),
+                Sym(Rotl),
+                Sym(Rotr),
+            ],
+        );
+
+        assert(">>>>>", vec![Sym(GreaterGreaterGreater), Sym(GreaterGreater)]);
+
+        assert(
+            "root(1,000, 3)+sqrt(4),",
+            vec![
+                Sym(Root),
+                Sym(ParenOpen),
+                num(1000),
+                Sym(Comma),
+                num(3),
+                Sym(ParenClose),
+                Sym(Plus),
+                Sym(Sqrt),
+                Sym(ParenOpen),
+                num(4),
+                Sym(ParenClose),
+                Sym(Comma),
+            ],
+        );
+
+        assert(
+            "< <= > >= == != <<",
+            vec![
+                Sym(Less),
+                Sym(LessEqual),
+                Sym(Greater),
+                Sym(GreaterEqual),
+                Sym(EqualEqual),
+                Sym(BangEqual),
+                Sym(LessLess),
+            ],
+        );
+
+        assert(
+            "(255&6)<<4xor1",
+            vec![
+                Sym(ParenOpen),
+                num(255),
+                Sym(Ampersand),
+                num(6),
+                Sym(ParenClose),
+                Sym(LessLess),
+                num(4),
+                Sym(Xor),
+                num(1),
+            ],
+        );
+    }
+
+    #[test]
+    fn test_lex_decimal() {
+        let decimal = |digits: i64, exponent: i64| {
+            Token::Decimal(Decimal::new(BigBinary::from_int(digits), BigBinary::from_int(exponent)))
+        };
+
+        assert_eq!(
+            lex("1.5e-3 + 2E10-0.25,1e+2").unwrap().tokens(),
+            vec![
+                decimal(15, -4),
+                Token::Sym(Symbol::Plus),
+                decimal(2, 10),
+                Token::Sym(Symbol::Minus),
+                decimal(25, -2),
+                Token::Sym(Symbol::Comma),
+                decimal(1, 2),
+            ]
+        );
+
+        assert_eq!(lex("1,000.5").unwrap().tokens(), vec![decimal(10005, -1)]);
+    }
+
+    #[test]
+    fn test_lex_imaginary() {
+        let imaginary = |t: Token| Token::Imaginary(Box::new(t));
+        let num = |n: i64| Token::Num(BigBinary::from_int(n));
+
+        assert_eq!(
+            lex("3+4i - 0.5i*re(2) + abs2(1i)").unwrap().tokens(),
+            vec![
+                num(3),
+                Token::Sym(Symbol::Plus),
+                imaginary(num(4)),
+                Token::Sym(Symbol::Minus),
+                imaginary(Token::Decimal(Decimal::new(BigBinary::from_int(5), BigBinary::from_int(-1)))),
+                Token::Sym(Symbol::Asterisk),
+                Token::Sym(Symbol::Re),
+                Token::Sym(Symbol::ParenOpen),
+                num(2),
+                Token::Sym(Symbol::ParenClose),
+                Token::Sym(Symbol::Plus),
+                Token::Sym(Symbol::Abs2),
+                Token::Sym(Symbol::ParenOpen),
+                imaginary(num(1)),
+                Token::Sym(Symbol::ParenClose),
+            ]
+        );
+
+        assert_eq!(
+            lex("conj(2) im xor3").unwrap().tokens(),
+            vec![
+                Token::Sym(Symbol::Conj),
+                Token::Sym(Symbol::ParenOpen),
+                num(2),
+                Token::Sym(Symbol::ParenClose),
+                Token::Sym(Symbol::Im),
+                Token::Sym(Symbol::Xor),
+                num(3),
+            ]
+        );
+
+        // Only a lone "i" makes a number imaginary
+        assert_eq!(lex("2 i"), Err(LexErr::InvalidCharacter { position: 3, chr: 'i' }));
+        assert_eq!(lex("2im").unwrap().tokens(), vec![num(2), Token::Sym(Symbol::Im)]);
+    }
+
+    #[test]
+    fn test_lex_radix() {
+        use self::LexErr::*;
+
+        let num = |n: i128| Token::Num(BigBinary::from_int(n));
+
+        assert_eq!(
+            lex("0b1010_0101 + 0xFF_ff - 0o17, 0x0i").unwrap().tokens(),
+            vec![
+                num(0b1010_0101),
+                Token::Sym(Symbol::Plus),
+                num(0xffff),
+                Token::Sym(Symbol::Minus),
+                num(0o17),
+                Token::Sym(Symbol::Comma),
+                Token::Imaginary(Box::new(num(0))),
+            ]
+        );
+        assert_eq!(lex("1_000_000 + 2.5_0").unwrap().tokens()[0], num(1_000_000));
+        assert_eq!(lex("0xor 1").unwrap().tokens(), vec![num(0), Token::Sym(Symbol::Xor), num(1)]);
+
+        assert_eq!(
+            lex(&format!("0x{}", "f".repeat(32))).unwrap().tokens(),
+            vec![Token::Num(BigBinary::from_radix(&"f".repeat(32), 16).unwrap())]
+        );
+        assert_eq!(
+            lex(&format!("1 + 0b1{}", "0".repeat(128))),
+            Err(LiteralTooWide { span: Span::new(4, 135), bits: 128 })
+        );
+        assert_eq!(
+            lex(&format!("1 + 1{}", "0".repeat(1300))),
+            Err(LiteralTooWide { span: Span::new(4, 1305), bits: BigBinary::MAX_BITS })
+        );
+
+        assert_eq!(lex("0b1012"), Err(InvalidDigit { position: 6, chr: '2', radix: 2 }));
+        assert_eq!(lex("0o78"), Err(InvalidDigit { position: 4, chr: '8', radix: 8 }));
+        assert_eq!(lex("0xFG"), Err(InvalidDigit { position: 4, chr: 'G', radix: 16 }));
+        assert_eq!(lex("0x"), Err(MissingDigits { span: Span::new(0, 2), radix: 16 }));
+        assert_eq!(lex("1 + 0b + 1"), Err(MissingDigits { span: Span::new(4, 6), radix: 2 }));
+        assert_eq!(lex("0oi"), Err(MissingDigits { span: Span::new(0, 2), radix: 8 }));
+        assert_eq!(lex("0x_1"), Err(UnexpectedCharacter { position: 3, chr: '_' }));
+        assert_eq!(lex("0b1__0"), Err(UnexpectedCharacter { position: 4, chr: '_' }));
+        assert_eq!(lex("10_ + 1"), Err(UnexpectedCharacter { position: 3, chr: '_' }));
+        assert_eq!(lex("1._5"), Err(UnexpectedCharacter { position: 3, chr: '_' }));
+    }
+
+    #[test]
+    fn test_lex_formats() {
+        use self::LexErr::*;
+        use crate::format::NumberFormat::*;
+
+        let num = |n: i64| Token::Num(BigBinary::from_int(n));
+        let decimal = |digits: i64, exponent: i64| {
+            Token::Decimal(Decimal::new(BigBinary::from_int(digits), BigBinary::from_int(exponent)))
+        };
+
+        assert_eq!(lex("1,234.5").unwrap().tokens(), vec![decimal(12345, -1)]);
+        assert_eq!(lex_with("1.234,5", DeDe).unwrap().tokens(), vec![decimal(12345, -1)]);
+        assert_eq!(lex_with("1 234,5", Fr).unwrap().tokens(), vec![decimal(12345, -1)]);
+        assert_eq!(lex_with("1\u{202f}234\u{a0}567", Fr).unwrap().tokens(), vec![num(1_234_567)]);
+        assert_eq!(lex_with("12,34,567", Indian).unwrap().tokens(), vec![num(1_234_567)]);
+        assert_eq!(lex_with("1,00,00,000.5", Indian).unwrap().tokens(), vec![decimal(100_000_005, -1)]);
+
+        assert_eq!(
+            lex("root(27,3) + root(1,000,000, 3)").unwrap().tokens(),
+            vec![
+                Token::Sym(Symbol::Root),
+                Token::Sym(Symbol::ParenOpen),
+                num(27),
+                Token::Sym(Symbol::Comma),
+                num(3),
+                Token::Sym(Symbol::ParenClose),
+                Token::Sym(Symbol::Plus),
+                Token::Sym(Symbol::Root),
+                Token::Sym(Symbol::ParenOpen),
+                num(1_000_000),
+                Token::Sym(Symbol::Comma),
+                num(3),
+                Token::Sym(Symbol::ParenClose),
+            ]
+        );
+        assert_eq!(lex("(27,3)"), Err(InvalidGroup { span: Span::new(4, 5), min: 3, max: 3 }));
+
+        // A decimal comma with no digits after it separates arguments
+        assert_eq!(
+            lex_with("root(8,5, 3)", DeDe).unwrap().tokens(),
+            vec![
+                Token::Sym(Symbol::Root),
+                Token::Sym(Symbol::ParenOpen),
+                decimal(85, -1),
+                Token::Sym(Symbol::Comma),
+                num(3),
+                Token::Sym(Symbol::ParenClose),
+            ]
+        );
+        assert_eq!(
+            lex_with("2 + 1 000  - 3", Fr).unwrap().tokens(),
+            vec![num(2), Token::Sym(Symbol::Plus), num(1000), Token::Sym(Symbol::Minus), num(3)]
+        );
+
+        assert_eq!(lex("1,2,3"), Err(InvalidGroup { span: Span::new(2, 3), min: 3, max: 3 }));
+        assert_eq!(lex("1234,567"), Err(InvalidGroup { span: Span::new(0, 4), min: 1, max: 3 }));
+        assert_eq!(lex("12,34,567"), Err(InvalidGroup { span: Span::new(3, 5), min: 3, max: 3 }));
+        assert_eq!(lex_with("1,234,567", Indian), Err(InvalidGroup { span: Span::new(2, 5), min: 2, max: 2 }));
+        assert_eq!(lex_with("1.5", DeDe), Err(InvalidGroup { span: Span::new(2, 3), min: 3, max: 3 }));
+        assert_eq!(lex_with("1,5", Fr).unwrap().tokens(), vec![decimal(15, -1)]);
+        assert_eq!(lex_with("1.5", Fr), Err(InvalidCharacter { position: 2, chr: '.' }));
+    }
+
+    #[test]
+    fn test_lex_range() {
+        use crate::parser::context::{Mode, Signedness, Width};
+
+        let ctx = Context::with_width(Width::W8);
+        let range = LiteralRange {
+            min: BigBinary::from_int(-128),
+            max: BigBinary::from_int(127),
+        };
+
+        assert_eq!(lex_in("127 + 0x7f", &ctx).unwrap().tokens(), lex("127 + 127").unwrap().tokens());
+        assert_eq!(
+            lex_in("1 - 128", &ctx),
+            Err(LexErr::LiteralOutOfRange { span: Span::new(4, 7), range: range.clone(), negated: false })
+        );
+        assert_eq!(
+            lex_in("2 * 0b1000_0000i", &ctx),
+            Err(LexErr::LiteralOutOfRange { span: Span::new(4, 16), range: range.clone(), negated: false })
+        );
+        assert_eq!(
+            lex_in("2 * (-128)", &ctx),
+            Err(LexErr::LiteralOutOfRange { span: Span::new(6, 9), range: range.clone(), negated: true })
+        );
+        assert_eq!(
+            lex_in("(1) -128", &ctx),
+            Err(LexErr::LiteralOutOfRange { span: Span::new(5, 8), range, negated: false })
+        );
+        assert_eq!(
+            lex_in("256", &Context { signedness: Signedness::Unsigned, ..ctx.clone() }),
+            Err(LexErr::LiteralOutOfRange {
+                span: Span::new(0, 3),
+                range: LiteralRange { min: BigBinary::zero(), max: BigBinary::from_int(255) },
+                negated: false,
+            })
+        );
+        assert_eq!(
+            lex_in("99999999999999999999", &Context::new()).err().unwrap().to_string(),
+            "Literal at 1 is above the largest value 9223372036854775807"
+        );
+
+        // Literals that don't fit are let through when they wouldn't be an
+        // error, or when a decimal makes floats of every value
+        assert!(lex_in("300", &Context { mode: Mode::Wrapping, ..ctx.clone() }).is_ok());
+        assert!(lex_in("300", &Context::with_width(Width::Unbounded)).is_ok());
+        assert!(lex_in("300 + 0.5", &ctx).is_ok());
+    }
+
+    #[test]
+    fn test_lex_spans() {
+        let spans = |s: &str| {
+            let spans: Vec<_> = lex(s).unwrap().spans().iter().map(|span| (span.start, span.end)).collect();
+            spans
+        };
+
+        assert_eq!(spans("12 + 3"), vec![(0, 2), (3, 4), (5, 6)]);
+        assert_eq!(spans("1>>>2 >= 3"), vec![(0, 1), (1, 4), (4, 5), (6, 8), (9, 10)]);
+        assert_eq!(spans(" sqrt(1,000)"), vec![(1, 5), (5, 6), (6, 11), (11, 12)]);
+        assert_eq!(spans("1.5e-3 4i"), vec![(0, 6), (7, 9)]);
+
+        // Spans count characters rather than bytes
+        assert_eq!(spans("1 \u{3000}+ 2"), vec![(0, 1), (3, 4), (5, 6)]);
+    }
+
+    #[test]
+    fn test_lex_wide_literal() {
+        let tokens = lex("170141183460469231731687303715884105727 + 1").unwrap();
+
+        assert_eq!(
+            tokens.tokens(),
+            vec![
+                Token::Num(BigBinary::from_int(i128::MAX)),
+                Token::Sym(Symbol::Plus),
+                Token::Num(BigBinary::one()),
+            ]
+        );
     }
 
     #[test]
@@ -213,12 +1085,23 @@ mod tests {
 
         let e = lex("x").err().unwrap();
 
-        assert_eq!(e, InvalidCharacter('x'));
-        assert_eq!(e.to_string(), String::from("Invalid character: 'x'"));
+        assert_eq!(e, InvalidCharacter { position: 1, chr: 'x' });
+        assert_eq!(e.to_string(), String::from("Invalid character at 1: 'x'"));
 
         let e = lex("5asdf").err().unwrap();
 
-        assert_eq!(e, InvalidCharacter('a'));
+        assert_eq!(e, InvalidCharacter { position: 2, chr: 'a' });
+
+        assert_eq!(lex("5 xo 3").err().unwrap(), InvalidCharacter { position: 3, chr: 'x' });
+        assert_eq!(lex("5 =").err().unwrap(), InvalidCharacter { position: 3, chr: '=' });
+        assert_eq!(lex("5 = 3").err().unwrap(), InvalidCharacter { position: 3, chr: '=' });
+        assert_eq!(lex("5 =3").err().unwrap(), InvalidCharacter { position: 3, chr: '=' });
+        assert_eq!(lex("5 ! 3").err().unwrap(), InvalidCharacter { position: 3, chr: '!' });
+
+        assert_eq!(lex("1.").err().unwrap(), InvalidCharacter { position: 2, chr: '.' });
+        assert_eq!(lex("1.x").err().unwrap(), UnexpectedCharacter { position: 3, chr: 'x' });
+        assert_eq!(lex("2e+").err().unwrap(), InvalidCharacter { position: 3, chr: '+' });
+        assert_eq!(lex("2e 3").err().unwrap(), UnexpectedCharacter { position: 3, chr: ' ' });
 
         let e = lex("5 + -12,192,,293").err().unwrap();
 
@@ -16,12 +16,54 @@ macro_rules! map {
     }};
 }
 
+pub mod diagnostics;
+pub mod format;
 pub mod lexer;
 pub mod parser;
 pub mod types;
 
 use types::{Binary, ParseResult};
 
+fn binaries(x: i64, y: i64) -> (Binary, Binary) {
+    (Binary::from_int(x), Binary::from_int(y))
+}
+
+pub fn add(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    (&x + &y).to_int()
+}
+
+pub fn subtract(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    (&x - &y).to_int()
+}
+
+pub fn multiply(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    (&x * &y).to_int()
+}
+
+pub fn divide(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    (&x / &y).to_int()
+}
+
+pub fn modulo(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    (&x % &y).to_int()
+}
+
+pub fn exponentiate(x: i64, y: i64) -> ParseResult {
+    let (x, y) = binaries(x, y);
+
+    x.pow(&y).to_int()
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -36,7 +78,7 @@ mod tests {
             (1, 200000),
             (123321, 192392),
             (98498239, 1238723),
-            (0, ::std::i64::MAX),
+            (0, i64::MAX),
             (-1, 0),
             (0, -1),
             (0, -100),
@@ -49,12 +91,12 @@ mod tests {
             (-1, -200000),
             (-123321, -192392),
             (-98498239, 1238723),
-            (0, ::std::i64::MIN),
-            (1, ::std::i64::MIN),
+            (0, i64::MIN),
+            (1, i64::MIN),
             (123, -32),
             (583, -91283),
             (-912389, 49823),
-            (::std::i64::MIN, ::std::i64::MAX),
+            (i64::MIN, i64::MAX),
         ];
 
         for (x, y) in args.iter() {
@@ -73,7 +115,7 @@ mod tests {
             (1, 200000),
             (123321, 192392),
             (98498239, 1238723),
-            (1, ::std::i64::MAX),
+            (1, i64::MAX),
             (0, -1),
             (0, -100),
             (0, -500),
@@ -85,8 +127,8 @@ mod tests {
             (-1, -200000),
             (-123321, -192392),
             (-98498239, 1238723),
-            (-1, ::std::i64::MIN),
-            (2, ::std::i64::MAX),
+            (-1, i64::MIN),
+            (2, i64::MAX),
             (123, -32),
             (583, -91283),
             (-912389, 49823),
@@ -146,4 +188,52 @@ mod tests {
             assert_eq!(super::divide(*x, *y), Ok(x / y), "{} * {}", x, y);
         }
     }
+
+    #[test]
+    fn modulo_test() {
+        let args = [
+            (10, 4),
+            (4, 2),
+            (123, 13),
+            (13, 123),
+            (0, 1),
+            (0, -1),
+            (-1, 1),
+            (-10, 3),
+            (10, -3),
+            (-10, -3),
+            (1023, 983298),
+            (983298, 1023),
+            (-9183032, 9283),
+            (-98329838, -29389238),
+            (i64::MIN, 7),
+            (i64::MAX, -7),
+            (i64::MIN, i64::MAX),
+            (7, i64::MIN),
+        ];
+
+        for (x, y) in args.iter() {
+            assert_eq!(super::modulo(*x, *y), Ok(x % y), "{} % {}", x, y);
+        }
+    }
+
+    #[test]
+    fn exponentiate_test() {
+        let args = [
+            (0, 0),
+            (0, 1),
+            (1, 0),
+            (2, 10),
+            (-2, 11),
+            (10, 18),
+            (-7, 4),
+            (3, 39),
+            (1, 123456),
+            (-1, 123457),
+        ];
+
+        for (x, y) in args.iter() {
+            assert_eq!(super::exponentiate(*x, *y), Ok(i64::pow(*x, *y as u32)), "{} ^ {}", x, y);
+        }
+    }
 }
@@ -1,38 +1,183 @@
 extern crate rustycalc;
 
-use rustycalc::{lexer, parser};
-use rustycalc::types::Operation;
+use rustycalc::{diagnostics, lexer, parser};
+use rustycalc::parser::context::{Context, Domain, RationalOutput};
+use rustycalc::types::Value;
 use std::io;
 use std::io::Write;
+use std::process::ExitCode;
 
-fn main() {
-    let args: Vec<_> = std::env::args().collect();
-
-    if args.len() < 2 {
-        println!(
-            "A Rusty command-line calculator
+const USAGE: &str = "A Rusty command-line calculator
 
 Usage:
-    calc -i                # Open interactive prompt
-    calc '<expression>'    # Calculate and print result of provided expression"
-        );
-        return;
+    calc [options] -i                # Open interactive prompt
+    calc [options] '<expression>'    # Calculate and print result of provided expression
+    calc [options] -- '<expression>' # Same, where the expression may look like an option, eg. '--5'
+
+Options:
+    --mode <wrapping|checked|saturating>    How results that overflow are handled (default: checked)
+    --width <8|16|32|64|128|big>            Number of bits in every register, where big grows as needed (default: 64)
+    --sign <signed|unsigned>                Whether the top bit of a register is a sign bit (default: signed)
+    --adder <ripple|lookahead|select|kogge-stone>
+                                            Adder circuit used to add, subtract and negate (default: ripple)
+    --multiplier <array|booth2|booth4|wallace>
+                                            Multiplier circuit used to multiply (default: array)
+    --divider <restoring|non-restoring|srt>
+                                            Divider circuit used for division and remainders (default: restoring)
+    --fixed <Qm.n>                          Qm.n fixed-point format of m + n bits, eg. Q16.16 or Q1.31 (default: none)
+    --rational <fraction|decimal>           Exact fractions in lowest terms, optionally followed by their
+                                            decimal expansion (default: none)
+    --domain <real|complex>                 Whether values are real or complex, written eg. 3+4i (default: real)
+    --mod <modulus>                         Reduce every integer result modulo a value of at least 2 (default: none)
+    --format <en-US|de-DE|fr|en-IN>         How numbers are grouped and written, eg. 1,234.5, 1.234,5, 1 234,5 or
+                                            12,34,567, also taken from CALC_NUMBER_FORMAT (default: none, reading
+                                            en-US and writing results without grouping)
+
+Interactive commands:
+    mod <modulus|off>                       Reduce every result modulo a value from now on, or stop doing so
+    q                                       Quit";
+
+/// Names of the options that take a value, where any other argument starts
+/// the expression unless it looks like an option itself
+const OPTIONS: [&str; 11] = [
+    "--mode", "--width", "--sign", "--adder", "--multiplier", "--divider",
+    "--fixed", "--rational", "--domain", "--mod", "--format",
+];
+
+/// How many places of a decimal expansion are written out before cutting it
+/// off, if its digits haven't ended or started repeating by then
+const DECIMAL_PLACES: usize = 50;
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads the options and then evaluates the expression or opens the prompt,
+/// giving back whatever went wrong as the message to print
+fn run() -> Result<(), String> {
+    let args: Vec<_> = std::env::args().skip(1).collect();
+    let mut ctx = Context::new();
+
+    // The environment picks a number format that an option can override
+    if let Ok(value) = std::env::var("CALC_NUMBER_FORMAT") {
+        match value.parse() {
+            Ok(format) => ctx.format = Some(format),
+            Err(e) => return Err(format!("Error: {} in CALC_NUMBER_FORMAT", e)),
+        }
+    }
+
+    let rest = read_options(&args, &mut ctx)?;
+
+    check(&ctx).map_err(|e| format!("Error: {}", e))?;
+
+    if rest.is_empty() {
+        println!("{}", USAGE);
+        return Ok(());
+    }
+
+    match rest[0].as_ref() {
+        "-i" => {
+            interact(ctx);
+            Ok(())
+        }
+        _ => evaluate(&rest.join(" "), &ctx),
+    }
+}
+
+/// Applies the options at the start of the arguments to a Context, giving
+/// back the arguments that are left after them
+fn read_options<'a>(args: &'a [String], ctx: &mut Context) -> Result<&'a [String], String> {
+    let mut rest = args.iter();
+
+    // Options come before the expression, which may itself start with '-',
+    // so anything that doesn't look like an option ends them
+    while let Some(arg) = rest.as_slice().first() {
+        if arg == "--" {
+            rest.next();
+            break;
+        }
+
+        if !OPTIONS.contains(&arg.as_ref()) {
+            // A misspelt option would otherwise be read as the expression
+            if arg.starts_with("--") && arg[2..].starts_with(|c: char| c.is_ascii_alphabetic()) {
+                return Err(format!("Error: Unknown option '{}'", arg));
+            }
+
+            break;
+        }
+
+        rest.next();
+
+        let value = match rest.next() {
+            Some(value) => value,
+            None => return Err(format!("Error: Option '{}' needs a value", arg)),
+        };
+
+        let result = match arg.as_ref() {
+            "--mode" => value.parse().map(|mode| ctx.mode = mode),
+            "--width" => value.parse().map(|width| ctx.width = width),
+            "--sign" => value.parse().map(|signedness| ctx.signedness = signedness),
+            "--adder" => value.parse().map(|adder| ctx.adder = adder),
+            "--multiplier" => value.parse().map(|multiplier| ctx.multiplier = multiplier),
+            "--divider" => value.parse().map(|divider| ctx.divider = divider),
+            "--fixed" => value.parse().map(|fixed| ctx.fixed = Some(fixed)),
+            "--rational" => value.parse().map(|output| ctx.rational = Some(output)),
+            "--domain" => value.parse().map(|domain| ctx.domain = domain),
+            "--mod" => value.parse().map(|modulus| ctx.modulus = Some(modulus)),
+            "--format" => value.parse().map(|format| ctx.format = Some(format)),
+            _ => unreachable!(),
+        };
+
+        result.map_err(|e| format!("Error: {}", e))?;
     }
 
-    match args[1].as_ref() {
-        "-i" => interact(),
-        _ => evaluate(&args[1..].join(" ")),
+    Ok(rest.as_slice())
+}
+
+/// Turns away settings that don't work together, since residues are only
+/// ever integers
+fn check(ctx: &Context) -> Result<(), String> {
+    if ctx.modulus.is_some() && (ctx.fixed.is_some() || ctx.rational.is_some() || ctx.domain == Domain::Complex) {
+        return Err("A modulus only works with integers in the real domain".to_string());
     }
+
+    Ok(())
 }
 
-fn interact() {
+fn interact(mut ctx: Context) {
     println!("--Kevin's Rusty Calculator--");
 
     loop {
         match prompt().as_ref() {
             "q" => std::process::exit(0),
             "hi" => println!("Hello!"),
-            input => evaluate(input),
+            "mod off" => ctx.modulus = None,
+            input if input.starts_with("mod ") => {
+                let modulus = input["mod ".len()..].trim().parse();
+                let result = modulus.and_then(|modulus| {
+                    let updated = Context {
+                        modulus: Some(modulus),
+                        ..ctx.clone()
+                    };
+                    check(&updated).map(|_| updated)
+                });
+
+                match result {
+                    Ok(updated) => ctx = updated,
+                    Err(e) => eprintln!("Error: {}", e),
+                }
+            }
+            input => {
+                if let Err(e) = evaluate(input, &ctx) {
+                    eprintln!("{}", e);
+                }
+            }
         }
     }
 }
@@ -47,13 +192,59 @@ fn prompt() -> String {
     input.trim().to_string()
 }
 
-fn evaluate(input: &str) {
-    let tokens = lexer::lex(input).unwrap();
-    let expression = parser::parse(&tokens).unwrap();
-    let result = expression.evaluate();
+/// Prints the result of an expression, or gives back the rendered diagnostic
+/// for whatever stopped it
+fn evaluate(input: &str, ctx: &Context) -> Result<(), String> {
+    let tokens = lexer::lex_in(input, ctx).map_err(|e| diagnostics::render(&e, input, ctx))?;
+    let expression = parser::parse(&tokens).map_err(|e| diagnostics::render(&e, input, ctx))?;
+    let result = expression.evaluate(ctx).map_err(|e| diagnostics::render(&e, input, ctx))?;
+
+    println!("{}", written(&result.to_string(), ctx));
+
+    if let (Value::Rational(r), Some(RationalOutput::Decimal)) = (&result, ctx.rational) {
+        println!("= {}", written(&r.to_decimal(DECIMAL_PLACES), ctx));
+    }
+
+    if ctx.additions.get() > 0 {
+        println!("({} partial-product additions)", ctx.additions.get());
+    }
+
+    Ok(())
+}
+
+/// Writes a result in the number format, if there is one
+fn written(result: &str, ctx: &Context) -> String {
+    match ctx.format {
+        Some(format) => format.format(result),
+        None => result.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rustycalc::parser::context::Mode;
 
-    println!("{:?}", tokens);
-    println!("{:?}", expression);
-    println!("{:?}", result);
-    println!("\n{:?}", result.to_int());
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_read_options() {
+        let mut ctx = Context::new();
+        let given = args(&["--mode", "wrapping", "1 + 2"]);
+        assert_eq!(read_options(&given, &mut ctx), Ok(&given[2..]));
+        assert_eq!(ctx.mode, Mode::Wrapping);
+
+        let given = args(&["--wdith", "8", "1 + 2"]);
+        assert_eq!(read_options(&given, &mut ctx), Err("Error: Unknown option '--wdith'".to_string()));
+
+        // Only "--" lets an expression start with what looks like an option
+        let given = args(&["--", "--wdith"]);
+        assert_eq!(read_options(&given, &mut ctx), Ok(&given[1..]));
+
+        let given = args(&["--5", "-i"]);
+        assert_eq!(read_options(&given, &mut ctx), Ok(&given[..]));
+        assert_eq!(read_options(&args(&["--width"]), &mut ctx), Err("Error: Option '--width' needs a value".to_string()));
+    }
 }
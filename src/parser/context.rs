@@ -0,0 +1,372 @@
+use std::cell::Cell;
+use std::str::FromStr;
+
+use crate::format::NumberFormat;
+use crate::lexer::LiteralRange;
+use crate::types::{AdderKind, BigBinary, Bit, DividerKind, MultiplierKind, Shift};
+
+/// How a result that doesn't fit in a Binary is handled, as reported by
+/// the overflow flag of the circuit that produced it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    /// Keep whatever Bits the circuit produced
+    Wrapping,
+    /// Report the overflow as an error
+    Checked,
+    /// Clamp to the largest or smallest representable value
+    Saturating,
+}
+
+impl FromStr for Mode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Mode::*;
+
+        Ok(match s {
+            "wrapping" => Wrapping,
+            "checked" => Checked,
+            "saturating" => Saturating,
+            _ => return Err(format!("Unknown arithmetic mode '{}'", s)),
+        })
+    }
+}
+
+/// The number of Bits in the register an expression is evaluated with, or
+/// no fixed number at all.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Width {
+    W8,
+    W16,
+    W32,
+    W64,
+    W128,
+    Unbounded,
+}
+
+impl Width {
+    /// The number of Bits in the register, or None if it grows as needed
+    pub fn bits(&self) -> Option<usize> {
+        match self {
+            Width::W8 => Some(8),
+            Width::W16 => Some(16),
+            Width::W32 => Some(32),
+            Width::W64 => Some(64),
+            Width::W128 => Some(128),
+            Width::Unbounded => None,
+        }
+    }
+}
+
+impl FromStr for Width {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Width::*;
+
+        Ok(match s {
+            "8" => W8,
+            "16" => W16,
+            "32" => W32,
+            "64" => W64,
+            "128" => W128,
+            "big" => Unbounded,
+            _ => return Err(format!("Unsupported width '{}'", s)),
+        })
+    }
+}
+
+/// Whether the top Bit of a register is read as a sign, as in two's
+/// complement, or as part of the value.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Signedness {
+    Signed,
+    Unsigned,
+}
+
+impl FromStr for Signedness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Signedness::*;
+
+        Ok(match s {
+            "signed" => Signed,
+            "unsigned" => Unsigned,
+            _ => return Err(format!("Unknown signedness '{}'", s)),
+        })
+    }
+}
+
+/// A Qm.n fixed-point format, where a register of m + n Bits holds a value
+/// scaled by two to the power of n, with the sign counted among the m
+/// integer Bits when there is one, eg. Q16.16 or Q1.31.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FixedFormat {
+    pub integer: usize,
+    pub fraction: usize,
+}
+
+impl FixedFormat {
+    /// The width of the register the format fills
+    pub fn width(&self) -> Width {
+        match self.integer + self.fraction {
+            8 => Width::W8,
+            16 => Width::W16,
+            32 => Width::W32,
+            64 => Width::W64,
+            _ => Width::W128,
+        }
+    }
+
+    /// Scales an integer up by the fraction Bits, to the value the format
+    /// holds it as
+    pub fn scale(&self, n: &BigBinary) -> BigBinary {
+        let (scaled, _) = n.shift_with_flags(&BigBinary::from_int(self.fraction as i64), Shift::Left);
+        scaled
+    }
+}
+
+impl FromStr for FixedFormat {
+    type Err = String;
+
+    /// Reads "Qm.n", or just "m.n", where m + n has to be one of the
+    /// supported widths other than big
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let unsupported = || format!("Unsupported fixed-point format '{}'", s);
+        let (integer, fraction) = s
+            .strip_prefix('Q')
+            .unwrap_or(s)
+            .split_once('.')
+            .ok_or_else(unsupported)?;
+
+        let (integer, fraction): (usize, usize) = match (integer.parse(), fraction.parse()) {
+            (Ok(integer), Ok(fraction)) => (integer, fraction),
+            _ => return Err(unsupported()),
+        };
+
+        match integer.checked_add(fraction) {
+            Some(8 | 16 | 32 | 64 | 128) => Ok(FixedFormat { integer, fraction }),
+            _ => Err(unsupported()),
+        }
+    }
+}
+
+/// How the results of exact rational arithmetic are written out.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RationalOutput {
+    /// As a fraction in lowest terms, eg. 1/6
+    Fraction,
+    /// As a fraction followed by its decimal expansion, with the digits
+    /// that repeat in parentheses, eg. 0.1(6)
+    Decimal,
+}
+
+impl FromStr for RationalOutput {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::RationalOutput::*;
+
+        Ok(match s {
+            "fraction" => Fraction,
+            "decimal" => Decimal,
+            _ => return Err(format!("Unknown rational output '{}'", s)),
+        })
+    }
+}
+
+/// The value every result is reduced modulo, which is at least 2.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Modulus(pub BigBinary);
+
+impl FromStr for Modulus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match BigBinary::from_decimal(s) {
+            Some(n) if n.compare(&BigBinary::one()).2 == Bit::On => Ok(Modulus(n)),
+            _ => Err(format!("Unsupported modulus '{}'", s)),
+        }
+    }
+}
+
+/// The set of numbers an expression is evaluated over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Domain {
+    Real,
+    /// Pairs of parts held in the register, the second of which is a
+    /// multiple of i
+    Complex,
+}
+
+impl FromStr for Domain {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::Domain::*;
+
+        Ok(match s {
+            "real" => Real,
+            "complex" => Complex,
+            _ => return Err(format!("Unknown domain '{}'", s)),
+        })
+    }
+}
+
+/// Settings that apply to the evaluation of a whole expression.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Context {
+    pub mode: Mode,
+    pub width: Width,
+    pub signedness: Signedness,
+    /// The adder circuit used for addition, subtraction and negation
+    pub adder: AdderKind,
+    /// The multiplier circuit used for multiplication
+    pub multiplier: MultiplierKind,
+    /// The divider circuit used for division and remainders
+    pub divider: DividerKind,
+    /// The fixed-point format every value is held in, which picks the width
+    /// of the register, or None for integers
+    pub fixed: Option<FixedFormat>,
+    /// Whether every value is an exact quotient of two integers in the
+    /// register, and how results are written out if so
+    pub rational: Option<RationalOutput>,
+    /// Whether values are real or complex
+    pub domain: Domain,
+    /// The value every result is reduced modulo, which only integers are
+    /// evaluated with, or None for plain arithmetic
+    pub modulus: Option<Modulus>,
+    /// How numbers are written, both in the expression and in the results,
+    /// or None to read them as in en-US and write results without grouping
+    pub format: Option<NumberFormat>,
+    /// How many partial products the multiplier added during the last
+    /// evaluation
+    pub additions: Cell<usize>,
+}
+
+impl Context {
+    pub fn new() -> Self {
+        Context {
+            mode: Mode::Checked,
+            width: Width::W64,
+            signedness: Signedness::Signed,
+            adder: AdderKind::RippleCarry,
+            multiplier: MultiplierKind::Array,
+            divider: DividerKind::Restoring,
+            fixed: None,
+            rational: None,
+            domain: Domain::Real,
+            modulus: None,
+            format: None,
+            additions: Cell::new(0),
+        }
+    }
+
+    /// The values an integer literal has to be within when read, which is
+    /// the range of the register as long as a literal that doesn't fit would
+    /// be an error anyway. Fixed-point and unbounded registers have none
+    pub fn literal_range(&self) -> Option<LiteralRange> {
+        let bits = self.width.bits()?;
+
+        if self.mode != Mode::Checked || self.fixed.is_some() {
+            return None;
+        }
+
+        let repeat = |bit: Bit, n: usize| std::iter::repeat_n(bit, n);
+
+        Some(match self.signedness {
+            Signedness::Signed => LiteralRange {
+                min: BigBinary::from_bits(repeat(Bit::On, 1).chain(repeat(Bit::Off, bits - 1)).collect()),
+                max: BigBinary::from_bits(repeat(Bit::Off, 1).chain(repeat(Bit::On, bits - 1)).collect()),
+            },
+            Signedness::Unsigned => LiteralRange {
+                min: BigBinary::zero(),
+                max: BigBinary::from_bits(repeat(Bit::Off, 1).chain(repeat(Bit::On, bits)).collect()),
+            },
+        })
+    }
+
+    pub fn with_mode(mode: Mode) -> Self {
+        Context {
+            mode,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_width(width: Width) -> Self {
+        Context {
+            width,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_signedness(signedness: Signedness) -> Self {
+        Context {
+            signedness,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_adder(adder: AdderKind) -> Self {
+        Context {
+            adder,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_multiplier(multiplier: MultiplierKind) -> Self {
+        Context {
+            multiplier,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_divider(divider: DividerKind) -> Self {
+        Context {
+            divider,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_fixed(fixed: FixedFormat) -> Self {
+        Context {
+            fixed: Some(fixed),
+            ..Context::new()
+        }
+    }
+
+    pub fn with_rational(output: RationalOutput) -> Self {
+        Context {
+            rational: Some(output),
+            ..Context::new()
+        }
+    }
+
+    pub fn with_domain(domain: Domain) -> Self {
+        Context {
+            domain,
+            ..Context::new()
+        }
+    }
+
+    pub fn with_modulus(modulus: Modulus) -> Self {
+        Context {
+            modulus: Some(modulus),
+            ..Context::new()
+        }
+    }
+
+    pub fn with_format(format: NumberFormat) -> Self {
+        Context {
+            format: Some(format),
+            ..Context::new()
+        }
+    }
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Context::new()
+    }
+}
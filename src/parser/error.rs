@@ -1,12 +1,15 @@
-use crate::lexer::Token;
+use super::syntax::{BinaryOp, Expr, Function};
+use crate::lexer::{Span, Token};
+use crate::types::BigBinary;
 use std::{error, fmt};
 
 #[derive(Debug, PartialEq)]
 pub enum ParseErr {
     IncompleteSequence,
-    StateNotFinishable,
-    UnexpectedToken(Token),
+    UnexpectedToken(Token, Span),
     NoEmptyNodeFound,
+    ArgumentCount { function: Function, expected: usize, found: usize, span: Span },
+    UnclosedParenthesis(Span),
     GeneralError(String),
 }
 
@@ -18,10 +21,63 @@ impl fmt::Display for ParseErr {
 
         match self {
             IncompleteSequence => write!(f, "Another token was expected"),
-            StateNotFinishable => write!(f, "Current state is not a finish state."),
-            UnexpectedToken(t) => write!(f, "Token {:?} was unexpected", t),
+            UnexpectedToken(t, _) => write!(f, "Token '{}' was unexpected", t),
             NoEmptyNodeFound => write!(f, "No empty node was found to insert expression"),
+            ArgumentCount { function, expected, found, .. } => {
+                write!(f, "Function '{}' takes {} argument(s) but was given {}", function, expected, found)
+            }
+            UnclosedParenthesis(_) => write!(f, "Parenthesis was never closed"),
             GeneralError(e) => write!(f, "{}", e),
         }
     }
 }
+
+/// The set of possible evaluation errors, pointing at the offending node.
+#[derive(Debug, PartialEq)]
+pub enum EvalErr {
+    /// Along with the operator, call or parentheses left without an
+    /// operand, if the whole input isn't empty
+    EmptyExpression(Option<Span>),
+    IncompleteOperation(Box<BinaryOp>),
+    DivisionByZero(Box<BinaryOp>),
+    Overflow(Box<BinaryOp>),
+    NegationOverflow(Box<Expr>),
+    ComplementOverflow(Box<Expr>),
+    LiteralOverflow(Box<Expr>),
+    NegativeRoot(Box<Expr>),
+    InvalidDegree(Box<Expr>),
+    NotIntegral(Box<Expr>),
+    NotComplex(Box<Expr>),
+    NotReal(Box<Expr>),
+    FunctionOverflow(Box<Expr>),
+    NotModular(Box<Expr>),
+    NotInvertible(Box<Expr>),
+    ModulusOverflow(BigBinary),
+}
+
+impl error::Error for EvalErr {}
+
+impl fmt::Display for EvalErr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::EvalErr::*;
+
+        match self {
+            EmptyExpression(_) => write!(f, "There is nothing to evaluate"),
+            IncompleteOperation(op) => write!(f, "Operation '{}' is missing an operand", op),
+            DivisionByZero(op) => write!(f, "Operation '{}' divides by zero", op),
+            Overflow(op) => write!(f, "Operation '{}' overflowed", op),
+            NegationOverflow(expr) => write!(f, "Negation '{}' overflowed", expr),
+            ComplementOverflow(expr) => write!(f, "Complement '{}' overflowed", expr),
+            LiteralOverflow(expr) => write!(f, "Literal '{}' does not fit", expr),
+            NegativeRoot(expr) => write!(f, "Root '{}' of a negative value is undefined", expr),
+            InvalidDegree(expr) => write!(f, "Root '{}' needs a degree above zero", expr),
+            NotIntegral(expr) => write!(f, "Operation '{}' only works on integers", expr),
+            NotComplex(expr) => write!(f, "Imaginary number '{}' needs the complex domain", expr),
+            NotReal(expr) => write!(f, "Operation '{}' only works on real numbers", expr),
+            FunctionOverflow(expr) => write!(f, "Call '{}' overflowed", expr),
+            NotModular(expr) => write!(f, "Operation '{}' needs a modulus", expr),
+            NotInvertible(expr) => write!(f, "Operation '{}' needs a value with no factor in common with the modulus", expr),
+            ModulusOverflow(modulus) => write!(f, "Modulus '{}' does not fit", modulus),
+        }
+    }
+}
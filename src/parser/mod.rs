@@ -1,26 +1,89 @@
+pub mod context;
 pub mod error;
 pub mod syntax;
 
-use crate::Binary;
 use super::{
-    lexer::{Symbol, Token, TokenSequence},
+    lexer::{Span, Symbol, Token, TokenSequence},
     parser::{
         error::ParseErr,
         //machine::Machine,
-        syntax::{BinaryOp, Expr, Operation},
+        syntax::{BinaryOp, Expr, Function, Operation},
     },
 };
-use std::{iter::Peekable, slice::Iter};
+use std::{iter::{Peekable, Zip}, slice::Iter};
+
+/// The tokens left to parse, each along with its span
+type Tokens<'a> = Peekable<Zip<Iter<'a, Token>, Iter<'a, Span>>>;
 
 pub fn parse(seq: &TokenSequence) -> Result<Expr, ParseErr> {
-    if seq.len() == 0 {
+    if seq.is_empty() {
         return Ok(Expr::Empty);
     }
 
-    to_ast(&mut seq.iter().peekable(), Expr::Empty)
+    let mut tokens = seq.spanned().peekable();
+    let expr = to_ast(&mut tokens, Expr::Empty)?.raise_unary();
+
+    // Commas only belong between the arguments of a function, and closing
+    // parentheses after one that was opened
+    match tokens.next() {
+        Some((t, span)) => Err(ParseErr::UnexpectedToken(t.clone(), *span)),
+        None => Ok(expr),
+    }
+}
+
+/// Parses the parenthesized, comma-separated arguments of a function whose
+/// name has the given span
+fn parse_call(tokens: &mut Tokens, function: Function, name: Span) -> Result<Expr, ParseErr> {
+    let open = match tokens.next() {
+        Some((Token::Sym(Symbol::ParenOpen), open)) => *open,
+        Some((t, span)) => return Err(ParseErr::UnexpectedToken(t.clone(), *span)),
+        None => return Err(ParseErr::IncompleteSequence),
+    };
+
+    let mut args = vec![to_ast(tokens, Expr::Empty)?];
+
+    let span = loop {
+        match tokens.next() {
+            Some((Token::Sym(Symbol::Comma), _)) => args.push(to_ast(tokens, Expr::Empty)?),
+            Some((_, close)) => break name.to(*close),
+            None => return Err(ParseErr::UnclosedParenthesis(open)),
+        }
+    };
+
+    if args.len() != function.arity() {
+        return Err(ParseErr::ArgumentCount {
+            function,
+            expected: function.arity(),
+            found: args.len(),
+            span,
+        });
+    }
+
+    Ok(Expr::Call(function, args, span))
+}
+
+/// Puts an operand wherever the expression so far is still missing one,
+/// failing on the token that produced it if nothing is missing
+fn place_operand(expr: Expr, operand: Expr, t: &Token, span: Span) -> Result<Expr, ParseErr> {
+    match expr {
+        Expr::Empty => Ok(operand),
+
+        mut unary if unary.is_open_unary() => {
+            unary.fill_unary(operand);
+            Ok(unary)
+        }
+
+        Expr::BinOp(mut tree) => match tree.append_expr(operand) {
+            Ok(()) => Ok(Expr::BinOp(tree)),
+            Err(ParseErr::NoEmptyNodeFound) => Err(ParseErr::UnexpectedToken(t.clone(), span)),
+            Err(e) => Err(e),
+        },
+
+        _ => Err(ParseErr::UnexpectedToken(t.clone(), span)),
+    }
 }
 
-fn to_ast(tokens: &mut Peekable<Iter<Token>>, starting: Expr) -> Result<Expr, ParseErr> {
+fn to_ast(tokens: &mut Tokens, starting: Expr) -> Result<Expr, ParseErr> {
     let mut expr = starting;
 
     type Ex = Expr;
@@ -28,80 +91,103 @@ fn to_ast(tokens: &mut Peekable<Iter<Token>>, starting: Expr) -> Result<Expr, Pa
     type Sy = Symbol;
     type Tk = Token;
 
-    while let Some(&t) = tokens.next() {
-        let next: Option<&Token> = tokens.peek().map(|t| t.clone());
+    while let Some(&(t, &span)) = tokens.peek() {
+        // Closing parentheses and commas end the innermost group, and are
+        // left for whatever opened it
+        if let Tk::Sym(Sy::ParenClose) | Tk::Sym(Sy::Comma) = t {
+            return Ok(expr);
+        }
 
-        expr = match t {
-            Tk::Sym(Sy::ParenClose) => return Ok(expr),
+        tokens.next();
 
+        expr = match t {
             Tk::Sym(Sy::ParenOpen) => {
                 let sub_expr = to_ast(tokens, Ex::Empty)?;
 
-                match expr {
-                    Ex::Empty => Ex::SubExpr(Box::new(sub_expr)),
+                let group = match tokens.next() {
+                    Some((t @ Tk::Sym(Sy::Comma), comma)) => return Err(ParseErr::UnexpectedToken(t.clone(), *comma)),
+                    Some((_, close)) => span.to(*close),
+                    None => return Err(ParseErr::UnclosedParenthesis(span)),
+                };
 
-                    Ex::Negation(expr) if *expr == Ex::Empty => {
-                        Ex::Negation(Box::new(Ex::SubExpr(Box::new(sub_expr))))
-                    }
+                place_operand(expr, Ex::SubExpr(Box::new(sub_expr), group), t, span)?
+            }
 
-                    Ex::BinOp(mut tree) => tree
-                        .append_expr(Ex::SubExpr(Box::new(sub_expr)))
-                        .map(|()| Ex::BinOp(tree))?,
+            Tk::Num(n) => place_operand(expr, Ex::Literal(n.clone(), span), t, span)?,
 
-                    _ => {
-                        eprintln!("expr: {:?}", expr);
-                        return Err(ParseErr::UnexpectedToken(t));
-                    }
-                }
+            Tk::Decimal(d) => place_operand(expr, Ex::Decimal(d.clone(), span), t, span)?,
+
+            // The number itself spans everything but the "i"
+            Tk::Imaginary(n) => {
+                let digits = Span::new(span.start, span.end - 1);
+                let operand = match &**n {
+                    Tk::Decimal(d) => Ex::Decimal(d.clone(), digits),
+                    Tk::Num(n) => Ex::Literal(n.clone(), digits),
+                    _ => return Err(ParseErr::UnexpectedToken(t.clone(), span)),
+                };
+
+                place_operand(expr, Ex::Imaginary(Box::new(operand), span), t, span)?
+            }
+
+            Tk::Sym(s @ Sy::Root)
+            | Tk::Sym(s @ Sy::Sqrt)
+            | Tk::Sym(s @ Sy::Re)
+            | Tk::Sym(s @ Sy::Im)
+            | Tk::Sym(s @ Sy::Conj)
+            | Tk::Sym(s @ Sy::Abs2)
+            | Tk::Sym(s @ Sy::Inv) => {
+                let function = Function::from_symbol(*s).map_err(ParseErr::GeneralError)?;
+                let call = parse_call(tokens, function, span)?;
+
+                place_operand(expr, call, t, span)?
             }
 
-            Tk::Num(n) => match expr {
-                Ex::Empty => Ex::Literal(Binary::from_int(n)),
+            // Minus is the only operator that's both unary and binary,
+            // so it needs some special treatment
+            Tk::Sym(Sy::Minus) => match expr {
+                Ex::Empty => Ex::Negation(Box::new(Ex::Empty), span),
 
-                Ex::Negation(val) if *val == Ex::Empty => Ex::Negation(Box::new(Ex::Literal(Binary::from_int(n)))),
+                mut unary if unary.is_open_unary() => {
+                    unary.fill_unary(Ex::Negation(Box::new(Ex::Empty), span));
+                    unary
+                }
 
                 Ex::BinOp(mut tree) => tree
-                    .append_expr(Ex::Literal(Binary::from_int(n)))
+                    .append_minus(span)
                     .map(|()| Ex::BinOp(tree))?,
 
-                _ => {
-                    eprintln!("expr: {:?}", expr);
-                    return Err(ParseErr::UnexpectedToken(t));
-                }
+                expr => Ex::BinOp(Box::new(BinaryOp::with_span(expr, Op::Sub, Ex::Empty, span))),
             },
 
-            // Minus is the only operator that's both unary and binary,
-            // so it needs some special treatment
-            Tk::Sym(Sy::Minus) => match expr {
-                Ex::Empty => Ex::Negation(Box::new(Ex::Empty)),
+            // Tilde is only ever unary, so it goes wherever an operand is
+            // still missing
+            Tk::Sym(Sy::Tilde) => match expr {
+                Ex::Empty => Ex::BitNot(Box::new(Ex::Empty), span),
 
-                Ex::Literal(n) => Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(n),
-                    Op::Sub,
-                    Ex::Empty,
-                ))),
+                mut unary if unary.is_open_unary() => {
+                    unary.fill_unary(Ex::BitNot(Box::new(Ex::Empty), span));
+                    unary
+                }
 
                 Ex::BinOp(mut tree) => tree
-                    .append_minus()
+                    .append_expr(Ex::BitNot(Box::new(Ex::Empty), span))
                     .map(|()| Ex::BinOp(tree))?,
 
-                _ => {
-                    eprintln!("expr: {:?}", expr);
-                    return Err(ParseErr::UnexpectedToken(t));
-                }
+                _ => return Err(ParseErr::UnexpectedToken(t.clone(), span)),
             },
 
-            // Asterisk | Caret | FwdSlash | Percent | Plus
-            Tk::Sym(s) => match Op::from_symbol(s) {
+            // Ampersand | Asterisk | Caret | FwdSlash | GreaterGreater |
+            // LessLess | Percent | Pipe | Plus | Xor
+            Tk::Sym(s) => match Op::from_symbol(*s) {
                 Ok(op) => match expr {
-                    Ex::Empty => return Err(ParseErr::UnexpectedToken(t)),
+                    Ex::Empty => return Err(ParseErr::UnexpectedToken(t.clone(), span)),
 
                     Ex::BinOp(mut tree) => {
-                        tree.append_operation(op);
+                        tree.append_operation(op, span);
                         Ex::BinOp(tree)
                     },
 
-                    expr => Ex::BinOp(Box::new(BinaryOp::new(expr, op, Ex::Empty))),
+                    expr => Ex::BinOp(Box::new(BinaryOp::with_span(expr, op, Ex::Empty, span))),
                 },
                 Err(e) => return Err(ParseErr::GeneralError(e)),
             },
@@ -113,7 +199,16 @@ fn to_ast(tokens: &mut Peekable<Iter<Token>>, starting: Expr) -> Result<Expr, Pa
 
 #[cfg(test)]
 mod tests {
-    use super::{parse, Expr as Ex, Operation as Op, Symbol as Sy, Token as Tk, BinaryOp, TokenSequence};
+    use super::{parse, Expr as Ex, Operation as Op, Symbol as Sy, Token as Tk, BinaryOp, Span, TokenSequence};
+    use crate::types::BigBinary;
+
+    fn num(n: i64) -> super::Token {
+        Tk::Num(BigBinary::from_int(n))
+    }
+
+    fn lit(n: i64) -> super::Expr {
+        Ex::Literal(BigBinary::from_int(n), Span::default())
+    }
 
     fn assert(tokens: Vec<super::Token>, expr: super::Expr) {
         assert_eq!(parse(&TokenSequence::with_tokens(tokens)), Ok(expr),);
@@ -130,7 +225,7 @@ mod tests {
     fn parse_literal() {
         use self::*;
 
-        assert(vec![Tk::Num(15)], Ex::Literal(15));
+        assert(vec![num(15)], lit(15));
     }
 
     #[test]
@@ -138,8 +233,8 @@ mod tests {
         use self::*;
 
         assert(
-            vec![Tk::Sym(Sy::ParenOpen), Tk::Num(15), Tk::Sym(Sy::ParenClose)],
-            Ex::SubExpr(Box::new(Ex::Literal(15))),
+            vec![Tk::Sym(Sy::ParenOpen), num(15), Tk::Sym(Sy::ParenClose)],
+            Ex::SubExpr(Box::new(lit(15)), Span::default()),
         );
     }
 
@@ -154,18 +249,32 @@ mod tests {
             (Sy::FwdSlash, Op::Div),
             (Sy::Caret, Op::Exp),
             (Sy::Percent, Op::Mod),
+            (Sy::Ampersand, Op::BitAnd),
+            (Sy::Pipe, Op::BitOr),
+            (Sy::Xor, Op::BitXor),
+            (Sy::LessLess, Op::Shl),
+            (Sy::GreaterGreater, Op::Shr),
+            (Sy::GreaterGreaterGreater, Op::LogicalShr),
+            (Sy::Rotl, Op::RotateLeft),
+            (Sy::Rotr, Op::RotateRight),
+            (Sy::Less, Op::Less),
+            (Sy::LessEqual, Op::LessEqual),
+            (Sy::Greater, Op::Greater),
+            (Sy::GreaterEqual, Op::GreaterEqual),
+            (Sy::EqualEqual, Op::Equal),
+            (Sy::BangEqual, Op::NotEqual),
         ];
 
         for (sym, op) in pairs.iter() {
             assert(vec![
-                Tk::Num(15),
+                num(15),
                 Tk::Sym(*sym),
-                Tk::Num(0)
+                num(0)
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(15),
+                lit(15),
                 *op,
-                Ex::Literal(0))
+                lit(0))
             )));
         }
     }
@@ -181,23 +290,37 @@ mod tests {
             (Sy::FwdSlash, Op::Div),
             (Sy::Caret, Op::Exp),
             (Sy::Percent, Op::Mod),
+            (Sy::Ampersand, Op::BitAnd),
+            (Sy::Pipe, Op::BitOr),
+            (Sy::Xor, Op::BitXor),
+            (Sy::LessLess, Op::Shl),
+            (Sy::GreaterGreater, Op::Shr),
+            (Sy::GreaterGreaterGreater, Op::LogicalShr),
+            (Sy::Rotl, Op::RotateLeft),
+            (Sy::Rotr, Op::RotateRight),
+            (Sy::Less, Op::Less),
+            (Sy::LessEqual, Op::LessEqual),
+            (Sy::Greater, Op::Greater),
+            (Sy::GreaterEqual, Op::GreaterEqual),
+            (Sy::EqualEqual, Op::Equal),
+            (Sy::BangEqual, Op::NotEqual),
         ];
 
         for (sym, op) in pairs.iter() {
             assert( vec![
                 Tk::Sym(Sy::ParenOpen),
-                Tk::Num(15),
+                num(15),
                 Tk::Sym(*sym),
-                Tk::Num(0),
+                num(0),
                 Tk::Sym(Sy::ParenClose),
             ],
             Ex::SubExpr(Box::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(15),
+                    lit(15),
                     *op,
-                    Ex::Literal(0),
+                    lit(0),
                 )))
-            )));
+            ), Span::default()));
         }
     }
 
@@ -206,8 +329,8 @@ mod tests {
         use self::*;
 
         assert(
-            vec![Tk::Sym(Sy::Minus), Tk::Num(15)],
-            Ex::Negation(Box::new(Ex::Literal(15))),
+            vec![Tk::Sym(Sy::Minus), num(15)],
+            Ex::Negation(Box::new(lit(15)), Span::default()),
         );
     }
 
@@ -220,20 +343,35 @@ mod tests {
         assert(vec![
             Tk::Sym(Sy::Minus),
             Tk::Sym(Sy::ParenOpen),
-            Tk::Num(4),
+            num(4),
             Tk::Sym(Sy::Plus),
-            Tk::Num(2),
+            num(2),
             Tk::Sym(Sy::ParenClose),
         ],
         Ex::Negation(Box::new(
             Ex::SubExpr(Box::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(4),
+                    lit(4),
                     Op::Add,
-                    Ex::Literal(2),
+                    lit(2),
                 )))
-            )),
-        )));
+            ), Span::default()),
+        ), Span::default()));
+    }
+
+    #[test]
+    fn parse_negation_of_power() {
+        use self::*;
+
+        // -2 ^ 2, which negates the power rather than raising -2
+
+        assert(
+            vec![Tk::Sym(Sy::Minus), num(2), Tk::Sym(Sy::Caret), num(2)],
+            Ex::Negation(
+                Box::new(Ex::BinOp(Box::new(BinaryOp::new(lit(2), Op::Exp, lit(2))))),
+                Span::default(),
+            ),
+        );
     }
 
     #[test]
@@ -246,10 +384,10 @@ mod tests {
             vec![
                 Tk::Sym(Sy::ParenOpen),
                 Tk::Sym(Sy::Minus),
-                Tk::Num(15),
+                num(15),
                 Tk::Sym(Sy::ParenClose),
             ],
-            Ex::SubExpr(Box::new(Ex::Negation(Box::new(Ex::Literal(15))))),
+            Ex::SubExpr(Box::new(Ex::Negation(Box::new(lit(15)), Span::default())), Span::default()),
         );
     }
 
@@ -264,20 +402,34 @@ mod tests {
             (Sy::FwdSlash, Op::Div),
             (Sy::Caret, Op::Exp),
             (Sy::Percent, Op::Mod),
+            (Sy::Ampersand, Op::BitAnd),
+            (Sy::Pipe, Op::BitOr),
+            (Sy::Xor, Op::BitXor),
+            (Sy::LessLess, Op::Shl),
+            (Sy::GreaterGreater, Op::Shr),
+            (Sy::GreaterGreaterGreater, Op::LogicalShr),
+            (Sy::Rotl, Op::RotateLeft),
+            (Sy::Rotr, Op::RotateRight),
+            (Sy::Less, Op::Less),
+            (Sy::LessEqual, Op::LessEqual),
+            (Sy::Greater, Op::Greater),
+            (Sy::GreaterEqual, Op::GreaterEqual),
+            (Sy::EqualEqual, Op::Equal),
+            (Sy::BangEqual, Op::NotEqual),
         ];
 
         for (sym, op) in pairs.iter() {
             assert(
                 vec![
-                    Tk::Num(1),
+                    num(1),
                     Tk::Sym(*sym),
                     Tk::Sym(Sy::Minus),
-                    Tk::Num(5)
+                    num(5)
                 ],
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(1),
+                    lit(1),
                     *op,
-                    Ex::Negation(Box::new(Ex::Literal(5))),
+                    Ex::Negation(Box::new(lit(5)), Span::default()),
                 )))
             );
         }
@@ -292,32 +444,104 @@ mod tests {
             (Sy::Minus, Op::Sub),
             (Sy::Asterisk, Op::Mul),
             (Sy::FwdSlash, Op::Div),
-            (Sy::Caret, Op::Exp),
             (Sy::Percent, Op::Mod),
+            (Sy::Ampersand, Op::BitAnd),
+            (Sy::Pipe, Op::BitOr),
+            (Sy::Xor, Op::BitXor),
+            (Sy::LessLess, Op::Shl),
+            (Sy::GreaterGreater, Op::Shr),
+            (Sy::GreaterGreaterGreater, Op::LogicalShr),
+            (Sy::Rotl, Op::RotateLeft),
+            (Sy::Rotr, Op::RotateRight),
+            (Sy::Less, Op::Less),
+            (Sy::LessEqual, Op::LessEqual),
+            (Sy::Greater, Op::Greater),
+            (Sy::GreaterEqual, Op::GreaterEqual),
+            (Sy::EqualEqual, Op::Equal),
+            (Sy::BangEqual, Op::NotEqual),
         ];
 
         for (sym, op) in pairs.iter() {
             assert(
                 vec![
-                    Tk::Num(1),
+                    num(1),
                     Tk::Sym(*sym),
-                    Tk::Num(3),
+                    num(3),
                     Tk::Sym(*sym),
-                    Tk::Num(5),
+                    num(5),
                 ],
                 Ex::BinOp(Box::new(BinaryOp::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(1),
+                        lit(1),
                         *op,
-                        Ex::Literal(3),
+                        lit(3),
                     ))),
                     *op,
-                    Ex::Literal(5),
+                    lit(5),
                 )))
             );
         }
     }
 
+    #[test]
+    fn parse_exponentiation_right_associative() {
+        use self::*;
+
+        // 1 ^ 3 ^ 5 ^ 7
+
+        assert(
+            vec![
+                num(1),
+                Tk::Sym(Sy::Caret),
+                num(3),
+                Tk::Sym(Sy::Caret),
+                num(5),
+                Tk::Sym(Sy::Caret),
+                num(7),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                lit(1),
+                Op::Exp,
+                Ex::BinOp(Box::new(BinaryOp::new(
+                    lit(3),
+                    Op::Exp,
+                    Ex::BinOp(Box::new(BinaryOp::new(
+                        lit(5),
+                        Op::Exp,
+                        lit(7),
+                    ))),
+                ))),
+            )))
+        );
+
+        // 2 ^ 3 ^ 2 * 4
+
+        assert(
+            vec![
+                num(2),
+                Tk::Sym(Sy::Caret),
+                num(3),
+                Tk::Sym(Sy::Caret),
+                num(2),
+                Tk::Sym(Sy::Asterisk),
+                num(4),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                Ex::BinOp(Box::new(BinaryOp::new(
+                    lit(2),
+                    Op::Exp,
+                    Ex::BinOp(Box::new(BinaryOp::new(
+                        lit(3),
+                        Op::Exp,
+                        lit(2),
+                    ))),
+                ))),
+                Op::Mul,
+                lit(4),
+            )))
+        );
+    }
+
     #[test]
     fn parse_operator_precedence() {
         use self::*;
@@ -326,19 +550,19 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(3),
+                num(3),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(1),
+                lit(1),
                 Op::Add,
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(3),
+                    lit(3),
                     Op::Mul,
-                    Ex::Literal(5),
+                    lit(5),
                 ))),
             )))
         );
@@ -353,23 +577,23 @@ mod tests {
         assert(
             vec![
                 Tk::Sym(Sy::ParenOpen),
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(3),
+                num(3),
                 Tk::Sym(Sy::ParenClose),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::SubExpr(Box::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(1),
+                        lit(1),
                         Op::Add,
-                        Ex::Literal(3),
+                        lit(3),
                     ))),
-                )),
+                ), Span::default()),
                 Op::Mul,
-                Ex::Literal(5),
+                lit(5),
             )))
         );
     }
@@ -382,24 +606,24 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Asterisk),
                 Tk::Sym(Sy::ParenOpen),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::ParenClose),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(1),
+                lit(1),
                 Op::Mul,
                 Ex::SubExpr(Box::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(5),
+                        lit(5),
                         Op::Add,
-                        Ex::Literal(2),
+                        lit(2),
                     )))
-                ))
+                ), Span::default())
             )))
         );
     }
@@ -412,44 +636,44 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
                 Tk::Sym(Sy::ParenOpen),
                 Tk::Sym(Sy::ParenOpen),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::ParenClose),
                 Tk::Sym(Sy::Caret),
                 Tk::Sym(Sy::ParenOpen),
-                Tk::Num(4),
+                num(4),
                 Tk::Sym(Sy::Minus),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::ParenClose),
                 Tk::Sym(Sy::ParenClose),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(1),
+                lit(1),
                 Op::Add,
                 Ex::SubExpr(Box::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
                         Ex::SubExpr(Box::new(
                             Ex::BinOp(Box::new(BinaryOp::new(
-                                Ex::Literal(5),
+                                lit(5),
                                 Op::Mul,
-                                Ex::Literal(2),
+                                lit(2),
                             ))),
-                        )),
+                        ), Span::default()),
                         Op::Exp,
                         Ex::SubExpr(Box::new(
                             Ex::BinOp(Box::new(BinaryOp::new(
-                                Ex::Literal(4),
+                                lit(4),
                                 Op::Sub,
-                                Ex::Literal(2),
+                                lit(2),
                             ))),
-                        )),
+                        ), Span::default()),
                     ))),
-                )),
+                ), Span::default()),
             )))
         );
     }
@@ -461,39 +685,39 @@ mod tests {
         // 1 * 2 * 5
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(5),
+                num(5),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(1),
+                    lit(1),
                     Op::Mul,
-                    Ex::Literal(2),
+                    lit(2),
                 ))),
                 Op::Add,
-                Ex::Literal(5),
+                lit(5),
             )))
         );
 
         // 1 + 2 * 5
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(1),
+                lit(1),
                 Op::Add,
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(2),
+                    lit(2),
                     Op::Mul,
-                    Ex::Literal(5),
+                    lit(5),
                 )))
             )))
         );
@@ -507,26 +731,26 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(4),
+                num(4),
                 Tk::Sym(Sy::Minus),
-                Tk::Num(2),
+                num(2),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(1),
+                        lit(1),
                         Op::Mul,
-                        Ex::Literal(5),
+                        lit(5),
                     ))),
                     Op::Add,
-                    Ex::Literal(4),
+                    lit(4),
                 ))),
                 Op::Sub,
-                Ex::Literal(2),
+                lit(2),
             )))
         );
     }
@@ -539,32 +763,32 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Caret),
-                Tk::Num(4),
+                num(4),
                 Tk::Sym(Sy::Minus),
-                Tk::Num(2),
+                num(2),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(1),
+                    lit(1),
                     Op::Add,
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(5),
+                        lit(5),
                         Op::Mul,
                         Ex::BinOp(Box::new(BinaryOp::new(
-                            Ex::Literal(2),
+                            lit(2),
                             Op::Exp,
-                            Ex::Literal(4),
+                            lit(4),
                         ))),
                     ))),
                 ))),
                 Op::Sub,
-                Ex::Literal(2),
+                lit(2),
             )))
         );
 
@@ -573,31 +797,31 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Caret),
-                Tk::Num(4),
+                num(4),
                 Tk::Sym(Sy::FwdSlash),
-                Tk::Num(2),
+                num(2),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
-                Ex::Literal(1),
+                lit(1),
                 Op::Add,
                 Ex::BinOp(Box::new(BinaryOp::new(
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(5),
+                        lit(5),
                         Op::Mul,
                         Ex::BinOp(Box::new(BinaryOp::new(
-                            Ex::Literal(2),
+                            lit(2),
                             Op::Exp,
-                            Ex::Literal(4),
+                            lit(4),
                         )))
                     ))),
                     Op::Div,
-                    Ex::Literal(2),
+                    lit(2),
                 )))
             )))
         );
@@ -611,25 +835,25 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Caret),
-                Tk::Num(4),
+                num(4),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(1),
+                    lit(1),
                     Op::Mul,
-                    Ex::Literal(5),
+                    lit(5),
                 ))),
                 Op::Add,
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(2),
+                    lit(2),
                     Op::Exp,
-                    Ex::Literal(4),
+                    lit(4),
                 )))
             )))
         );
@@ -638,27 +862,242 @@ mod tests {
 
         assert(
             vec![
-                Tk::Num(1),
+                num(1),
                 Tk::Sym(Sy::Asterisk),
-                Tk::Num(5),
+                num(5),
                 Tk::Sym(Sy::Caret),
-                Tk::Num(2),
+                num(2),
                 Tk::Sym(Sy::Plus),
-                Tk::Num(4),
+                num(4),
             ],
             Ex::BinOp(Box::new(BinaryOp::new(
                 Ex::BinOp(Box::new(BinaryOp::new(
-                    Ex::Literal(1),
+                    lit(1),
                     Op::Mul,
                     Ex::BinOp(Box::new(BinaryOp::new(
-                        Ex::Literal(5),
+                        lit(5),
                         Op::Exp,
-                        Ex::Literal(2),
+                        lit(2),
+                    ))),
+                ))),
+                Op::Add,
+                lit(4),
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_bitwise_precedence() {
+        use self::*;
+
+        // 1 | 2 xor 3 & 4 << 5
+
+        assert(
+            vec![
+                num(1),
+                Tk::Sym(Sy::Pipe),
+                num(2),
+                Tk::Sym(Sy::Xor),
+                num(3),
+                Tk::Sym(Sy::Ampersand),
+                num(4),
+                Tk::Sym(Sy::LessLess),
+                num(5),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                lit(1),
+                Op::BitOr,
+                Ex::BinOp(Box::new(BinaryOp::new(
+                    lit(2),
+                    Op::BitXor,
+                    Ex::BinOp(Box::new(BinaryOp::new(
+                        lit(3),
+                        Op::BitAnd,
+                        Ex::BinOp(Box::new(BinaryOp::new(
+                            lit(4),
+                            Op::Shl,
+                            lit(5),
+                        ))),
                     ))),
                 ))),
+            )))
+        );
+
+        // 1 + 2 << 3
+
+        assert(
+            vec![
+                num(1),
+                Tk::Sym(Sy::Plus),
+                num(2),
+                Tk::Sym(Sy::LessLess),
+                num(3),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                Ex::BinOp(Box::new(BinaryOp::new(
+                    lit(1),
+                    Op::Add,
+                    lit(2),
+                ))),
+                Op::Shl,
+                lit(3),
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_unary_chain() {
+        use self::*;
+
+        // ~-5
+
+        assert(
+            vec![Tk::Sym(Sy::Tilde), Tk::Sym(Sy::Minus), num(5)],
+            Ex::BitNot(Box::new(Ex::Negation(Box::new(lit(5)), Span::default())), Span::default()),
+        );
+
+        // 1 & ~(2)
+
+        assert(
+            vec![
+                num(1),
+                Tk::Sym(Sy::Ampersand),
+                Tk::Sym(Sy::Tilde),
+                Tk::Sym(Sy::ParenOpen),
+                num(2),
+                Tk::Sym(Sy::ParenClose),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                lit(1),
+                Op::BitAnd,
+                Ex::BitNot(Box::new(Ex::SubExpr(Box::new(lit(2)), Span::default())), Span::default()),
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_subtraction_after_negation() {
+        use self::*;
+
+        // -5 - 1
+
+        assert(
+            vec![Tk::Sym(Sy::Minus), num(5), Tk::Sym(Sy::Minus), num(1)],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                Ex::Negation(Box::new(lit(5)), Span::default()),
+                Op::Sub,
+                lit(1),
+            )))
+        );
+    }
+
+    #[test]
+    fn parse_function_call() {
+        use self::*;
+        use super::{Function, ParseErr};
+
+        // 1 + root(sqrt(16) * 2, 3)
+
+        assert(
+            vec![
+                num(1),
+                Tk::Sym(Sy::Plus),
+                Tk::Sym(Sy::Root),
+                Tk::Sym(Sy::ParenOpen),
+                Tk::Sym(Sy::Sqrt),
+                Tk::Sym(Sy::ParenOpen),
+                num(16),
+                Tk::Sym(Sy::ParenClose),
+                Tk::Sym(Sy::Asterisk),
+                num(2),
+                Tk::Sym(Sy::Comma),
+                num(3),
+                Tk::Sym(Sy::ParenClose),
+            ],
+            Ex::BinOp(Box::new(BinaryOp::new(
+                lit(1),
                 Op::Add,
-                Ex::Literal(4),
+                Ex::Call(Function::Root, vec![
+                    Ex::BinOp(Box::new(BinaryOp::new(
+                        Ex::Call(Function::Sqrt, vec![lit(16)], Span::default()),
+                        Op::Mul,
+                        lit(2),
+                    ))),
+                    lit(3),
+                ], Span::default()),
             )))
         );
+
+        // -sqrt(4)
+
+        assert(
+            vec![Tk::Sym(Sy::Minus), Tk::Sym(Sy::Sqrt), Tk::Sym(Sy::ParenOpen), num(4), Tk::Sym(Sy::ParenClose)],
+            Ex::Negation(Box::new(Ex::Call(Function::Sqrt, vec![lit(4)], Span::default())), Span::default()),
+        );
+
+        let parse = |tokens| parse(&TokenSequence::with_tokens(tokens));
+
+        assert_eq!(
+            parse(vec![Tk::Sym(Sy::Sqrt), Tk::Sym(Sy::ParenOpen), num(4), Tk::Sym(Sy::Comma), num(2), Tk::Sym(Sy::ParenClose)]),
+            Err(ParseErr::ArgumentCount { function: Function::Sqrt, expected: 1, found: 2, span: Span::new(0, 6) })
+        );
+        assert_eq!(parse(vec![Tk::Sym(Sy::Sqrt), num(4)]), Err(ParseErr::UnexpectedToken(num(4), Span::new(1, 2))));
+        assert_eq!(parse(vec![num(4), Tk::Sym(Sy::Comma), num(2)]), Err(ParseErr::UnexpectedToken(Tk::Sym(Sy::Comma), Span::new(1, 2))));
+        assert_eq!(
+            parse(vec![Tk::Sym(Sy::ParenOpen), num(4), Tk::Sym(Sy::Comma), num(2), Tk::Sym(Sy::ParenClose)]),
+            Err(ParseErr::UnexpectedToken(Tk::Sym(Sy::Comma), Span::new(2, 3)))
+        );
+    }
+
+    #[test]
+    fn parse_unbalanced_parentheses() {
+        use crate::lexer::lex;
+        use crate::parser::error::ParseErr;
+
+        let parse = |s: &str| parse(&lex(s).unwrap());
+
+        assert_eq!(parse("2 * (1 + 3"), Err(ParseErr::UnclosedParenthesis(Span::new(4, 5))));
+        assert_eq!(parse("((1)"), Err(ParseErr::UnclosedParenthesis(Span::new(0, 1))));
+        assert_eq!(parse("sqrt(4"), Err(ParseErr::UnclosedParenthesis(Span::new(4, 5))));
+        assert_eq!(parse("1 + 2)"), Err(ParseErr::UnexpectedToken(Tk::Sym(Sy::ParenClose), Span::new(5, 6))));
+        assert_eq!(parse("1 2"), Err(ParseErr::UnexpectedToken(num(2), Span::new(2, 3))));
+        assert_eq!(parse("1 + 2 3"), Err(ParseErr::UnexpectedToken(num(3), Span::new(6, 7))));
+
+        assert_eq!(parse("1 + 2)").unwrap_err().to_string(), "Token ')' was unexpected");
+        assert_eq!(parse("1 + 2 3").unwrap_err().to_string(), "Token '3' was unexpected");
+        assert_eq!(parse("4 sqrt(4)").unwrap_err().to_string(), "Token 'sqrt' was unexpected");
+    }
+
+    #[test]
+    fn parse_spans() {
+        use crate::lexer::lex;
+
+        let span = |s: &str| {
+            let span = parse(&lex(s).unwrap()).unwrap().span().unwrap();
+            (span.start, span.end)
+        };
+
+        assert_eq!(span(" 12 "), (1, 3));
+        assert_eq!(span("1 + 23 * 456"), (0, 12));
+        assert_eq!(span("- ~7"), (0, 4));
+        assert_eq!(span("(1 + 2)  "), (0, 7));
+        assert_eq!(span("root(27, 3) "), (0, 11));
+        assert_eq!(span("  4.5i"), (2, 6));
+        assert_eq!(parse(&lex("").unwrap()).unwrap().span(), None);
+
+        // Precedence moves operations around, and their spans along with them
+        let tree = match parse(&lex("2 * 30 - 4 ^ 5").unwrap()).unwrap() {
+            Ex::BinOp(tree) => tree,
+            expr => panic!("Expected an operation, found {:?}", expr),
+        };
+
+        assert_eq!(tree.span(), Span::new(0, 14));
+        assert_eq!(tree.lhs().span(), Some(Span::new(0, 6)));
+        assert_eq!(tree.rhs().span(), Some(Span::new(9, 14)));
+
+        match tree.rhs() {
+            Ex::BinOp(power) => assert_eq!(power.rhs().span(), Some(Span::new(13, 14))),
+            expr => panic!("Expected an operation, found {:?}", expr),
+        }
     }
 }
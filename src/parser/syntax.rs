@@ -1,8 +1,10 @@
-use super::error::ParseErr;
+use super::context::{Context, Domain, Mode, Signedness, Width};
+use super::error::{EvalErr, ParseErr};
 use crate::{
-    lexer::Symbol,
-    Binary,
+    lexer::{Span, Symbol},
+    types::{BigBinary, Binary, Bit, Complex, Decimal, Fixed, Flags, Modular, Number, Rational, Shift, Unsigned, Value},
 };
+use std::fmt;
 
 /// The supported binary BinaryOp for building a syntax tree.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -11,8 +13,22 @@ pub enum Operation {
     Sub,
     Mul,
     Div,
-    //Exp,
-    //Mod,
+    Exp,
+    Mod,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Shl,
+    Shr,
+    LogicalShr,
+    RotateLeft,
+    RotateRight,
+    Less,
+    LessEqual,
+    Greater,
+    GreaterEqual,
+    Equal,
+    NotEqual,
 }
 
 impl Operation {
@@ -24,8 +40,22 @@ impl Operation {
             FwdSlash => Div,
             Minus => Sub,
             Plus => Add,
-            //Caret => Exp,
-            //Percent => Mod,
+            Caret => Exp,
+            Percent => Mod,
+            Ampersand => BitAnd,
+            Pipe => BitOr,
+            Xor => BitXor,
+            LessLess => Shl,
+            GreaterGreater => Shr,
+            GreaterGreaterGreater => LogicalShr,
+            Rotl => RotateLeft,
+            Rotr => RotateRight,
+            Symbol::Less => Operation::Less,
+            Symbol::LessEqual => Operation::LessEqual,
+            Symbol::Greater => Operation::Greater,
+            Symbol::GreaterEqual => Operation::GreaterEqual,
+            EqualEqual => Equal,
+            BangEqual => NotEqual,
 
             _ => return Err(format!("Cannot convert symbol '{:?}' to operation", s)),
         })
@@ -33,65 +63,389 @@ impl Operation {
 
     pub fn has_precedence_over(&self, other: Operation) -> bool {
         self.precedence() > other.precedence()
+            || (*self == other && self.is_right_associative())
     }
 
+    /// Follows C, where comparisons bind more loosely than shifts, and the
+    /// bitwise operations more loosely still and each other in the order
+    /// "&", "xor", "|"
     fn precedence(&self) -> usize {
         use self::Operation::*;
 
         match self {
-            //Exp => 3,
-            Mul | Div /* | Mod */ => 2,
-            Add | Sub => 1,
+            Exp => 9,
+            Mul | Div | Mod => 8,
+            Add | Sub => 7,
+            Shl | Shr | LogicalShr | RotateLeft | RotateRight => 6,
+            Less | LessEqual | Greater | GreaterEqual => 5,
+            Equal | NotEqual => 4,
+            BitAnd => 3,
+            BitXor => 2,
+            BitOr => 1,
         }
     }
 
-    fn evaluate(&self, lhs: &Expr, rhs: &Expr) -> Binary {
+    /// Whether a chain of this operation groups from the right, eg.
+    /// "2 ^ 3 ^ 2" as "2 ^ (3 ^ 2)"
+    fn is_right_associative(&self) -> bool {
+        *self == Operation::Exp
+    }
+
+    /// Whether the operation only works on whole numbers, as the bitwise
+    /// operations, shifts and rotations do
+    fn needs_integers(&self) -> bool {
+        use self::Operation::*;
+
+        matches!(self, BitAnd | BitOr | BitXor | Shl | Shr | LogicalShr | RotateLeft | RotateRight)
+    }
+
+    /// Whether the operation only works on real numbers, as comparing by
+    /// order and taking a remainder do
+    fn needs_reals(&self) -> bool {
         use self::Operation::*;
 
-        let (lhs, rhs) = (lhs.evaluate(), rhs.evaluate());
+        matches!(self, Less | LessEqual | Greater | GreaterEqual | Mod)
+    }
+
+    /// Runs the circuit for the operation with those picked by the context,
+    /// counting any partial products the multiplier adds
+    fn evaluate<T: Number>(&self, lhs: &T, rhs: &T, ctx: &Context) -> (T, Flags) {
+        use self::Operation::*;
 
         match self {
-            Add => &lhs + &rhs,
-            Sub => &lhs - &rhs,
-            Mul => &lhs * &rhs,
-            Div => &lhs / &rhs,
-            //Mod => lhs % rhs,
-            //Exp => lhs.powf(rhs),
+            Add => lhs.add_using(rhs, &ctx.adder),
+            Sub => lhs.sub_using(rhs, &ctx.adder),
+            Mul => {
+                let (product, flags, additions) = match ctx.fixed {
+                    Some(format) => lhs.mul_fixed_using(rhs, &ctx.multiplier, format.fraction),
+                    None => lhs.mul_using(rhs, &ctx.multiplier),
+                };
+                ctx.additions.set(ctx.additions.get() + additions);
+                (product, flags)
+            }
+            Div => match ctx.fixed {
+                Some(format) => lhs.div_fixed_using(rhs, &ctx.divider, format.fraction),
+                None => {
+                    let (quotient, _, flags) = lhs.div_rem_using(rhs, &ctx.divider);
+                    (quotient, flags)
+                }
+            },
+            Exp if integral::<T>(ctx) => {
+                let (power, flags, additions) = lhs.pow_using(rhs, &ctx.multiplier, &ctx.divider);
+                ctx.additions.set(ctx.additions.get() + additions);
+                (power, flags)
+            }
+            Exp => Operation::power(lhs, rhs, ctx),
+            Mod => lhs.rem_using(rhs, &ctx.divider),
+            BitAnd => lhs.and_with_flags(rhs),
+            BitOr => lhs.or_with_flags(rhs),
+            BitXor => lhs.xor_with_flags(rhs),
+            Shl => lhs.shift_with_flags(rhs, Shift::Left),
+            // As with Rust's integers, only signed numbers fill with the sign
+            Shr if T::SIGNED => lhs.shift_with_flags(rhs, Shift::ArithmeticRight),
+            Shr | LogicalShr => lhs.shift_with_flags(rhs, Shift::LogicalRight),
+            RotateLeft => lhs.shift_with_flags(rhs, Shift::RotateLeft),
+            RotateRight => lhs.shift_with_flags(rhs, Shift::RotateRight),
+            Less | LessEqual | Greater | GreaterEqual | Equal | NotEqual => {
+                let (less, equal, greater) = lhs.compare(rhs);
+                let condition = match self {
+                    Less => less,
+                    LessEqual => less | equal,
+                    Greater => greater,
+                    GreaterEqual => greater | equal,
+                    Equal => equal,
+                    _ => !equal,
+                };
+
+                let flags = Flags::from_condition(condition);
+
+                match ctx.fixed {
+                    // A true condition is 1 in the format, which a format
+                    // without an integer Bit to spare can't hold
+                    Some(format) => {
+                        let (result, loaded) = T::from_literal(&format.scale(&BigBinary::from_bit(condition)));
+                        (result, Flags { overflow: loaded.overflow, ..flags })
+                    }
+                    None => (T::from_bit(condition), flags),
+                }
+            }
         }
     }
+
+    /// Raises anything but a plain integer to a whole power by squaring and
+    /// multiplying with the context's multiplication, which keeps fixed-point
+    /// values scaled, and then dividing 1 by the result for a negative power
+    fn power<T: Number>(lhs: &T, rhs: &T, ctx: &Context) -> (T, Flags) {
+        use self::Operation::*;
+
+        // Fractional exponents are turned away before getting here
+        let exp = match rhs.to_integer(fraction(ctx)) {
+            Some(exp) => exp,
+            None => return (lhs.clone(), Flags::from_condition(Bit::Off)),
+        };
+
+        // 1 doesn't fit in a format without an integer Bit to spare, so the
+        // power only starts from it when there's nothing to multiply
+        let one = || T::from_literal(&ctx.fixed.map_or(BigBinary::one(), |format| format.scale(&BigBinary::one())));
+        let (mut base, mut rest) = (lhs.clone(), exp.magnitude());
+        let mut result: Option<T> = None;
+        let mut overflow = Bit::Off;
+
+        while rest.any() == Bit::On {
+            if rest.low_bit() == Bit::On {
+                result = Some(match result {
+                    Some(result) => {
+                        let (product, flags) = Mul.evaluate(&result, &base, ctx);
+                        overflow = overflow | flags.overflow;
+                        product
+                    }
+                    None => base.clone(),
+                });
+            }
+
+            rest = rest.shift_with_flags(&BigBinary::one(), Shift::LogicalRight).0;
+
+            if rest.any() == Bit::On {
+                let (square, flags) = Mul.evaluate(&base, &base, ctx);
+                base = square;
+                overflow = overflow | flags.overflow;
+            }
+        }
+
+        let (power, flags) = match result {
+            Some(power) => (power, Flags::from_condition(Bit::Off)),
+            None => one(),
+        };
+        let overflow = overflow | flags.overflow;
+
+        if exp.is_negative() {
+            let (one, loaded) = one();
+            let (reciprocal, flags) = Div.evaluate(&one, &power, ctx);
+
+            return (reciprocal, Flags { overflow: overflow | loaded.overflow | flags.overflow, ..flags });
+        }
+
+        (power, Flags { overflow, ..flags })
+    }
+
+    /// Picks the saturated value for an overflowed result by running the
+    /// operation again without a limit on size, so that only what doesn't
+    /// fit is clamped, or otherwise from the sign the result would have had
+    fn saturate<T: Number>(&self, lhs: &T, rhs: &T, result: T, ctx: &Context) -> Option<T> {
+        use self::Operation::*;
+
+        let negative = match self {
+            // A sum only overflows when both operands share a sign, and a
+            // difference only when they don't, so the true result takes the
+            // sign of the left operand
+            Add => lhs.sign(),
+            Sub if T::SIGNED => lhs.sign(),
+            // An unsigned difference only overflows by going below zero
+            Sub => Bit::On,
+            Mul | Div => lhs.sign() ^ rhs.sign(),
+            // Only odd powers of a negative base stay negative
+            Exp => lhs.sign() & rhs.to_integer(fraction(ctx)).map_or(Bit::Off, |exp| exp.low_bit()),
+            // The only overflow is from the quotient, while the remainder
+            // itself is always representable
+            Mod => return Some(result),
+            // Bitwise operations never overflow
+            BitAnd | BitOr | BitXor => return Some(result),
+            // Shifting by too much has already moved every Bit out
+            Shl | Shr | LogicalShr | RotateLeft | RotateRight => return Some(result),
+            // Comparisons are always 0 or 1
+            Less | LessEqual | Greater | GreaterEqual | Equal | NotEqual => return Some(result),
+        };
+
+        // Representations without a limit have nothing to saturate at
+        T::saturated(negative)?;
+
+        // A copy of the context keeps the operation's partial products from
+        // being counted twice
+        if let (Some(lhs), Some(rhs)) = (lhs.widen(), rhs.widen()) {
+            let (exact, flags) = self.evaluate(&lhs, &rhs, &ctx.clone());
+
+            if flags.overflow == Bit::Off {
+                return T::narrow_saturating(&exact);
+            }
+        }
+
+        T::saturated(negative)
+    }
+}
+
+impl fmt::Display for Operation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Operation::*;
+
+        write!(f, "{}", match self {
+            Add => "+",
+            Sub => "-",
+            Mul => "*",
+            Div => "/",
+            Exp => "^",
+            Mod => "%",
+            BitAnd => "&",
+            BitOr => "|",
+            BitXor => "xor",
+            Shl => "<<",
+            Shr => ">>",
+            LogicalShr => ">>>",
+            RotateLeft => "rotl",
+            RotateRight => "rotr",
+            Less => "<",
+            LessEqual => "<=",
+            Greater => ">",
+            GreaterEqual => ">=",
+            Equal => "==",
+            NotEqual => "!=",
+        })
+    }
 }
 
 /// A hierachical syntax element that enables the parsing of expressions
-/// that rely on operator precedence rather than parentheses.
-#[derive(Clone, Debug, PartialEq)]
-pub struct BinaryOp(Expr, Operation, Expr);
+/// that rely on operator precedence rather than parentheses, along with the
+/// span of its operator.
+#[derive(Clone, Debug)]
+pub struct BinaryOp(Expr, Operation, Expr, Span);
 
 impl BinaryOp {
     pub fn new(lhs: Expr, op: Operation, rhs: Expr) -> Self {
-        Self(lhs, op, rhs)
+        Self(lhs, op, rhs, Span::default())
+    }
+
+    pub fn with_span(lhs: Expr, op: Operation, rhs: Expr, span: Span) -> Self {
+        Self(lhs, op, rhs, span)
+    }
+
+    pub fn lhs(&self) -> &Expr {
+        &self.0
+    }
+
+    pub fn rhs(&self) -> &Expr {
+        &self.2
+    }
+
+    pub fn operation(&self) -> Operation {
+        self.1
+    }
+
+    /// The span from the start of the left operand to the end of the right
+    pub fn span(&self) -> Span {
+        [self.0.span(), self.2.span()]
+            .iter()
+            .flatten()
+            .fold(self.3, |span, operand| span.to(*operand))
+    }
+
+    /// Evaluates both operands and then the operation itself, reporting
+    /// a missing operand anywhere below as an incomplete operation
+    fn evaluate<T: Number>(&self, ctx: &Context) -> Result<T, EvalErr> {
+        let Self(lhs, op, rhs, _) = self;
+
+        let operand = |expr: &Expr| match expr.evaluate_as(ctx) {
+            Err(EvalErr::EmptyExpression(_)) => Err(EvalErr::IncompleteOperation(Box::new(self.clone()))),
+            result => result,
+        };
+
+        let (lhs, rhs): (T, T) = (operand(lhs)?, operand(rhs)?);
+
+        if !integral::<T>(ctx) && op.needs_integers() {
+            return Err(EvalErr::NotIntegral(Box::new(Expr::BinOp(Box::new(self.clone())))));
+        }
+
+        // Powers of anything else are built up by multiplying, which needs
+        // a whole number of times to multiply by
+        if !integral::<T>(ctx) && *op == Operation::Exp && rhs.to_integer(fraction(ctx)).is_none() {
+            return Err(EvalErr::NotIntegral(Box::new(Expr::BinOp(Box::new(self.clone())))));
+        }
+
+        if ctx.domain == Domain::Complex && op.needs_reals() {
+            return Err(EvalErr::NotReal(Box::new(Expr::BinOp(Box::new(self.clone())))));
+        }
+
+        if let Some(modular) = modular::<T>(ctx) {
+            return self.evaluate_modular(&lhs, &rhs, &modular, ctx);
+        }
+
+        let (result, flags) = op.evaluate(&lhs, &rhs, ctx);
+
+        if flags.divide_by_zero == Bit::On {
+            return Err(EvalErr::DivisionByZero(Box::new(self.clone())));
+        }
+
+        settle(
+            ctx,
+            result,
+            flags,
+            |result| op.saturate(&lhs, &rhs, result, ctx),
+            || EvalErr::Overflow(Box::new(self.clone())),
+        )
     }
 
-    fn evaluate(&self) -> Binary {
-        let Self(lhs, op, rhs) = self;
+    /// Runs the operation modulo the context's modulus on both operands
+    /// reduced first, except for the exponent of a power, which is taken as
+    /// it is so that a negative one raises the inverse
+    fn evaluate_modular<T: Number>(&self, lhs: &T, rhs: &T, modular: &Modular<T>, ctx: &Context) -> Result<T, EvalErr> {
+        let Self(_, op, _, _) = self;
+        let (a, b) = (modular.reduce(lhs), modular.reduce(rhs));
+        let not_invertible = || EvalErr::NotInvertible(Box::new(Expr::BinOp(Box::new(self.clone()))));
+
+        let (result, flags, additions) = match op {
+            Operation::Add => return Ok(modular.add(&a, &b)),
+            Operation::Sub => return Ok(modular.sub(&a, &b)),
+            Operation::Mul => modular.mul(&a, &b),
+            // Multiplies by the inverse, which 0 never has
+            Operation::Div => {
+                if b.compare(&T::from_bit(Bit::Off)).1 == Bit::On {
+                    return Err(EvalErr::DivisionByZero(Box::new(self.clone())));
+                }
+
+                let (inverse, inverted) = modular.inverse(&b).ok_or_else(not_invertible)?;
+                let (quotient, flags, additions) = modular.mul(&a, &inverse);
+
+                (quotient, Flags { overflow: flags.overflow | inverted.overflow, ..flags }, additions)
+            }
+            Operation::Exp => modular.pow(&a, rhs).ok_or_else(not_invertible)?,
+            _ => {
+                let (result, flags) = op.evaluate(lhs, rhs, ctx);
+
+                if flags.divide_by_zero == Bit::On {
+                    return Err(EvalErr::DivisionByZero(Box::new(self.clone())));
+                }
 
-        op.evaluate(lhs, rhs)
+                let result = settle(
+                    ctx,
+                    result,
+                    flags,
+                    |result| op.saturate(lhs, rhs, result, ctx),
+                    || EvalErr::Overflow(Box::new(self.clone())),
+                )?;
+
+                return Ok(modular.reduce(&result));
+            }
+        };
+        ctx.additions.set(ctx.additions.get() + additions);
+
+        // A product that didn't fit has lost the Bits its remainder depends
+        // on, so there's nothing to saturate at
+        settle(ctx, result, flags, |_| None, || EvalErr::Overflow(Box::new(self.clone())))
     }
 
     /// Traverses down the right-most branch to compare itself against
     /// existing operators, stopping when the new operation no longer has
     /// precedence and restructuring the expression to suit.
-    pub fn append_operation(&mut self, next_op: Operation) {
+    pub fn append_operation(&mut self, next_op: Operation, span: Span) {
         if next_op.has_precedence_over(self.1) {
             match &mut self.2 {
                 Expr::BinOp(tree) => {
-                    tree.append_operation(next_op);
+                    tree.append_operation(next_op, span);
                 },
-                expr => {
+                _ => {
                     // Since new operation takes precedence over existing one,
                     // assuming self is equivalent to "1 + 3" and the incoming
                     // operation is "*", then self should be restructurwd to
                     // "1 + Tree(3 * empty)"
-                    let mut new_rhs = Self(Expr::Empty, next_op, Expr::Empty);
+                    let mut new_rhs = Self(Expr::Empty, next_op, Expr::Empty, span);
 
                     std::mem::swap(&mut self.2, &mut new_rhs.0);
                     self.2 = Expr::BinOp(Box::new(new_rhs));
@@ -103,22 +457,23 @@ impl BinaryOp {
         // Existing operation takes precedence, so assuming self is "1 * 3"
         // and incoming operation is "+", self should become
         // "Tree(1 * 3) + empty"
-        let mut new_lhs = Self(Expr::Empty, self.1, Expr::Empty);
+        let mut new_lhs = Self(Expr::Empty, self.1, Expr::Empty, self.3);
 
         std::mem::swap(&mut self.0, &mut new_lhs.0);
         std::mem::swap(&mut self.2, &mut new_lhs.2);
 
         self.0 = Expr::BinOp(Box::new(new_lhs));
         self.1 = next_op;
+        self.3 = span;
     }
 
     /// Inspects the right-most branch to determine if and how a Minus token
     /// can be aded, either as a unary or binary operation
-    pub fn append_minus(&mut self) -> Result<(), ParseErr> {
+    pub fn append_minus(&mut self, span: Span) -> Result<(), ParseErr> {
         if self.has_empty() {
-            self.append_expr(Expr::Negation(Box::new(Expr::Empty)))
+            self.append_expr(Expr::Negation(Box::new(Expr::Empty), span))
         } else {
-            self.append_operation(Operation::Sub);
+            self.append_operation(Operation::Sub, span);
             Ok(())
         }
     }
@@ -131,8 +486,8 @@ impl BinaryOp {
                 self.2 = expr;
                 Ok(())
             }
-            Expr::Negation(val) if **val == Expr::Empty => {
-                self.2 = Expr::Negation(Box::new(expr));
+            unary if unary.is_open_unary() => {
+                unary.fill_unary(expr);
                 Ok(())
             }
 
@@ -148,31 +503,1242 @@ impl BinaryOp {
         match &self.2 {
             Expr::BinOp(tree) => tree.has_empty(),
             Expr::Empty => true,
-            _ => false,
+            expr => expr.is_open_unary(),
+        }
+    }
+}
+
+/// The functions an expression can call, each taking a fixed number of
+/// arguments.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Function {
+    Sqrt,
+    Root,
+    Re,
+    Im,
+    Conj,
+    Abs2,
+    Inv,
+}
+
+impl Function {
+    pub fn from_symbol(s: Symbol) -> Result<Self, String> {
+        Ok(match s {
+            Symbol::Sqrt => Function::Sqrt,
+            Symbol::Root => Function::Root,
+            Symbol::Re => Function::Re,
+            Symbol::Im => Function::Im,
+            Symbol::Conj => Function::Conj,
+            Symbol::Abs2 => Function::Abs2,
+            Symbol::Inv => Function::Inv,
+
+            _ => return Err(format!("Cannot convert symbol '{:?}' to function", s)),
+        })
+    }
+
+    pub fn arity(&self) -> usize {
+        match self {
+            Function::Root => 2,
+            Function::Sqrt | Function::Re | Function::Im | Function::Conj | Function::Abs2 | Function::Inv => 1,
         }
     }
 }
 
-/// The possible syntax tree elements.
-#[derive(Clone, Debug, PartialEq)]
+/// The possible syntax tree elements, each with the span of the tokens it
+/// was read from, apart from those of its operands. A unary operation only
+/// spans its operator, while a group and a call span their parentheses too.
+#[derive(Clone, Debug)]
 pub enum Expr {
     Empty,
     BinOp(Box<BinaryOp>),
-    Literal(Binary),
-    Negation(Box<Expr>),
-    SubExpr(Box<Expr>),
+    Literal(BigBinary, Span),
+    Decimal(Decimal, Span),
+    /// A literal times the imaginary unit, eg. `4i`
+    Imaginary(Box<Expr>, Span),
+    Negation(Box<Expr>, Span),
+    BitNot(Box<Expr>, Span),
+    SubExpr(Box<Expr>, Span),
+    Call(Function, Vec<Expr>, Span),
+}
+
+/// Expressions are equal when they have the same structure, wherever in the
+/// input they were read from
+impl PartialEq for Expr {
+    fn eq(&self, other: &Self) -> bool {
+        use self::Expr::*;
+
+        match (self, other) {
+            (Empty, Empty) => true,
+            (BinOp(a), BinOp(b)) => a == b,
+            (Literal(a, _), Literal(b, _)) => a == b,
+            (Decimal(a, _), Decimal(b, _)) => a == b,
+            (Imaginary(a, _), Imaginary(b, _))
+            | (Negation(a, _), Negation(b, _))
+            | (BitNot(a, _), BitNot(b, _))
+            | (SubExpr(a, _), SubExpr(b, _)) => a == b,
+            (Call(f, a, _), Call(g, b, _)) => f == g && a == b,
+            _ => false,
+        }
+    }
+}
+
+impl PartialEq for BinaryOp {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1 && self.2 == other.2
+    }
 }
 
 impl Expr {
-    pub fn evaluate(&self) -> Binary {
+    /// The span of the whole expression, operands included, or None if it
+    /// is empty
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            Expr::Empty => None,
+            Expr::BinOp(binary_op) => Some(binary_op.span()),
+            Expr::Literal(_, span)
+            | Expr::Decimal(_, span)
+            | Expr::Imaginary(_, span)
+            | Expr::SubExpr(_, span)
+            | Expr::Call(_, _, span) => Some(*span),
+            Expr::Negation(expr, span) | Expr::BitNot(expr, span) => {
+                Some(expr.span().map_or(*span, |operand| span.to(operand)))
+            }
+        }
+    }
+
+    /// Moves the unary operations in front of the base of a power out to
+    /// cover the whole power, since powers bind tighter than them as they do
+    /// in maths, eg. "-2 ^ 2" as "-(2 ^ 2)"
+    pub fn raise_unary(self) -> Expr {
+        match self {
+            Expr::BinOp(tree) => {
+                let BinaryOp(lhs, op, rhs, span) = *tree;
+                let (lhs, rhs) = (lhs.raise_unary(), rhs.raise_unary());
+                let power = |base: Expr| Expr::BinOp(Box::new(BinaryOp(base, op, rhs.clone(), span))).raise_unary();
+
+                match lhs {
+                    Expr::Negation(base, unary) if op == Operation::Exp => Expr::Negation(Box::new(power(*base)), unary),
+                    Expr::BitNot(base, unary) if op == Operation::Exp => Expr::BitNot(Box::new(power(*base)), unary),
+                    lhs => Expr::BinOp(Box::new(BinaryOp(lhs, op, rhs, span))),
+                }
+            }
+            Expr::Negation(expr, span) => Expr::Negation(Box::new(expr.raise_unary()), span),
+            Expr::BitNot(expr, span) => Expr::BitNot(Box::new(expr.raise_unary()), span),
+            Expr::Imaginary(expr, span) => Expr::Imaginary(Box::new(expr.raise_unary()), span),
+            Expr::SubExpr(expr, span) => Expr::SubExpr(Box::new(expr.raise_unary()), span),
+            Expr::Call(function, args, span) => {
+                Expr::Call(function, args.into_iter().map(Expr::raise_unary).collect(), span)
+            }
+            expr => expr,
+        }
+    }
+
+    /// Whether this is a chain of unary operations still waiting on the
+    /// operand at its bottom, eg. "-~_"
+    pub fn is_open_unary(&self) -> bool {
+        match self {
+            Expr::Negation(expr, _) | Expr::BitNot(expr, _) => {
+                **expr == Expr::Empty || expr.is_open_unary()
+            }
+            _ => false,
+        }
+    }
+
+    /// Places the operand at the bottom of an open chain of unary
+    /// operations, see `is_open_unary`
+    pub fn fill_unary(&mut self, operand: Expr) {
+        if let Expr::Negation(expr, _) | Expr::BitNot(expr, _) = self {
+            if **expr == Expr::Empty {
+                **expr = operand;
+            } else {
+                expr.fill_unary(operand);
+            }
+        }
+    }
+
+    /// Whether a decimal literal appears anywhere in the expression
+    pub fn has_decimal(&self) -> bool {
+        match self {
+            Expr::Decimal(..) => true,
+            Expr::BinOp(binary_op) => binary_op.0.has_decimal() || binary_op.2.has_decimal(),
+            Expr::Imaginary(expr, _) | Expr::Negation(expr, _) | Expr::BitNot(expr, _) | Expr::SubExpr(expr, _) => {
+                expr.has_decimal()
+            }
+            Expr::Call(_, args, _) => args.iter().any(Expr::has_decimal),
+            Expr::Empty | Expr::Literal(..) => false,
+        }
+    }
+
+    /// Evaluates with the representation the context asks for, in the
+    /// register its fixed-point format fills if it has one, or otherwise with
+    /// floats if there is a decimal literal anywhere, which are binary32 for
+    /// a width of 32 and binary64 otherwise
+    pub fn evaluate(&self, ctx: &Context) -> Result<Value, EvalErr> {
+        ctx.additions.set(0);
+
+        // Residues are whole numbers, which decimals would otherwise turn
+        // into floats
+        if ctx.modulus.is_some() && self.has_decimal() {
+            return Err(EvalErr::NotIntegral(Box::new(self.clone())));
+        }
+
+        if let Some(format) = ctx.fixed {
+            let fraction = format.fraction;
+
+            return match (ctx.signedness, format.width()) {
+                (Signedness::Signed, Width::W8) => {
+                    self.evaluate_in(ctx, |n: Binary<8>| Value::Fixed(Fixed::from_binary(&n, fraction)))
+                }
+                (Signedness::Signed, Width::W16) => {
+                    self.evaluate_in(ctx, |n: Binary<16>| Value::Fixed(Fixed::from_binary(&n, fraction)))
+                }
+                (Signedness::Signed, Width::W32) => {
+                    self.evaluate_in(ctx, |n: Binary<32>| Value::Fixed(Fixed::from_binary(&n, fraction)))
+                }
+                (Signedness::Signed, Width::W64) => {
+                    self.evaluate_in(ctx, |n: Binary<64>| Value::Fixed(Fixed::from_binary(&n, fraction)))
+                }
+                (Signedness::Signed, _) => {
+                    self.evaluate_in(ctx, |n: Binary<128>| Value::Fixed(Fixed::from_binary(&n, fraction)))
+                }
+                (Signedness::Unsigned, Width::W8) => {
+                    self.evaluate_in(ctx, |n: Unsigned<Binary<8>>| Value::Fixed(Fixed::from_unsigned(&n.0, fraction)))
+                }
+                (Signedness::Unsigned, Width::W16) => {
+                    self.evaluate_in(ctx, |n: Unsigned<Binary<16>>| Value::Fixed(Fixed::from_unsigned(&n.0, fraction)))
+                }
+                (Signedness::Unsigned, Width::W32) => {
+                    self.evaluate_in(ctx, |n: Unsigned<Binary<32>>| Value::Fixed(Fixed::from_unsigned(&n.0, fraction)))
+                }
+                (Signedness::Unsigned, Width::W64) => {
+                    self.evaluate_in(ctx, |n: Unsigned<Binary<64>>| Value::Fixed(Fixed::from_unsigned(&n.0, fraction)))
+                }
+                (Signedness::Unsigned, _) => {
+                    self.evaluate_in(ctx, |n: Unsigned<Binary<128>>| Value::Fixed(Fixed::from_unsigned(&n.0, fraction)))
+                }
+            };
+        }
+
+        if ctx.rational.is_some() {
+            return match (ctx.signedness, ctx.width) {
+                (Signedness::Signed, Width::W8) => {
+                    self.evaluate_in(ctx, |n: Rational<Binary<8>>| Value::Rational(n.map(BigBinary::from_binary)))
+                }
+                (Signedness::Signed, Width::W16) => {
+                    self.evaluate_in(ctx, |n: Rational<Binary<16>>| Value::Rational(n.map(BigBinary::from_binary)))
+                }
+                (Signedness::Signed, Width::W32) => {
+                    self.evaluate_in(ctx, |n: Rational<Binary<32>>| Value::Rational(n.map(BigBinary::from_binary)))
+                }
+                (Signedness::Signed, Width::W64) => {
+                    self.evaluate_in(ctx, |n: Rational<Binary<64>>| Value::Rational(n.map(BigBinary::from_binary)))
+                }
+                (Signedness::Signed, Width::W128) => {
+                    self.evaluate_in(ctx, |n: Rational<Binary<128>>| Value::Rational(n.map(BigBinary::from_binary)))
+                }
+                (Signedness::Signed, Width::Unbounded) => self.evaluate_in(ctx, Value::Rational),
+                (Signedness::Unsigned, Width::W8) => self.evaluate_in(ctx, |n: Rational<Unsigned<Binary<8>>>| {
+                    Value::Rational(n.map(|n| BigBinary::from_unsigned(&n.0)))
+                }),
+                (Signedness::Unsigned, Width::W16) => self.evaluate_in(ctx, |n: Rational<Unsigned<Binary<16>>>| {
+                    Value::Rational(n.map(|n| BigBinary::from_unsigned(&n.0)))
+                }),
+                (Signedness::Unsigned, Width::W32) => self.evaluate_in(ctx, |n: Rational<Unsigned<Binary<32>>>| {
+                    Value::Rational(n.map(|n| BigBinary::from_unsigned(&n.0)))
+                }),
+                (Signedness::Unsigned, Width::W64) => self.evaluate_in(ctx, |n: Rational<Unsigned<Binary<64>>>| {
+                    Value::Rational(n.map(|n| BigBinary::from_unsigned(&n.0)))
+                }),
+                (Signedness::Unsigned, Width::W128) => self.evaluate_in(ctx, |n: Rational<Unsigned<Binary<128>>>| {
+                    Value::Rational(n.map(|n| BigBinary::from_unsigned(&n.0)))
+                }),
+                (Signedness::Unsigned, Width::Unbounded) => {
+                    self.evaluate_in(ctx, |n: Rational<Unsigned<BigBinary>>| Value::Rational(n.map(|n| n.0.clone())))
+                }
+            };
+        }
+
+        if self.has_decimal() {
+            return match ctx.width {
+                Width::W32 => self.evaluate_in(ctx, Value::F32),
+                _ => self.evaluate_in(ctx, Value::F64),
+            };
+        }
+
+        match (ctx.signedness, ctx.width) {
+            (Signedness::Signed, Width::W8) => self.evaluate_in(ctx, Value::B8),
+            (Signedness::Signed, Width::W16) => self.evaluate_in(ctx, Value::B16),
+            (Signedness::Signed, Width::W32) => self.evaluate_in(ctx, Value::B32),
+            (Signedness::Signed, Width::W64) => self.evaluate_in(ctx, Value::B64),
+            (Signedness::Signed, Width::W128) => self.evaluate_in(ctx, Value::B128),
+            (Signedness::Signed, Width::Unbounded) => self.evaluate_in(ctx, Value::Big),
+            (Signedness::Unsigned, Width::W8) => self.evaluate_in(ctx, Value::U8),
+            (Signedness::Unsigned, Width::W16) => self.evaluate_in(ctx, Value::U16),
+            (Signedness::Unsigned, Width::W32) => self.evaluate_in(ctx, Value::U32),
+            (Signedness::Unsigned, Width::W64) => self.evaluate_in(ctx, Value::U64),
+            (Signedness::Unsigned, Width::W128) => self.evaluate_in(ctx, Value::U128),
+            (Signedness::Unsigned, Width::Unbounded) => {
+                self.evaluate_in(ctx, |n: Unsigned<BigBinary>| Value::Big(n.0))
+            }
+        }
+    }
+
+    /// Evaluates with the given representation, or with complex numbers
+    /// whose parts are held in it if the context's domain is complex, and
+    /// reads the result out with the given conversion
+    fn evaluate_in<T: Number, F: Fn(T) -> Value>(&self, ctx: &Context, read: F) -> Result<Value, EvalErr> {
+        if let Some(modulus) = &ctx.modulus {
+            if T::from_literal(&modulus.0).1.overflow == Bit::On {
+                return Err(EvalErr::ModulusOverflow(modulus.0.clone()));
+            }
+        }
+
+        match ctx.domain {
+            Domain::Real => {
+                let result = self.evaluate_as::<T>(ctx)?;
+
+                match modular::<T>(ctx) {
+                    Some(modular) => Ok(read(modular.reduce(&result))),
+                    None => Ok(read(result)),
+                }
+            }
+            Domain::Complex => {
+                let Complex { re, im } = self.evaluate_as::<Complex<T>>(ctx)?;
+                Ok(Value::Complex(Box::new(read(re)), Box::new(read(im))))
+            }
+        }
+    }
+
+    /// Evaluates as the operand of the operator, call or parentheses at the
+    /// span, which a missing operand is then pointed at
+    fn operand<T: Number>(&self, span: Span, ctx: &Context) -> Result<T, EvalErr> {
+        match self.evaluate_as(ctx) {
+            Err(EvalErr::EmptyExpression(None)) => Err(EvalErr::EmptyExpression(Some(span))),
+            result => result,
+        }
+    }
+
+    /// Loads a literal right after a minus already negated in a fixed-point
+    /// format, which holds one more step below zero than above it, eg. "-1"
+    /// in Q1.31, or None if it isn't one or doesn't fit even so
+    fn negated_literal<T: Number>(&self, ctx: &Context) -> Option<T> {
+        let format = ctx.fixed?;
+        let magnitude = match self {
+            Expr::Negation(expr, _) => match &**expr {
+                Expr::Literal(n, _) => format.scale(n),
+                Expr::Decimal(d, _) => d.to_fixed(format.fraction)?,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        let (loaded, flags) = T::from_literal(&-&magnitude);
+        (flags.overflow == Bit::Off).then_some(loaded)
+    }
+
+    /// Evaluates with the given representation, regardless of the context's
+    /// width
+    pub fn evaluate_as<T: Number>(&self, ctx: &Context) -> Result<T, EvalErr> {
         use self::Expr::*;
 
         match self {
-            Empty => panic!("Cannot evaluate empty node"),
-            BinOp(binary_op) => binary_op.evaluate(),
-            Literal(n) => n.clone(),
-            Negation(expr) => -&expr.evaluate(),
-            SubExpr(expr) => expr.evaluate(),
+            Empty => Err(EvalErr::EmptyExpression(None)),
+            BinOp(binary_op) => binary_op.evaluate(ctx),
+            Literal(n, _) => {
+                let (loaded, flags) = match ctx.fixed {
+                    Some(format) => T::from_literal(&format.scale(n)),
+                    None => T::from_literal(n),
+                };
+                let sign = if n.is_negative() { Bit::On } else { Bit::Off };
+
+                settle(
+                    ctx,
+                    loaded,
+                    flags,
+                    |_| T::saturated(sign),
+                    || EvalErr::LiteralOverflow(Box::new(self.clone())),
+                )
+            }
+            Decimal(d, _) => {
+                let (loaded, flags) = match ctx.fixed {
+                    Some(format) => T::from_fixed_decimal(d, format.fraction),
+                    None => T::from_decimal(d),
+                };
+
+                settle(
+                    ctx,
+                    loaded,
+                    flags,
+                    |_| T::saturated(Bit::Off),
+                    || EvalErr::LiteralOverflow(Box::new(self.clone())),
+                )
+            }
+            // The imaginary part of a literal is 0, so turning it never
+            // overflows
+            Imaginary(expr, _) => match expr.evaluate_as::<T>(ctx)?.mul_i(&ctx.adder) {
+                Some((turned, _)) => Ok(turned),
+                None => Err(EvalErr::NotComplex(Box::new(self.clone()))),
+            },
+            Negation(expr, span) => {
+                if let Some(loaded) = self.negated_literal(ctx) {
+                    return Ok(loaded);
+                }
+
+                let operand = expr.operand::<T>(*span, ctx)?;
+                let (negated, flags) = operand.neg_using(&ctx.adder);
+
+                // Only the minimum signed value overflows, which should have
+                // become positive, while any unsigned value but 0 goes below
+                // zero
+                let sign = if T::SIGNED { Bit::Off } else { Bit::On };
+
+                // Negating without a limit on size finds which parts of a
+                // complex value didn't fit
+                settle(
+                    ctx,
+                    negated,
+                    flags,
+                    |_| match operand.widen() {
+                        Some(wide) => T::narrow_saturating(&wide.neg_using(&ctx.adder).0),
+                        None => T::saturated(sign),
+                    },
+                    || EvalErr::NegationOverflow(Box::new(self.clone())),
+                )
+            }
+            BitNot(..) if !integral::<T>(ctx) => Err(EvalErr::NotIntegral(Box::new(self.clone()))),
+            BitNot(expr, span) => {
+                let (complement, flags) = expr.operand::<T>(*span, ctx)?.not_with_flags();
+
+                // Only representations without a top Bit to stop at can
+                // overflow, which leaves nothing to saturate at either
+                settle(
+                    ctx,
+                    complement,
+                    flags,
+                    |_| None,
+                    || EvalErr::ComplementOverflow(Box::new(self.clone())),
+                )
+            }
+            SubExpr(expr, span) => expr.operand(*span, ctx),
+            Call(function, args, span) => {
+                let args = args
+                    .iter()
+                    .map(|arg| arg.operand::<T>(*span, ctx))
+                    .collect::<Result<Vec<_>, _>>()?;
+
+                match function {
+                    Function::Re => Ok(args[0].re()),
+                    Function::Im => Ok(args[0].im()),
+                    Function::Conj => {
+                        let (conjugate, flags) = args[0].conj_using(&ctx.adder);
+
+                        settle(
+                            ctx,
+                            conjugate,
+                            flags,
+                            |_| None,
+                            || EvalErr::FunctionOverflow(Box::new(self.clone())),
+                        )
+                    }
+                    // The product with the conjugate, whose imaginary part
+                    // always cancels out
+                    Function::Abs2 => {
+                        let (conjugate, _) = args[0].conj_using(&ctx.adder);
+                        let (product, flags, additions) = match ctx.fixed {
+                            Some(format) => args[0].mul_fixed_using(&conjugate, &ctx.multiplier, format.fraction),
+                            None => args[0].mul_using(&conjugate, &ctx.multiplier),
+                        };
+                        ctx.additions.set(ctx.additions.get() + additions);
+
+                        settle(
+                            ctx,
+                            product.re(),
+                            flags,
+                            |_| T::saturated(Bit::Off),
+                            || EvalErr::FunctionOverflow(Box::new(self.clone())),
+                        )
+                    }
+                    Function::Inv => match modular::<T>(ctx) {
+                        Some(modular) => {
+                            let (inverse, flags) = modular
+                                .inverse(&args[0])
+                                .ok_or_else(|| EvalErr::NotInvertible(Box::new(self.clone())))?;
+
+                            settle(ctx, inverse, flags, |_| None, || EvalErr::FunctionOverflow(Box::new(self.clone())))
+                        }
+                        None => Err(EvalErr::NotModular(Box::new(self.clone()))),
+                    },
+                    Function::Sqrt | Function::Root => self.root(function, &args, ctx),
+                }
+            }
+        }
+    }
+
+    /// Takes the root of the first argument, of the degree given by the
+    /// second for `root`
+    fn root<T: Number>(&self, function: &Function, args: &[T], ctx: &Context) -> Result<T, EvalErr> {
+        if ctx.domain == Domain::Complex {
+            return Err(EvalErr::NotReal(Box::new(self.clone())));
+        }
+
+        if ctx.fixed.is_some() {
+            return Err(EvalErr::NotIntegral(Box::new(self.clone())));
+        }
+
+        // Negative values have no integer roots to speak of, and unsigned
+        // ones are never negative
+        if args[0].sign() == Bit::On {
+            return Err(EvalErr::NegativeRoot(Box::new(self.clone())));
+        }
+
+        match function {
+            Function::Root if !T::INTEGRAL => Err(EvalErr::NotIntegral(Box::new(self.clone()))),
+            Function::Root => {
+                let (_, zero, _) = args[1].compare(&T::from_bit(Bit::Off));
+
+                if (args[1].sign() | zero) == Bit::On {
+                    return Err(EvalErr::InvalidDegree(Box::new(self.clone())));
+                }
+
+                Ok(args[0].root(&args[1]))
+            }
+            _ => Ok(args[0].sqrt()),
+        }
+    }
+}
+
+/// Whether every value is a whole number, which neither floats nor
+/// fixed-point values are
+fn integral<T: Number>(ctx: &Context) -> bool {
+    T::INTEGRAL && ctx.fixed.is_none()
+}
+
+/// Number of fraction Bits in the context's fixed-point format, if any
+fn fraction(ctx: &Context) -> usize {
+    ctx.fixed.map_or(0, |format| format.fraction)
+}
+
+/// The arithmetic modulo the context's modulus, loaded into the given
+/// representation, or None if there is no modulus
+fn modular<T: Number>(ctx: &Context) -> Option<Modular<'_, T>> {
+    ctx.modulus.as_ref().map(|modulus| Modular {
+        modulus: T::from_literal(&modulus.0).0,
+        adder: &ctx.adder,
+        multiplier: &ctx.multiplier,
+        divider: &ctx.divider,
+    })
+}
+
+/// Passes a result through unless its circuit overflowed, in which case the
+/// context's mode decides whether to wrap, fail or saturate. Representations
+/// without a limit to wrap or saturate at always fail.
+fn settle<T: Number, S, E>(
+    ctx: &Context,
+    result: T,
+    flags: Flags,
+    saturate: S,
+    error: E,
+) -> Result<T, EvalErr>
+where
+    S: FnOnce(T) -> Option<T>,
+    E: FnOnce() -> EvalErr,
+{
+    if flags.overflow == Bit::Off {
+        return Ok(result);
+    }
+
+    match ctx.mode {
+        Mode::Wrapping if T::saturated(Bit::Off).is_some() => Ok(result),
+        Mode::Saturating => saturate(result).ok_or_else(error),
+        _ => Err(error()),
+    }
+}
+
+impl fmt::Display for BinaryOp {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.0, self.1, self.2)
+    }
+}
+
+impl fmt::Display for Function {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", match self {
+            Function::Sqrt => "sqrt",
+            Function::Root => "root",
+            Function::Re => "re",
+            Function::Im => "im",
+            Function::Conj => "conj",
+            Function::Abs2 => "abs2",
+            Function::Inv => "inv",
+        })
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use self::Expr::*;
+
+        match self {
+            Empty => write!(f, "_"),
+            BinOp(binary_op) => binary_op.fmt(f),
+            Literal(n, _) => write!(f, "{}", n),
+            Decimal(d, _) => write!(f, "{}", d),
+            Imaginary(expr, _) => write!(f, "{}i", expr),
+            Negation(expr, _) => write!(f, "-{}", expr),
+            BitNot(expr, _) => write!(f, "~{}", expr),
+            SubExpr(expr, _) => write!(f, "({})", expr),
+            Call(function, args, _) => {
+                let args: Vec<_> = args.iter().map(|arg| arg.to_string()).collect();
+
+                write!(f, "{}({})", function, args.join(", "))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Context, Domain, EvalErr, Expr, Mode, Signedness, Span, Value, Width};
+    use crate::parser::context::{FixedFormat, RationalOutput};
+    use crate::types::{AdderKind, DividerKind, MultiplierKind};
+    use crate::{lexer, parser, Binary};
+
+    fn evaluate_with(s: &str, ctx: &Context) -> Result<i64, EvalErr> {
+        let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+
+        expr.evaluate_as::<Binary>(ctx).map(|n| n.to_int().unwrap())
+    }
+
+    fn evaluate(s: &str) -> Result<i64, EvalErr> {
+        evaluate_with(s, &Context::default())
+    }
+
+    fn error(s: &str) -> String {
+        evaluate(s).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn evaluate_success() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(7));
+        assert_eq!(evaluate("(1 + 2) * 3"), Ok(9));
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(512));
+        assert_eq!(evaluate("-17 % 5 - -4"), Ok(2));
+        assert_eq!(evaluate("-2 ^ 2"), Ok(-4));
+        assert_eq!(evaluate("(-2) ^ 2 + 2 * -2 ^ 3"), Ok(-12));
+        assert_eq!(evaluate("2 ^ -1"), Ok(0));
+    }
+
+    #[test]
+    fn evaluate_incomplete() {
+        assert_eq!(evaluate(""), Err(EvalErr::EmptyExpression(None)));
+        assert_eq!(evaluate("()"), Err(EvalErr::EmptyExpression(Some(Span::new(0, 2)))));
+        assert_eq!(error("5 +"), "Operation '5 + _' is missing an operand");
+        assert_eq!(error("1 + (5 * -)"), "Operation '5 * -_' is missing an operand");
+    }
+
+    #[test]
+    fn evaluate_faults() {
+        assert_eq!(error("4 / (2 - 2)"), "Operation '4 / (2 - 2)' divides by zero");
+        assert_eq!(error("1 + 4 % 0"), "Operation '4 % 0' divides by zero");
+        assert_eq!(error("0 ^ -1"), "Operation '0 ^ -1' divides by zero");
+        assert_eq!(
+            error("9223372036854775807 + 1"),
+            "Operation '9223372036854775807 + 1' overflowed"
+        );
+        assert_eq!(error("2 ^ 64"), "Operation '2 ^ 64' overflowed");
+        assert_eq!(
+            error("-(0 - 9223372036854775807 - 1)"),
+            "Negation '-(0 - 9223372036854775807 - 1)' overflowed"
+        );
+
+        match evaluate("1 + 3 * 3000000000 ^ 3") {
+            Err(EvalErr::Overflow(op)) => assert_eq!(op.to_string(), "3000000000 ^ 3"),
+            result => panic!("Unexpected result {:?}", result),
+        }
+
+        assert_eq!(Expr::Empty.to_string(), "_");
+    }
+
+    #[test]
+    fn evaluate_wrapping() {
+        let ctx = Context::with_mode(Mode::Wrapping);
+        let evaluate = |s| evaluate_with(s, &ctx);
+
+        assert_eq!(evaluate("9223372036854775807 + 1"), Ok(i64::MIN));
+        assert_eq!(evaluate("0 - 9223372036854775807 - 2"), Ok(i64::MAX));
+        assert_eq!(evaluate("(0 - 9223372036854775807 - 1) * -1"), Ok(i64::MIN));
+        assert_eq!(evaluate("(0 - 9223372036854775807 - 1) / -1"), Ok(i64::MIN));
+        assert_eq!(evaluate("(0 - 9223372036854775807 - 1) % -1"), Ok(0));
+        assert_eq!(evaluate("-(0 - 9223372036854775807 - 1)"), Ok(i64::MIN));
+        assert_eq!(evaluate("3 ^ 41"), Ok(3i64.wrapping_pow(41)));
+        assert_eq!(evaluate("2 ^ -64"), Ok(0));
+        assert!(evaluate("1 / 0").is_err());
+    }
+
+    #[test]
+    fn evaluate_checked() {
+        let ctx = Context::with_mode(Mode::Checked);
+        let evaluate = |s| evaluate_with(s, &ctx);
+
+        assert_eq!(evaluate("9223372036854775806 + 1"), Ok(i64::MAX));
+        assert!(evaluate("9223372036854775807 + 1").is_err());
+        assert!(evaluate("(0 - 9223372036854775807 - 1) % -1").is_err());
+        assert!(evaluate("3 ^ 41").is_err());
+        assert_eq!(evaluate("2 ^ -64"), Ok(0));
+    }
+
+    #[test]
+    fn evaluate_saturating() {
+        let ctx = Context::with_mode(Mode::Saturating);
+        let evaluate = |s| evaluate_with(s, &ctx);
+
+        assert_eq!(evaluate("9223372036854775807 + 1"), Ok(i64::MAX));
+        assert_eq!(evaluate("0 - 9223372036854775807 - 2"), Ok(i64::MIN));
+        assert_eq!(evaluate("9223372036854775807 - -5"), Ok(i64::MAX));
+        assert_eq!(evaluate("4000000000 * -4000000000"), Ok(i64::MIN));
+        assert_eq!(evaluate("-4000000000 * -4000000000"), Ok(i64::MAX));
+        assert_eq!(evaluate("(0 - 9223372036854775807 - 1) / -1"), Ok(i64::MAX));
+        assert_eq!(evaluate("(0 - 9223372036854775807 - 1) % -1"), Ok(0));
+        assert_eq!(evaluate("-(0 - 9223372036854775807 - 1)"), Ok(i64::MAX));
+        assert_eq!(evaluate("(-3) ^ 41"), Ok(i64::MIN));
+        assert_eq!(evaluate("(-3) ^ 40"), Ok(i64::MAX));
+        assert_eq!(evaluate("3 ^ 41 - 1"), Ok(i64::MAX - 1));
+    }
+
+    #[test]
+    fn evaluate_width() {
+        let evaluate = |s: &str, width: Width| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+
+            expr.evaluate(&Context::with_width(width)).map(|n| n.to_string())
+        };
+
+        assert_eq!(evaluate("100 + 27", Width::W8), Ok(String::from("127")));
+        assert_eq!(evaluate("0 - 100 - 28", Width::W8), Ok(String::from("-128")));
+        assert!(evaluate("100 + 28", Width::W8).is_err());
+        assert_eq!(evaluate("2 ^ 14 - 1 + 2 ^ 14", Width::W16), Ok(String::from("32767")));
+        assert_eq!(evaluate("2 ^ 31", Width::W32).unwrap_err().to_string(), "Operation '2 ^ 31' overflowed");
+        assert_eq!(
+            evaluate("2 ^ 100 / 3", Width::W128),
+            Ok(String::from("422550200076076467165567735125"))
+        );
+        assert_eq!(
+            evaluate("1 + 300", Width::W8).unwrap_err().to_string(),
+            "Literal '300' does not fit"
+        );
+
+        assert_eq!(
+            evaluate("2 ^ 200", Width::Unbounded),
+            Ok(String::from("1606938044258990275541962092341162602522202993782792835301376"))
+        );
+        assert_eq!(
+            evaluate("-170141183460469231731687303715884105728 * 3 / 7 % 1000000007", Width::Unbounded),
+            Ok(String::from("-417064061"))
+        );
+        assert_eq!(
+            evaluate("170141183460469231731687303715884105728 - 1", Width::W128),
+            Err(EvalErr::LiteralOverflow(Box::new(Expr::Literal(
+                crate::types::BigBinary::from_decimal("170141183460469231731687303715884105728").unwrap()
+            , Span::default()))))
+        );
+        assert!(evaluate("2 ^ 5000", Width::Unbounded).is_err());
+        assert!(evaluate("2 ^ 2050 * 2 ^ 2050 > 0", Width::Unbounded).is_err());
+
+        let ctx = Context { mode: Mode::Wrapping, width: Width::W8, ..Context::new() };
+        let expr = parser::parse(&lexer::lex("300 + 1").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("45")));
+
+        let ctx = Context { mode: Mode::Saturating, width: Width::W8, ..Context::new() };
+        assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("127")));
+    }
+
+    #[test]
+    fn evaluate_unsigned() {
+        let evaluate = |s: &str, mode: Mode, width: Width| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            let ctx = Context { mode, width, signedness: Signedness::Unsigned, ..Context::new() };
+
+            expr.evaluate(&ctx).map(|n| n.to_string())
+        };
+        let checked = |s| evaluate(s, Mode::Checked, Width::W64);
+
+        assert_eq!(checked("18446744073709551615"), Ok(u64::MAX.to_string()));
+        assert_eq!(checked("18446744073709551615 / 3"), Ok((u64::MAX / 3).to_string()));
+        assert_eq!(checked("18446744073709551615 % 10"), Ok(String::from("5")));
+        assert_eq!(checked("2 ^ 63 - 1 + 2 ^ 63"), Ok(u64::MAX.to_string()));
+        assert_eq!(checked("4294967296 * 4294967295"), Ok((u64::MAX - 4294967295).to_string()));
+        assert_eq!(checked("18446744073709551615 + 1").unwrap_err().to_string(), "Operation '18446744073709551615 + 1' overflowed");
+        assert_eq!(checked("1 - 2").unwrap_err().to_string(), "Operation '1 - 2' overflowed");
+        assert_eq!(checked("-1").unwrap_err().to_string(), "Negation '-1' overflowed");
+        assert_eq!(checked("-0"), Ok(String::from("0")));
+        assert!(checked("18446744073709551616").is_err());
+
+        let wrapping = |s| evaluate(s, Mode::Wrapping, Width::W64);
+        assert_eq!(wrapping("0 - 1"), Ok(u64::MAX.to_string()));
+        assert_eq!(wrapping("18446744073709551615 + 2"), Ok(String::from("1")));
+        assert_eq!(wrapping("2 ^ 64 + 5"), Ok(String::from("5")));
+
+        let saturating = |s| evaluate(s, Mode::Saturating, Width::W8);
+        assert_eq!(saturating("200 + 100"), Ok(String::from("255")));
+        assert_eq!(saturating("100 - 200"), Ok(String::from("0")));
+        assert_eq!(saturating("-5"), Ok(String::from("0")));
+        assert_eq!(saturating("16 * 16"), Ok(String::from("255")));
+        assert_eq!(saturating("300"), Ok(String::from("255")));
+
+        let big = |s| evaluate(s, Mode::Checked, Width::Unbounded);
+        assert_eq!(big("2 ^ 100 - 1"), Ok(String::from("1267650600228229401496703205375")));
+        assert!(big("2 - 2 ^ 100").is_err());
+    }
+
+    #[test]
+    fn evaluate_bitwise() {
+        assert_eq!(evaluate("(255 & 60) << 4"), Ok(960));
+        assert_eq!(evaluate("12 | 3"), Ok(15));
+        assert_eq!(evaluate("12 xor 10"), Ok(6));
+        assert_eq!(evaluate("1 | 6 xor 3 & 5"), Ok(7));
+        assert_eq!(evaluate("~0"), Ok(-1));
+        assert_eq!(evaluate("~-5 - ~5"), Ok(10));
+        assert_eq!(evaluate("-16 >> 2"), Ok(-4));
+        assert_eq!(evaluate("1 << 63"), Ok(i64::MIN));
+        assert_eq!(evaluate("1 + 1 << 2"), Ok(8));
+        assert_eq!(error("1 << 64"), "Operation '1 << 64' overflowed");
+        assert_eq!(error("1 >> -1"), "Operation '1 >> -1' overflowed");
+
+        let wrapping = Context::with_mode(Mode::Wrapping);
+        assert_eq!(evaluate_with("-1 >> 64", &wrapping), Ok(-1));
+        assert_eq!(evaluate_with("-1 >>> 64", &wrapping), Ok(0));
+
+        let unsigned = Context::with_signedness(Signedness::Unsigned);
+        let expr = parser::parse(&lexer::lex("~0 >> 60").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&unsigned).map(|n| n.to_string()), Ok(String::from("15")));
+
+        let big = Context::with_width(Width::Unbounded);
+        let expr = parser::parse(&lexer::lex("~0 << 100 >> 98").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&big).map(|n| n.to_string()), Ok(String::from("-4")));
+
+        let unsigned_big = Context { signedness: Signedness::Unsigned, ..big };
+        assert_eq!(
+            expr.evaluate(&unsigned_big).unwrap_err().to_string(),
+            "Complement '~0' overflowed"
+        );
+    }
+
+    #[test]
+    fn evaluate_shifts() {
+        assert_eq!(evaluate("-16 >>> 60"), Ok(15));
+        assert_eq!(evaluate("-16 >> 60"), Ok(-1));
+        assert_eq!(evaluate("1 rotr 1"), Ok(i64::MIN));
+        assert_eq!(evaluate("3 rotl 63"), Ok(i64::MIN + 1));
+        assert_eq!(evaluate("3 rotl -1"), Ok(i64::MIN + 1));
+        assert_eq!(evaluate("1 rotl 64 + 1"), Ok(2));
+
+        let unsigned = Context::with_signedness(Signedness::Unsigned);
+        let expr = parser::parse(&lexer::lex("(~0 >> 63) rotr 1").unwrap()).unwrap();
+        assert_eq!(
+            expr.evaluate(&unsigned).map(|n| n.to_string()),
+            Ok(String::from("9223372036854775808"))
+        );
+
+        let big = Context::with_width(Width::Unbounded);
+        let expr = parser::parse(&lexer::lex("-1 >>> 1").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&big).unwrap_err().to_string(), "Operation '-1 >>> 1' overflowed");
+    }
+
+    #[test]
+    fn evaluate_comparisons() {
+        assert_eq!(evaluate("1 < 2"), Ok(1));
+        assert_eq!(evaluate("2 < 2"), Ok(0));
+        assert_eq!(evaluate("2 <= 2"), Ok(1));
+        assert_eq!(evaluate("-3 > 2"), Ok(0));
+        assert_eq!(evaluate("-3 >= -3"), Ok(1));
+        assert_eq!(evaluate("2 + 2 == 4"), Ok(1));
+        assert_eq!(evaluate("2 + 2 != 4"), Ok(0));
+        assert_eq!(evaluate("1 < 2 == 3 > 2"), Ok(1));
+        assert_eq!(evaluate("3 > 2 > 1"), Ok(0));
+        assert_eq!(evaluate("1 << 2 < 5 & 1"), Ok(1));
+        assert_eq!(evaluate("(5 > 3) * 10"), Ok(10));
+
+        let unsigned = Context::with_signedness(Signedness::Unsigned);
+        let expr = parser::parse(&lexer::lex("~0 > 1").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&unsigned).map(|n| n.to_string()), Ok(String::from("1")));
+        assert_eq!(expr.evaluate(&Context::new()).map(|n| n.to_string()), Ok(String::from("0")));
+
+        let big = Context::with_width(Width::Unbounded);
+        let expr = parser::parse(&lexer::lex("2 ^ 200 > 2 ^ 199 * 2 - 1").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&big).map(|n| n.to_string()), Ok(String::from("1")));
+    }
+
+    #[test]
+    fn evaluate_adders() {
+        let adders = [
+            AdderKind::RippleCarry,
+            AdderKind::CarryLookahead,
+            AdderKind::CarrySelect,
+            AdderKind::KoggeStone,
+        ];
+
+        for &adder in adders.iter() {
+            let ctx = Context::with_adder(adder);
+            let evaluate = |s| evaluate_with(s, &ctx);
+
+            assert_eq!(evaluate("1234567 + 7654321 - -99"), Ok(8888987));
+            assert_eq!(evaluate("-(5 - 12)"), Ok(7));
+            assert_eq!(evaluate("9223372036854775807 - 1 + 1"), Ok(i64::MAX));
+            assert!(evaluate("9223372036854775807 + 1").is_err());
+
+            for &(width, signedness) in [(Width::W8, Signedness::Unsigned), (Width::Unbounded, Signedness::Signed)].iter() {
+                let ctx = Context { width, signedness, ..ctx.clone() };
+                let expr = parser::parse(&lexer::lex("100 + 155 - 1").unwrap()).unwrap();
+
+                assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("254")), "{:?}", adder);
+            }
         }
     }
+
+    #[test]
+    fn evaluate_multipliers() {
+        let multipliers = [
+            MultiplierKind::Array,
+            MultiplierKind::BoothRadix2,
+            MultiplierKind::BoothRadix4,
+            MultiplierKind::Wallace,
+        ];
+        let expr = parser::parse(&lexer::lex("-123456 * 7890 * (0 - 3)").unwrap()).unwrap();
+        let mut additions = Vec::new();
+
+        for &multiplier in multipliers.iter() {
+            let ctx = Context::with_multiplier(multiplier);
+
+            assert_eq!(evaluate_with("-123456 * 7890 * (0 - 3)", &ctx), Ok(2922203520));
+            assert_eq!(evaluate_with("-9223372036854775807 * -1", &ctx), Ok(i64::MAX));
+            assert!(evaluate_with("4294967296 * 2147483648", &ctx).is_err());
+
+            for &width in [Width::W8, Width::Unbounded].iter() {
+                let ctx = Context { width, ..ctx.clone() };
+                let expr = parser::parse(&lexer::lex("-11 * 11").unwrap()).unwrap();
+
+                assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("-121")), "{:?}", multiplier);
+            }
+
+            assert!(expr.evaluate(&ctx).is_ok());
+            additions.push(ctx.additions.get());
+        }
+
+        // Every multiplication through the array adds all 64 partial products
+        assert_eq!(additions[0], 128);
+        assert!(additions[1] < additions[0]);
+        assert!(additions[2] < additions[1]);
+        assert_eq!(additions[3], 126);
+
+        // A power counts the partial products of its 3 squares and 2 multiplies
+        for &(multiplier, added) in [(MultiplierKind::Array, 64), (MultiplierKind::Wallace, 63)].iter() {
+            let ctx = Context::with_multiplier(multiplier);
+            let expr = parser::parse(&lexer::lex("3 ^ 5").unwrap()).unwrap();
+
+            assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("243")));
+            assert_eq!(ctx.additions.get(), 5 * added, "{:?}", multiplier);
+        }
+    }
+
+    #[test]
+    fn evaluate_dividers() {
+        for &divider in [DividerKind::Restoring, DividerKind::NonRestoring, DividerKind::Srt].iter() {
+            let ctx = Context::with_divider(divider);
+            let evaluate = |s| evaluate_with(s, &ctx);
+
+            assert_eq!(evaluate("-17 / 5 + -17 % 5 * 100"), Ok(-203));
+            assert_eq!(evaluate("9223372036854775807 / -3"), Ok(i64::MAX / -3));
+            assert_eq!(evaluate("(0 - 9223372036854775807 - 1) % 10"), Ok(i64::MIN % 10));
+            assert!(matches!(evaluate("7 / 0"), Err(EvalErr::DivisionByZero(_))));
+            assert_eq!(evaluate("2 ^ -1 + -1 ^ -3"), Ok(-1));
+
+            for &(width, signedness) in [(Width::W8, Signedness::Unsigned), (Width::Unbounded, Signedness::Signed)].iter() {
+                let ctx = Context { width, signedness, ..ctx.clone() };
+                let expr = parser::parse(&lexer::lex("250 / 7 * 7 + 250 % 7").unwrap()).unwrap();
+
+                assert_eq!(expr.evaluate(&ctx).map(|n| n.to_string()), Ok(String::from("250")), "{:?}", divider);
+            }
+        }
+    }
+
+    #[test]
+    fn evaluate_roots() {
+        assert_eq!(evaluate("sqrt(16) + sqrt(99)"), Ok(13));
+        assert_eq!(evaluate("root(1,000,000, 3) * root(2 ^ 62, 62)"), Ok(200));
+        assert_eq!(evaluate("root(27,3)"), Ok(3));
+        assert_eq!(evaluate("sqrt(9223372036854775807)"), Ok(3037000499));
+        assert_eq!(evaluate("root(9223372036854775807, 1)"), Ok(i64::MAX));
+        assert_eq!(evaluate("-sqrt(0) + root(1, 1000)"), Ok(1));
+
+        assert_eq!(error("1 + sqrt(3 - 4)"), "Root 'sqrt(3 - 4)' of a negative value is undefined");
+        assert_eq!(error("root(8, 0)"), "Root 'root(8, 0)' needs a degree above zero");
+        assert_eq!(error("root(8, -3)"), "Root 'root(8, -3)' needs a degree above zero");
+
+        let unsigned = Context::with_signedness(Signedness::Unsigned);
+        let expr = parser::parse(&lexer::lex("sqrt(~0) + root(~0, 3)").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&unsigned).map(|n| n.to_string()), Ok(String::from("4297609540")));
+
+        let big = Context::with_width(Width::Unbounded);
+        let expr = parser::parse(&lexer::lex("sqrt(10 ^ 60) + root(3 ^ 300, 100)").unwrap()).unwrap();
+        assert_eq!(expr.evaluate(&big).map(|n| n.to_string()), Ok(String::from("1000000000000000000000000000027")));
+    }
+
+    #[test]
+    fn evaluate_floats() {
+        let float = |s: &str, ctx: &Context| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            expr.evaluate(ctx)
+        };
+
+        let bits = |s: &str| match float(s, &Context::default()) {
+            Ok(Value::F64(n)) => n.to_f64().to_bits(),
+            result => panic!("{:?}", result),
+        };
+
+        // A decimal literal anywhere switches every operand to binary64
+        assert_eq!(bits("0.1 + 0.2"), (0.1f64 + 0.2).to_bits());
+        assert_eq!(bits("1.5e-3 * 3 - 2 / 7"), (1.5e-3f64 * 3.0 - 2.0 / 7.0).to_bits());
+        assert_eq!(bits("-(1 / 0.0)"), f64::NEG_INFINITY.to_bits());
+        assert_eq!(bits("sqrt(2.0) % 0.5"), (2.0f64.sqrt() % 0.5).to_bits());
+        assert_eq!(bits("(0.1 < 0.2) + (-0.0 == 0)"), 2.0f64.to_bits());
+        assert_eq!(bits("1.5 ^ 3 + 2.0 ^ -1"), (1.5f64.powi(3) + 0.5).to_bits());
+        assert_eq!(float("2 ^ 0.5", &Context::default()).unwrap_err().to_string(), "Operation '2 ^ 0.5' only works on integers");
+
+        // Only an infinity from finite operands overflows, and only where overflow is checked
+        assert_eq!(float("1e308 * 10", &Context::default()).unwrap_err().to_string(), "Operation '1e308 * 10' overflowed");
+        assert_eq!(float("-1e308 - 1e308", &Context::default()).unwrap_err().to_string(), "Operation '-1e308 - 1e308' overflowed");
+        assert_eq!(bits("(1 / 0.0) * 10"), f64::INFINITY.to_bits());
+        assert_eq!(bits("1e308 % 1e-300"), (1e308f64 % 1e-300).to_bits());
+
+        let in_mode = |s: &str, mode: Mode| match float(s, &Context::with_mode(mode)) {
+            Ok(Value::F64(n)) => n.to_f64().to_bits(),
+            result => panic!("{:?}", result),
+        };
+        assert_eq!(in_mode("1e308 * 10", Mode::Wrapping), f64::INFINITY.to_bits());
+        assert_eq!(in_mode("1e308 * 10", Mode::Saturating), f64::MAX.to_bits());
+        assert_eq!(in_mode("-1e308 * 10", Mode::Saturating), f64::MIN.to_bits());
+
+        let single = Context::with_width(Width::W32);
+        assert_eq!(float("0.1 * 3", &single).map(|n| n.to_string()), Ok(format!("{:?}", 0.1f32 * 3.0)));
+
+        assert_eq!(float("1.5 & 1", &Context::default()).unwrap_err().to_string(), "Operation '1.5 & 1' only works on integers");
+        assert_eq!(float("~2.0", &Context::default()).unwrap_err().to_string(), "Operation '~2.0' only works on integers");
+        assert_eq!(float("root(8.0, 3)", &Context::default()).unwrap_err().to_string(), "Operation 'root(8.0, 3)' only works on integers");
+
+        // Integers only keep the whole part
+        assert_eq!(evaluate("2.75e1 + 1"), Ok(28));
+    }
+
+    #[test]
+    fn evaluate_fixed() {
+        let fixed = |s: &str, ctx: &Context| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            expr.evaluate(ctx).map(|n| n.to_string())
+        };
+
+        let q16 = Context::with_fixed("Q16.16".parse().unwrap());
+        let ok = |s: &str| fixed(s, &q16).unwrap();
+
+        assert_eq!(ok("1.5 * 2.25"), "3.375");
+        assert_eq!(ok("1 / 3"), "0.3333282470703125");
+        assert_eq!(ok("-7 / 2"), "-3.5");
+        assert_eq!(ok("0.1"), "0.100006103515625");
+        assert_eq!(ok("5.5 % 2"), "1.5");
+        assert_eq!(ok("(1.5 > 1) + 0.25"), "1.25");
+        assert!(fixed("32767 + 1", &q16).is_err());
+        assert!(fixed("200 * 200", &q16).is_err());
+        assert_eq!(fixed("1 & 1", &q16).unwrap_err().to_string(), "Operation '1 & 1' only works on integers");
+        assert_eq!(fixed("sqrt(4)", &q16).unwrap_err().to_string(), "Operation 'sqrt(4)' only works on integers");
+        assert_eq!(ok("1.5 ^ 2"), "2.25");
+        assert_eq!(ok("2 ^ -2"), "0.25");
+        assert_eq!(ok("0.5 ^ 0"), "1.0");
+        assert!(fixed("300 ^ 3", &q16).is_err());
+        assert_eq!(fixed("2 ^ 0.5", &q16).unwrap_err().to_string(), "Operation '2 ^ 0.5' only works on integers");
+
+        let saturating = Context { mode: Mode::Saturating, ..q16.clone() };
+        assert_eq!(fixed("(0 - 300) ^ 3", &saturating), Ok("-32768.0".to_string()));
+
+        let q1 = Context::with_fixed("1.31".parse().unwrap());
+        assert_eq!(fixed("0.5 * 0.5", &q1), Ok("0.25".to_string()));
+        assert_eq!(fixed("-0.5 / 0.75", &q1), Ok("-0.6666666665114462375640869140625".to_string()));
+        assert!(fixed("0.5 + 0.5", &q1).is_err());
+        assert_eq!(fixed("-1", &q1), Ok("-1.0".to_string()));
+        assert_eq!(fixed("-1.0 + 0.25", &q1), Ok("-0.75".to_string()));
+        assert_eq!(fixed("1", &q1).unwrap_err().to_string(), "Literal '1' does not fit");
+        assert!(fixed("-(1)", &q1).is_err());
+
+        let q8 = Context {
+            signedness: Signedness::Unsigned,
+            ..Context::with_fixed("Q8.8".parse().unwrap())
+        };
+        assert_eq!(fixed("255.5 + 0.25", &q8), Ok("255.75".to_string()));
+        assert_eq!(fixed("10 / 4 * 3", &q8), Ok("7.5".to_string()));
+
+        assert!("Q3.3".parse::<FixedFormat>().is_err());
+        assert!("Q16".parse::<FixedFormat>().is_err());
+        assert_eq!("Q32.32".parse::<FixedFormat>().map(|format| format.width()), Ok(Width::W64));
+    }
+
+    #[test]
+    fn evaluate_rational() {
+        let rational = |s: &str, ctx: &Context| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            expr.evaluate(ctx).map(|n| n.to_string())
+        };
+
+        let ctx = Context::with_rational(RationalOutput::Fraction);
+        let ok = |s: &str| rational(s, &ctx).unwrap();
+
+        assert_eq!(ok("1/3 + 1/6"), "1/2");
+        assert_eq!(ok("1/3 * 3"), "1");
+        assert_eq!(ok("0.75 - 1/4"), "1/2");
+        assert_eq!(ok("-2/4"), "-1/2");
+        assert_eq!(ok("1.5e-3"), "3/2000");
+        assert_eq!(ok("15/4 % (3/2)"), "3/4");
+        assert_eq!(ok("(1/3 < 0.34) + 1/2"), "3/2");
+        assert_eq!(rational("1/0", &ctx).unwrap_err().to_string(), "Operation '1 / 0' divides by zero");
+        assert_eq!(ok("2 ^ 3"), "8");
+        assert_eq!(ok("(1/2) ^ 2"), "1/4");
+        assert_eq!(ok("(2/3) ^ -2"), "9/4");
+        assert_eq!(rational("2 ^ (1/2)", &ctx).unwrap_err().to_string(), "Operation '2 ^ (1 / 2)' only works on integers");
+        assert_eq!(rational("0 ^ -1", &ctx).unwrap_err().to_string(), "Operation '0 ^ -1' divides by zero");
+
+        // Both parts share the register, so 8 Bits only hold small quotients
+        let small = Context {
+            width: Width::W8,
+            ..Context::with_rational(RationalOutput::Fraction)
+        };
+        assert_eq!(rational("100/3 * (3/100)", &small), Ok("1".to_string()));
+        assert!(rational("1/100 + 1/99", &small).is_err());
+        assert_eq!(rational("(1/2) ^ 6", &small), Ok("1/64".to_string()));
+        assert_eq!(rational("(1/2) ^ 8", &small).unwrap_err().to_string(), "Operation '(1 / 2) ^ 8' overflowed");
+
+        // Only values past the range saturate, since one within it that lost
+        // precision has no limit to clamp at
+        let saturating = Context {
+            mode: Mode::Saturating,
+            ..small.clone()
+        };
+        assert!(rational("1/100 + 1/99", &saturating).is_err());
+        assert_eq!(rational("100 + 100 + 1/2", &saturating), Ok("127".to_string()));
+        assert_eq!(rational("0 - 100 - 100 - 1/3", &saturating), Ok("-128".to_string()));
+        assert_eq!(rational("127/2 + 1/2", &saturating), Ok("64".to_string()));
+
+        let big = Context {
+            width: Width::Unbounded,
+            ..Context::with_rational(RationalOutput::Fraction)
+        };
+        assert_eq!(rational("1/100 + 1/99", &big), Ok("199/9900".to_string()));
+
+        let unsigned = Context {
+            signedness: Signedness::Unsigned,
+            ..Context::with_rational(RationalOutput::Fraction)
+        };
+        assert!(rational("1/3 - 1/2", &unsigned).is_err());
+    }
+
+    #[test]
+    fn evaluate_complex() {
+        let complex = |s: &str, ctx: &Context| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            expr.evaluate(ctx).map(|n| n.to_string())
+        };
+
+        let ctx = Context::with_domain(Domain::Complex);
+        let ok = |s: &str| complex(s, &ctx).unwrap();
+
+        assert_eq!(ok("3+4i"), "3+4i");
+        assert_eq!(ok("(3+4i) * (1-2i)"), "11-2i");
+        assert_eq!(ok("(11-2i) / (1-2i)"), "3+4i");
+        assert_eq!(ok("2i * 2i"), "-4+0i");
+        assert_eq!(ok("abs2(3+4i)"), "25+0i");
+        assert_eq!(ok("conj(3+4i)"), "3-4i");
+        assert_eq!(ok("re(3+4i) + im(3+4i)"), "7+0i");
+        assert_eq!(ok("(1+2i) == (1+2i)"), "1+0i");
+        assert_eq!(ok("(1.5+0.25i) * 2"), "3.0+0.5i");
+        assert_eq!(complex("1i < 2", &ctx).unwrap_err().to_string(), "Operation '1i < 2' only works on real numbers");
+        assert_eq!(complex("(5+2i) % 3", &ctx).unwrap_err().to_string(), "Operation '(5 + 2i) % 3' only works on real numbers");
+        assert_eq!(complex("sqrt(4)", &ctx).unwrap_err().to_string(), "Operation 'sqrt(4)' only works on real numbers");
+        assert_eq!(complex("1 / 0i", &ctx).unwrap_err().to_string(), "Operation '1 / 0i' divides by zero");
+        assert_eq!(ok("(1+1i) ^ 2"), "0+2i");
+        assert_eq!(ok("(1+2i) ^ 3"), "-11-2i");
+        assert_eq!(ok("1i ^ -1"), "0-1i");
+        assert_eq!(complex("2 ^ 1i", &ctx).unwrap_err().to_string(), "Operation '2 ^ 1i' only works on integers");
+
+        let rational = Context {
+            rational: Some(RationalOutput::Fraction),
+            ..Context::with_domain(Domain::Complex)
+        };
+        assert_eq!(complex("(1+1i) / (1-2i)", &rational), Ok("-1/5+3/5i".to_string()));
+
+        // Each part saturates on its own, and one that fits keeps its value
+        let saturating = Context {
+            mode: Mode::Saturating,
+            width: Width::W8,
+            ..Context::with_domain(Domain::Complex)
+        };
+        let saturate = |s: &str| complex(s, &saturating).unwrap();
+        assert_eq!(saturate("(100+0i) * (2+0i)"), "127+0i");
+        assert_eq!(saturate("(100+0i) + (100+1i)"), "127+1i");
+        assert_eq!(saturate("(0-100+5i) - (100+1i)"), "-128+4i");
+        assert_eq!(saturate("(100+10i) * 2i"), "-20+127i");
+        assert_eq!(saturate("-(5-128i)"), "-5+127i");
+        assert_eq!(saturate("200"), "127+0i");
+
+        let real = Context::new();
+        assert_eq!(complex("4i", &real).unwrap_err().to_string(), "Imaginary number '4i' needs the complex domain");
+        assert_eq!(complex("abs2(-3) + re(2) + im(2) + conj(5)", &real), Ok("16".to_string()));
+    }
+
+    #[test]
+    fn evaluate_modular() {
+        let modular = |s: &str, ctx: &Context| {
+            let expr = parser::parse(&lexer::lex(s).unwrap()).unwrap();
+            expr.evaluate(ctx).map(|n| n.to_string())
+        };
+
+        let ctx = Context::with_modulus("65537".parse().unwrap());
+        let ok = |s: &str| modular(s, &ctx).unwrap();
+
+        assert_eq!(ok("3 ^ 1000"), "64906");
+        assert_eq!(ok("3 ^ 65536"), "1");
+        assert_eq!(ok("inv(3) * 3"), "1");
+        assert_eq!(ok("3 ^ -1"), ok("inv(3)"));
+        assert_eq!(ok("2 / 3 * 3"), "2");
+        assert_eq!(ok("1 - 2"), "65536");
+        assert_eq!(ok("-1"), "65536");
+        assert_eq!(ok("65536 * 65536"), "1");
+        assert_eq!(ok("70000"), "4463");
+        assert_eq!(modular("1 / (65537 - 65537)", &ctx).unwrap_err().to_string(), "Operation '1 / (65537 - 65537)' divides by zero");
+        assert_eq!(modular("0.5 * 2", &ctx).unwrap_err().to_string(), "Operation '0.5 * 2' only works on integers");
+
+        let composite = Context::with_modulus("12".parse().unwrap());
+        assert_eq!(
+            modular("inv(8)", &composite).unwrap_err().to_string(),
+            "Operation 'inv(8)' needs a value with no factor in common with the modulus"
+        );
+        assert_eq!(modular("5 / 7", &composite), Ok("11".to_string()));
+
+        // Sums would leave 8 unsigned Bits if they weren't reduced on the way
+        let narrow = Context {
+            width: Width::W8,
+            signedness: Signedness::Unsigned,
+            ..Context::with_modulus("251".parse().unwrap())
+        };
+        assert_eq!(modular("250 + 250", &narrow), Ok("249".to_string()));
+        assert_eq!(modular("3 - 250", &narrow), Ok("4".to_string()));
+        assert_eq!(modular("2 ^ 250", &narrow), Ok("1".to_string()));
+        assert_eq!(modular("250 * 250 / 249", &narrow), Ok("125".to_string()));
+
+        // Products are reduced from a register twice as wide, so a modulus
+        // near the register's limit still works
+        let signed = Context { width: Width::W8, ..Context::with_modulus("100".parse().unwrap()) };
+        assert_eq!(modular("99 * 99", &signed), Ok("1".to_string()));
+        let large = Context::with_modulus("9223372036854775783".parse().unwrap());
+        assert_eq!(modular("2 ^ 100", &large), Ok("3435973836800".to_string()));
+        assert_eq!(modular("inv(9223372036854775782)", &large), Ok("9223372036854775782".to_string()));
+        assert_eq!(modular("1", &Context { width: Width::W8, ..ctx.clone() }).unwrap_err().to_string(), "Modulus '65537' does not fit");
+
+        assert_eq!(modular("inv(3)", &Context::new()).unwrap_err().to_string(), "Operation 'inv(3)' needs a modulus");
+    }
 }
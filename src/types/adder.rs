@@ -0,0 +1,278 @@
+use std::str::FromStr;
+
+use super::Bit;
+
+/// Adder: A circuit that adds two equally wide sequences of Bits, ordered
+/// from most to least significant like Binary
+pub trait Adder {
+    /// Adds with an initial carry, returning the sum along with the carry
+    /// out of the most significant Bit and the carry that went into it, as
+    /// `(sum, carry_out, carry_into_top)`
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit);
+}
+
+/// Number of Bits in each block of the carry-lookahead and carry-select
+/// adders
+const BLOCK: usize = 4;
+
+/// Chains full adders from the least significant Bit up, so every carry
+/// has to wait for the one before it
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RippleCarry;
+
+/// Computes every carry within a block of Bits directly from the generate
+/// and propagate signals of its positions, rippling only between blocks
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CarryLookahead;
+
+/// Adds every block of Bits twice, once for each possible carry in, and
+/// picks between the two sums once the real carry arrives
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct CarrySelect;
+
+/// Parallel prefix adder that combines generate and propagate signals over
+/// spans doubling in length, so every carry is ready after a logarithmic
+/// number of stages
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct KoggeStone;
+
+/// The adder a calculation is run with, picked at runtime
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AdderKind {
+    RippleCarry,
+    CarryLookahead,
+    CarrySelect,
+    KoggeStone,
+}
+
+/// Reverses Bits into least significant first order, which keeps the index
+/// of each position the same as its weight
+fn lsb_first(bits: &[Bit]) -> Vec<Bit> {
+    bits.iter().rev().copied().collect()
+}
+
+/// Produces the sum of each position from its propagate signal and carry
+/// in, where `carries` holds one more carry than there are positions
+fn finish(propagate: &[Bit], carries: &[Bit]) -> (Vec<Bit>, Bit, Bit) {
+    let n = propagate.len();
+    let sum = (0..n).rev().map(|i| propagate[i] ^ carries[i]).collect();
+    let carry_into_top = if n == 0 { carries[0] } else { carries[n - 1] };
+
+    (sum, carries[n], carry_into_top)
+}
+
+/// The generate (both Bits on) and propagate (either Bit on, but not both)
+/// signals of every position, least significant first
+fn generate_propagate(a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>) {
+    let (a, b) = (lsb_first(a), lsb_first(b));
+
+    (0..a.len()).map(|i| Bit::half_adder(a[i], b[i])).map(|(p, g)| (g, p)).unzip()
+}
+
+impl Adder for RippleCarry {
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit) {
+        let (a, b) = (lsb_first(a), lsb_first(b));
+        let mut sum = Vec::with_capacity(a.len());
+        let mut carries = vec![carry_in];
+
+        for i in 0..a.len() {
+            let (s, carry) = Bit::full_adder(a[i], b[i], carries[i]);
+
+            sum.push(s);
+            carries.push(carry);
+        }
+
+        let n = a.len();
+        let carry_into_top = if n == 0 { carry_in } else { carries[n - 1] };
+
+        sum.reverse();
+        (sum, carries[n], carry_into_top)
+    }
+}
+
+impl Adder for CarryLookahead {
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit) {
+        let (generate, propagate) = generate_propagate(a, b);
+        let mut carries = vec![carry_in];
+
+        for start in (0..generate.len()).step_by(BLOCK) {
+            let end = (start + BLOCK).min(generate.len());
+            let block_carry = carries[start];
+
+            // The carry out of position j is on if some position m at or
+            // below it generated one and everything between propagated it,
+            // or the block's carry in propagated all the way up
+            for j in start..end {
+                let mut carry = (start..=j).fold(block_carry, |acc, m| acc & propagate[m]);
+
+                for (m, &g) in generate.iter().enumerate().take(j + 1).skip(start) {
+                    let propagated = (m + 1..=j).fold(Bit::On, |acc, l| acc & propagate[l]);
+
+                    carry = carry | (g & propagated);
+                }
+
+                carries.push(carry);
+            }
+        }
+
+        finish(&propagate, &carries)
+    }
+}
+
+impl Adder for CarrySelect {
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit) {
+        let (a, b) = (lsb_first(a), lsb_first(b));
+        let mut sum = Vec::with_capacity(a.len());
+        let mut carries = vec![carry_in];
+
+        for start in (0..a.len()).step_by(BLOCK) {
+            let end = (start + BLOCK).min(a.len());
+
+            // Both possible outcomes of the block are ready before its carry
+            // in is known...
+            let outcomes: Vec<_> = [Bit::Off, Bit::On]
+                .iter()
+                .map(|&assumed| {
+                    let mut carry = assumed;
+
+                    (start..end)
+                        .map(|i| {
+                            let (s, c) = Bit::full_adder(a[i], b[i], carry);
+
+                            carry = c;
+                            (s, c)
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+
+            // ... so it only has to pass through a row of multiplexers
+            let select = carries[start];
+
+            for (&(s0, c0), &(s1, c1)) in outcomes[0].iter().zip(outcomes[1].iter()) {
+                sum.push(Bit::multiplexer(select, s0, s1));
+                carries.push(Bit::multiplexer(select, c0, c1));
+            }
+        }
+
+        let n = a.len();
+        let carry_into_top = if n == 0 { carry_in } else { carries[n - 1] };
+
+        sum.reverse();
+        (sum, carries[n], carry_into_top)
+    }
+}
+
+impl Adder for KoggeStone {
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit) {
+        let (generate, propagate) = generate_propagate(a, b);
+        let (mut g, mut p) = (generate, propagate.clone());
+        let mut distance = 1;
+
+        // After each stage, position i covers the span of positions from
+        // i - 2 * distance + 1 up to i
+        while distance < g.len() {
+            let (prev_g, prev_p) = (g.clone(), p.clone());
+
+            for i in distance..g.len() {
+                g[i] = prev_g[i] | (prev_p[i] & prev_g[i - distance]);
+                p[i] = prev_p[i] & prev_p[i - distance];
+            }
+
+            distance *= 2;
+        }
+
+        // Every span now reaches down to position 0, which leaves only the
+        // carry in to bring into each carry
+        let mut carries = vec![carry_in];
+
+        carries.extend((0..g.len()).map(|i| g[i] | (p[i] & carry_in)));
+
+        finish(&propagate, &carries)
+    }
+}
+
+impl Adder for AdderKind {
+    fn add(&self, a: &[Bit], b: &[Bit], carry_in: Bit) -> (Vec<Bit>, Bit, Bit) {
+        match self {
+            AdderKind::RippleCarry => RippleCarry.add(a, b, carry_in),
+            AdderKind::CarryLookahead => CarryLookahead.add(a, b, carry_in),
+            AdderKind::CarrySelect => CarrySelect.add(a, b, carry_in),
+            AdderKind::KoggeStone => KoggeStone.add(a, b, carry_in),
+        }
+    }
+}
+
+impl FromStr for AdderKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::AdderKind::*;
+
+        Ok(match s {
+            "ripple" => RippleCarry,
+            "lookahead" => CarryLookahead,
+            "select" => CarrySelect,
+            "kogge-stone" => KoggeStone,
+            _ => return Err(format!("Unknown adder '{}'", s)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Adder, AdderKind};
+    use super::Bit::{Off, On};
+    use crate::types::circuits::{bits, for_8_bit_pairs, value};
+
+    const ADDERS: [AdderKind; 4] = [
+        AdderKind::RippleCarry,
+        AdderKind::CarryLookahead,
+        AdderKind::CarrySelect,
+        AdderKind::KoggeStone,
+    ];
+
+    #[test]
+    fn test_8_bit_agreement() {
+        for adder in ADDERS.iter() {
+            for_8_bit_pairs(|x, y, a, b| {
+                for &carry_in in [Off, On].iter() {
+                    let c = if carry_in == On { 1 } else { 0 };
+                    let total = x + y + c;
+
+                    let (sum, carry_out, carry_into_top) = adder.add(a, b, carry_in);
+
+                    // The carry into the top Bit is whatever overflowed out
+                    // of the lower seven
+                    let lower = (x & 0x7f) + (y & 0x7f) + c;
+
+                    assert_eq!(value(&sum), total & 0xff, "{:?}: {} + {} + {}", adder, x, y, c);
+                    assert_eq!(carry_out == On, total > 0xff, "{:?}: {} + {} + {}", adder, x, y, c);
+                    assert_eq!(carry_into_top == On, lower > 0x7f, "{:?}: {} + {} + {}", adder, x, y, c);
+                }
+            });
+        }
+    }
+
+    #[test]
+    fn test_odd_widths_agree() {
+        let operands = [
+            (0, 0),
+            (1, u128::MAX),
+            (0x5555_5555_5555_5555, 0xaaaa_aaaa_aaaa_aaab),
+            (123_456_789_012_345_678_901_234, 987_654_321_098_765_432_109_876),
+            (u128::MAX, u128::MAX),
+        ];
+
+        for width in [1, 3, 5, 13, 64, 100, 128].iter() {
+            for (x, y) in operands.iter() {
+                let (a, b) = (bits(*x, *width), bits(*y, *width));
+                let expected = AdderKind::RippleCarry.add(&a, &b, On);
+
+                for adder in ADDERS.iter() {
+                    assert_eq!(adder.add(&a, &b, On), expected, "{:?}: {} + {} at {}", adder, x, y, width);
+                }
+            }
+        }
+    }
+}
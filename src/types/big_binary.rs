@@ -0,0 +1,1052 @@
+use std::cmp::PartialEq;
+use std::fmt;
+use std::num::ParseIntError;
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Sub};
+
+use super::adder::{Adder, RippleCarry};
+use super::divider::{Divider, Restoring};
+use super::multiplier::{Array, Multiplier};
+use super::{Binary, Bit, Flags, Int, Number, Shift};
+
+/// BigBinary: Sequence of Bits like Binary, ordered from most to least
+/// significant, that grows as needed instead of overflowing
+///
+/// There is always at least one Bit, and no more leading copies of the
+/// sign Bit than needed to represent the value.
+#[derive(Clone)]
+pub struct BigBinary(Vec<Bit>);
+
+impl BigBinary {
+    /// Largest result that any circuit, or a decimal literal, will build
+    /// before giving up and raising overflow, since anything longer takes
+    /// too long to work with
+    pub const MAX_BITS: usize = 1 << 12;
+
+    /// Create a BigBinary from Bits, dropping redundant copies of the sign
+    pub fn from_bits(mut bits: Vec<Bit>) -> BigBinary {
+        if bits.is_empty() {
+            bits.push(Bit::Off);
+        }
+
+        let redundant = bits
+            .windows(2)
+            .take_while(|pair| pair[0] == pair[1])
+            .count();
+
+        bits.drain(..redundant);
+        BigBinary(bits)
+    }
+
+    /// Create a BigBinary representing 0
+    pub fn zero() -> BigBinary {
+        BigBinary(vec![Bit::Off])
+    }
+
+    /// Create a BigBinary representing 1
+    pub fn one() -> BigBinary {
+        BigBinary(vec![Bit::Off, Bit::On])
+    }
+
+    /// Create a BigBinary from an int
+    pub fn from_int<T: Int>(n: T) -> BigBinary {
+        BigBinary::from_binary(&Binary::<128>::from_int(n))
+    }
+
+    /// Create a BigBinary holding the same value as a Binary
+    pub fn from_binary<const N: usize>(binary: &Binary<N>) -> BigBinary {
+        BigBinary::from_bits((0..N).map(|i| binary.get(i)).collect())
+    }
+
+    /// Create a BigBinary holding the value of a Binary read as unsigned
+    pub fn from_unsigned<const N: usize>(binary: &Binary<N>) -> BigBinary {
+        BigBinary::from_bits(std::iter::once(Bit::Off).chain((0..N).map(|i| binary.get(i))).collect())
+    }
+
+    /// Create a BigBinary from decimal digits, running each one through a
+    /// multiply-by-ten and add circuit, or None if one isn't a digit or the
+    /// value needs more than `MAX_BITS`
+    pub fn from_decimal(digits: &str) -> Option<BigBinary> {
+        let mut accumulator = BigBinary::zero();
+
+        for c in digits.chars() {
+            let digit = BigBinary::from_int(c.to_digit(10)? as i8);
+
+            accumulator = &accumulator.times_ten() + &digit;
+
+            if accumulator.width() > BigBinary::MAX_BITS {
+                return None;
+            }
+        }
+
+        Some(accumulator)
+    }
+
+    /// Multiplies by ten as `(n << 3) + (n << 1)`, which takes a single adder
+    /// rather than a whole multiplier
+    pub fn times_ten(&self) -> BigBinary {
+        let shifted = |by: usize| {
+            BigBinary::from_bits(self.0.iter().cloned().chain(std::iter::repeat_n(Bit::Off, by)).collect())
+        };
+
+        &shifted(3) + &shifted(1)
+    }
+
+    /// Create a BigBinary from digits in base 2, 8 or 16, each of which is
+    /// simply wired to its own group of Bits, read as unsigned
+    pub fn from_radix(digits: &str, radix: u32) -> Option<BigBinary> {
+        let width = match radix {
+            2 => 1,
+            8 => 3,
+            16 => 4,
+            _ => return None,
+        };
+
+        let mut bits = vec![Bit::Off];
+
+        for c in digits.chars() {
+            let digit = c.to_digit(radix)?;
+            bits.extend((0..width).rev().map(|i| if digit >> i & 1 == 1 { Bit::On } else { Bit::Off }));
+        }
+
+        Some(BigBinary::from_bits(bits))
+    }
+
+    /// Attempt to convert BigBinary to an int, failing if it doesn't fit
+    pub fn to_int<T: Int>(&self) -> Result<T, ParseIntError> {
+        // As with Binary, from_str_radix wants a sign and a magnitude rather
+        // than two's complement
+        let mut s = String::with_capacity(self.width() + 1);
+
+        if self.is_negative() {
+            s.push('-');
+        }
+
+        for bit in self.magnitude().0 {
+            s.push(if bit == Bit::On { '1' } else { '0' });
+        }
+
+        T::from_str_radix(s.as_str(), 2)
+    }
+
+    /// Sign-extends or truncates to a register of fixed width, raising
+    /// overflow if the value doesn't fit
+    pub fn resize_with_flags<const N: usize>(&self) -> (Binary<N>, Flags) {
+        let extended = self.extend(N);
+        let mut resized = Binary::<N>::zero();
+        let mut overflow = Bit::Off;
+
+        for (i, &bit) in extended[extended.len() - N..].iter().enumerate() {
+            resized.set(i, bit);
+        }
+
+        // Any Bits truncated off the top must have matched the new sign
+        for &bit in &extended[..extended.len() - N] {
+            overflow = overflow | (bit ^ resized.get(0));
+        }
+
+        let flags = Flags {
+            overflow,
+            ..Flags::from_result(&resized)
+        };
+
+        (resized, flags)
+    }
+
+    /// Zero-extends or truncates to a register of fixed width read as
+    /// unsigned, raising overflow if the value is negative or doesn't fit
+    pub fn resize_unsigned_with_flags<const N: usize>(&self) -> (Binary<N>, Flags) {
+        let extended = self.extend(N);
+        let mut resized = Binary::<N>::zero();
+        let mut overflow = self.0[0];
+
+        for (i, &bit) in extended[extended.len() - N..].iter().enumerate() {
+            resized.set(i, bit);
+        }
+
+        // Any Bits truncated off the top must have been off
+        for &bit in &extended[..extended.len() - N] {
+            overflow = overflow | bit;
+        }
+
+        let flags = Flags {
+            overflow,
+            ..Flags::from_result(&resized)
+        };
+
+        (resized, flags)
+    }
+
+    /// Returns the number of Bits
+    pub fn width(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns Bit at given position
+    pub fn get(&self, i: usize) -> Bit {
+        self.0[i]
+    }
+
+    /// Returns whether or not BigBinary represents negative number
+    pub fn is_negative(&self) -> bool {
+        self.0[0] == Bit::On
+    }
+
+    /// Returns On if any Bit is on, ie. the BigBinary is not 0
+    pub fn any(&self) -> Bit {
+        self.0.iter().fold(Bit::Off, |acc, &b| acc | b)
+    }
+
+    /// Drops the lowest `fraction` Bits of a fixed-point value, or returns
+    /// None if any of them is on, since it then isn't a whole number
+    pub fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        let kept = self.width().saturating_sub(fraction);
+
+        if self.0[kept..].contains(&Bit::On) {
+            return None;
+        }
+
+        match kept {
+            0 => Some(BigBinary::zero()),
+            _ => Some(BigBinary::from_bits(self.0[..kept].to_vec())),
+        }
+    }
+
+    /// Returns the absolute value
+    pub fn magnitude(&self) -> BigBinary {
+        if self.is_negative() {
+            -self
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Magnitude comparator circuit over both operands extended to the same
+    /// width, returning `(less, equal, greater)` as with Binary
+    pub fn compare(&self, other: &BigBinary) -> (Bit, Bit, Bit) {
+        let len = self.width().max(other.width());
+        let (a, b) = (self.extend(len), other.extend(len));
+        let mut less = Bit::Off;
+        let mut equal = Bit::On;
+        let mut greater = Bit::Off;
+
+        // The sign Bits are inverted, since an on sign makes a value smaller
+        for i in 0..len {
+            let (a, b) = if i == 0 { (!a[i], !b[i]) } else { (a[i], b[i]) };
+
+            less = less | (equal & !a & b);
+            greater = greater | (equal & a & !b);
+            equal = equal & !(a ^ b);
+        }
+
+        (less, equal, greater)
+    }
+
+    /// Returns the Bits sign-extended to at least the given length
+    fn extend(&self, len: usize) -> Vec<Bit> {
+        let mut bits = vec![self.0[0]; len.saturating_sub(self.width())];
+
+        bits.extend_from_slice(&self.0);
+        bits
+    }
+
+    /// Derives the zero and negative flags, with every other flag off
+    fn flags(&self) -> Flags {
+        Flags {
+            carry: Bit::Off,
+            overflow: Bit::Off,
+            zero: !self.any(),
+            negative: self.0[0],
+            divide_by_zero: Bit::Off,
+        }
+    }
+
+    /// Derives the flags of a sum or product, raising overflow if it grew
+    /// longer than `MAX_BITS`
+    fn bounded_flags(&self) -> Flags {
+        let overflow = if self.width() > BigBinary::MAX_BITS { Bit::On } else { Bit::Off };
+
+        Flags {
+            overflow,
+            ..self.flags()
+        }
+    }
+
+    /// Full addition circuit with one more Bit than the longer operand, so
+    /// that the sum can never overflow
+    fn add_with_carry(&self, other: &BigBinary, carry_in: Bit, adder: &dyn Adder) -> BigBinary {
+        let len = self.width().max(other.width()) + 1;
+        let (a, b) = (self.extend(len), other.extend(len));
+        let (sum, _, _) = adder.add(&a, &b, carry_in);
+
+        BigBinary::from_bits(sum)
+    }
+
+    /// Flips every Bit
+    fn invert(&self) -> BigBinary {
+        BigBinary(self.0.iter().map(|&b| !b).collect())
+    }
+
+    /// Adds other, which never raises carry, and only raises overflow beyond
+    /// `MAX_BITS`
+    pub fn add_with_flags(&self, other: &BigBinary) -> (BigBinary, Flags) {
+        self.add_using(other, &RippleCarry)
+    }
+
+    /// Adds other with the given adder
+    pub fn add_using(&self, other: &BigBinary, adder: &dyn Adder) -> (BigBinary, Flags) {
+        let sum = self.add_with_carry(other, Bit::Off, adder);
+        let flags = sum.bounded_flags();
+
+        (sum, flags)
+    }
+
+    /// Subtracts other by adding its complement with an initial carry
+    pub fn sub_with_flags(&self, other: &BigBinary) -> (BigBinary, Flags) {
+        self.sub_using(other, &RippleCarry)
+    }
+
+    /// Subtracts other with the given adder
+    pub fn sub_using(&self, other: &BigBinary, adder: &dyn Adder) -> (BigBinary, Flags) {
+        let difference = self.add_with_carry(&other.invert(), Bit::On, adder);
+        let flags = difference.bounded_flags();
+
+        (difference, flags)
+    }
+
+    /// Inverts the sign, which never overflows
+    pub fn neg_with_flags(&self) -> (BigBinary, Flags) {
+        BigBinary::zero().sub_with_flags(self)
+    }
+
+    /// Multiplier that uses basic series of shifts and adding partial
+    /// products of the magnitudes, with room for every Bit of the product
+    pub fn mul_with_flags(&self, other: &BigBinary) -> (BigBinary, Flags) {
+        let (product, flags, _) = self.mul_using(other, &Array);
+
+        (product, flags)
+    }
+
+    /// Multiplies the magnitudes with the given multiplier, also returning
+    /// how many partial products it added
+    pub fn mul_using(&self, other: &BigBinary, multiplier: &dyn Multiplier) -> (BigBinary, Flags, usize) {
+        let negate = self.0[0] ^ other.0[0];
+        let (multiplicand, multipliers) = (self.magnitude(), other.magnitude());
+        let len = multiplicand.width().max(multipliers.width());
+        let (multiplicand, multipliers) = (multiplicand.extend(len), multipliers.extend(len));
+
+        // Both magnitudes have an Off sign Bit, so the product does too
+        let (bits, additions) = multiplier.multiply(&multiplicand, &multipliers);
+        let magnitude = BigBinary::from_bits(bits);
+
+        let product = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude
+        };
+        let flags = product.bounded_flags();
+
+        (product, flags, additions)
+    }
+
+    /// Emulates long division by subtracting the divisor from a partial
+    /// dividend and keeping the difference whenever it isn't negative
+    ///
+    /// As with Binary, the quotient truncates toward zero and the remainder
+    /// takes the sign of the dividend, and the flags describe the quotient.
+    pub fn div_rem_with_flags(&self, other: &BigBinary) -> (BigBinary, BigBinary, Flags) {
+        self.div_rem_using(other, &Restoring)
+    }
+
+    /// Divides the magnitudes with the given divider
+    pub fn div_rem_using(&self, other: &BigBinary, divider: &dyn Divider) -> (BigBinary, BigBinary, Flags) {
+        let negate = self.0[0] ^ other.0[0];
+        let (dividend, divisor) = (self.magnitude(), other.magnitude());
+        let len = dividend.width().max(divisor.width());
+
+        // Magnitudes always start with an Off sign Bit, which the quotient
+        // and remainder keep
+        let (quotient, remainder) = divider.divide(&dividend.extend(len), &divisor.extend(len));
+        let (quotient, remainder) = (BigBinary::from_bits(quotient), BigBinary::from_bits(remainder));
+
+        let quotient = if negate == Bit::On {
+            -&quotient
+        } else {
+            quotient
+        };
+
+        let remainder = if self.is_negative() {
+            -&remainder
+        } else {
+            remainder
+        };
+
+        let flags = Flags {
+            divide_by_zero: !divisor.any(),
+            ..quotient.flags()
+        };
+
+        (quotient, remainder, flags)
+    }
+
+    /// Multiplies as fixed-point values with the given number of fraction
+    /// Bits, dropping as many from the product of the magnitudes, which
+    /// truncates toward zero as with Binary
+    pub fn mul_fixed_using(&self, other: &BigBinary, multiplier: &dyn Multiplier, fraction: usize) -> (BigBinary, Flags, usize) {
+        let negate = self.0[0] ^ other.0[0];
+        let (bits, _, additions) = self.magnitude().mul_using(&other.magnitude(), multiplier);
+        let (magnitude, _) = bits.shift_with_flags(&BigBinary::from_int(fraction as i64), Shift::ArithmeticRight);
+
+        let product = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude
+        };
+        let flags = product.flags();
+
+        (product, flags, additions)
+    }
+
+    /// Divides as fixed-point values with the given number of fraction Bits,
+    /// shifting the dividend's magnitude up by as many first so the quotient
+    /// keeps them, which raises overflow beyond `MAX_BITS`
+    pub fn div_fixed_using(&self, other: &BigBinary, divider: &dyn Divider, fraction: usize) -> (BigBinary, Flags) {
+        let negate = self.0[0] ^ other.0[0];
+        let (dividend, shifted) = self.magnitude().shift_with_flags(&BigBinary::from_int(fraction as i64), Shift::Left);
+        let (magnitude, _, flags) = dividend.div_rem_using(&other.magnitude(), divider);
+
+        let quotient = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude
+        };
+
+        let flags = Flags {
+            overflow: shifted.overflow,
+            divide_by_zero: flags.divide_by_zero,
+            ..quotient.flags()
+        };
+
+        (quotient, flags)
+    }
+
+    /// Raises to the power of the exponent with a square-and-multiply
+    /// circuit, raising overflow instead of building a result longer than
+    /// `MAX_BITS`
+    ///
+    /// As with Binary, a negative exponent yields the truncated reciprocal
+    /// and `0 ^ 0` is defined as 1.
+    pub fn pow_with_flags(&self, exp: &BigBinary) -> (BigBinary, Flags) {
+        let (power, flags, _) = self.pow_using(exp, &Array, &Restoring);
+
+        (power, flags)
+    }
+
+    /// Raises to the power with the given multiplier and divider, also
+    /// returning how many partial products the multiplier added, see
+    /// `pow_with_flags`
+    pub fn pow_using(&self, exp: &BigBinary, multiplier: &dyn Multiplier, divider: &dyn Divider) -> (BigBinary, Flags, usize) {
+        let magnitude = exp.magnitude();
+        let mut result = BigBinary::one();
+        let mut overflow = Bit::Off;
+        let mut additions = 0;
+
+        for &bit in &magnitude.0 {
+            if result.width() * 2 > BigBinary::MAX_BITS {
+                overflow = Bit::On;
+                break;
+            }
+
+            let (squared, _, added) = result.mul_using(&result, multiplier);
+            result = squared;
+            additions += added;
+
+            if bit == Bit::On {
+                let (product, flags, added) = result.mul_using(self, multiplier);
+                result = product;
+                additions += added;
+
+                if flags.overflow == Bit::On {
+                    overflow = Bit::On;
+                    break;
+                }
+            }
+        }
+
+        if exp.is_negative() {
+            // Any power that grew too large has a reciprocal that truncates
+            // to 0, just like every other power of a base beyond 1 and -1
+            if overflow == Bit::On {
+                let zero = BigBinary::zero();
+                let flags = zero.flags();
+
+                return (zero, flags, additions);
+            }
+
+            let (quotient, _, flags) = BigBinary::one().div_rem_using(&result, divider);
+
+            (quotient, flags, additions)
+        } else {
+            let flags = Flags {
+                overflow,
+                ..result.flags()
+            };
+
+            (result, flags, additions)
+        }
+    }
+
+    /// Digit-by-digit square root of a value that isn't negative, bringing
+    /// down two Bits at a time like Binary, with room for every Bit
+    pub fn sqrt(&self) -> BigBinary {
+        let mut bits = self.0.clone();
+
+        if bits.len() % 2 != 0 {
+            bits.insert(0, Bit::Off);
+        }
+
+        let mut root = BigBinary::zero();
+        let mut remainder = BigBinary::zero();
+
+        for pair in bits.chunks(2) {
+            let mut brought_down = remainder.0.clone();
+            brought_down.extend_from_slice(pair);
+            remainder = BigBinary::from_bits(brought_down);
+
+            let mut trial = root.0.clone();
+            trial.extend_from_slice(&[Bit::Off, Bit::On]);
+
+            let difference = &remainder - &BigBinary::from_bits(trial);
+            let fits = !difference.0[0];
+
+            if fits == Bit::On {
+                remainder = difference;
+            }
+
+            let mut grown = root.0.clone();
+            grown.push(fits);
+            root = BigBinary::from_bits(grown);
+        }
+
+        root
+    }
+
+    /// Integer root of the given degree of a value that isn't negative,
+    /// settling one Bit at a time from the top like Binary
+    ///
+    /// Raising a candidate with its top Bit at weight `k` gives at least
+    /// `2 ^ (k * degree)`, so weights beyond the width divided by the degree
+    /// are never tried.
+    pub fn root(&self, degree: &BigBinary) -> BigBinary {
+        let top = match degree.to_int::<i64>() {
+            Ok(n) if n > 0 => (self.width() - 1) / n as usize,
+            _ => 0,
+        };
+
+        let mut root = vec![Bit::Off; top + 2];
+
+        for i in 1..root.len() {
+            root[i] = Bit::On;
+
+            let (power, flags) = BigBinary::from_bits(root.clone()).pow_with_flags(degree);
+            let (_, _, greater) = power.compare(self);
+
+            root[i] = !(flags.overflow | greater);
+        }
+
+        BigBinary::from_bits(root)
+    }
+
+    /// Shifts by the amount, which without a fixed width means appending
+    /// zeros for a left shift and dropping the least significant Bits for a
+    /// right shift, until only copies of the sign are left
+    ///
+    /// Overflow is raised for a negative amount, for a left shift that would
+    /// grow beyond `MAX_BITS`, and for anything without a top Bit to fill
+    /// from or rotate through: a logical shift of a negative value, and
+    /// every rotation.
+    pub fn shift_with_flags(&self, amount: &BigBinary, shift: Shift) -> (BigBinary, Flags) {
+        let shifted = match (shift, amount.to_int::<i64>()) {
+            _ if amount.is_negative() => None,
+            (Shift::Left, Ok(k)) if self.width() + k as usize <= BigBinary::MAX_BITS => {
+                let mut bits = self.0.clone();
+
+                bits.resize(self.width() + k as usize, Bit::Off);
+                Some(BigBinary::from_bits(bits))
+            }
+            (Shift::LogicalRight, _) if self.is_negative() => None,
+            (Shift::LogicalRight | Shift::ArithmeticRight, k) => {
+                let kept = match k {
+                    Ok(k) => self.width().saturating_sub(k as usize),
+                    Err(_) => 0,
+                };
+
+                // Shifting every Bit out leaves only the sign
+                Some(if kept == 0 {
+                    BigBinary(vec![self.0[0]])
+                } else {
+                    BigBinary::from_bits(self.0[..kept].to_vec())
+                })
+            }
+            _ => None,
+        };
+
+        match shifted {
+            Some(shifted) => {
+                let flags = shifted.flags();
+
+                (shifted, flags)
+            }
+            None => {
+                let zero = BigBinary::zero();
+                let flags = Flags {
+                    overflow: Bit::On,
+                    ..zero.flags()
+                };
+
+                (zero, flags)
+            }
+        }
+    }
+
+    /// Applies a Bit operation to every position of both operands, after
+    /// extending them to the same width
+    fn zip_with<F: Fn(Bit, Bit) -> Bit>(&self, other: &BigBinary, op: F) -> BigBinary {
+        let len = self.width().max(other.width());
+        let (a, b) = (self.extend(len), other.extend(len));
+
+        BigBinary::from_bits(a.iter().zip(b.iter()).map(|(&x, &y)| op(x, y)).collect())
+    }
+}
+
+impl fmt::Debug for BigBinary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0b")?;
+
+        for (i, bit) in self.0.iter().enumerate() {
+            if (self.width() - i) % 4 == 0 {
+                write!(f, "_")?;
+            }
+            bit.fmt(f)?
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for BigBinary {
+    /// Prints decimal digits by doubling a number held in chunks of nine
+    /// decimal digits once for every Bit, since dividing by ten over
+    /// thousands of Bits takes far too long
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        const CHUNK: usize = 9;
+        const BASE: u32 = 1_000_000_000;
+
+        // Least significant chunk first
+        let mut chunks: Vec<u32> = vec![0];
+
+        for bit in self.magnitude().0 {
+            let mut carry = (bit == Bit::On) as u32;
+
+            for chunk in chunks.iter_mut() {
+                let doubled = *chunk * 2 + carry;
+                carry = doubled / BASE;
+                *chunk = doubled % BASE;
+            }
+
+            if carry > 0 {
+                chunks.push(carry);
+            }
+        }
+
+        if self.is_negative() {
+            write!(f, "-")?;
+        }
+
+        // Every chunk but the first is padded out to its full width
+        let mut chunks = chunks.iter().rev();
+        write!(f, "{}", chunks.next().unwrap_or(&0))?;
+
+        for chunk in chunks {
+            write!(f, "{:0width$}", chunk, width = CHUNK)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl PartialEq for BigBinary {
+    fn eq(&self, other: &BigBinary) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl BitAnd<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn bitand(self, other: &BigBinary) -> BigBinary {
+        self.zip_with(other, |x, y| x & y)
+    }
+}
+
+impl BitOr<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn bitor(self, other: &BigBinary) -> BigBinary {
+        self.zip_with(other, |x, y| x | y)
+    }
+}
+
+impl BitXor<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn bitxor(self, other: &BigBinary) -> BigBinary {
+        self.zip_with(other, |x, y| x ^ y)
+    }
+}
+
+impl Not for &BigBinary {
+    type Output = BigBinary;
+
+    fn not(self) -> BigBinary {
+        self.invert()
+    }
+}
+
+impl Eq for BigBinary {}
+
+impl Neg for &BigBinary {
+    type Output = BigBinary;
+
+    fn neg(self) -> BigBinary {
+        self.neg_with_flags().0
+    }
+}
+
+impl Add<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn add(self, other: &BigBinary) -> BigBinary {
+        self.add_with_flags(other).0
+    }
+}
+
+impl Sub<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn sub(self, other: &BigBinary) -> BigBinary {
+        self.sub_with_flags(other).0
+    }
+}
+
+impl Mul<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn mul(self, other: &BigBinary) -> BigBinary {
+        self.mul_with_flags(other).0
+    }
+}
+
+impl Div<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn div(self, other: &BigBinary) -> BigBinary {
+        self.div_rem_with_flags(other).0
+    }
+}
+
+impl Rem<&BigBinary> for &BigBinary {
+    type Output = BigBinary;
+
+    fn rem(self, other: &BigBinary) -> BigBinary {
+        self.div_rem_with_flags(other).1
+    }
+}
+
+impl Number for BigBinary {
+    const SIGNED: bool = true;
+
+    type Wide = BigBinary;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        (n.clone(), n.flags())
+    }
+
+    fn sign(&self) -> Bit {
+        self.0[0]
+    }
+
+    fn low_bit(&self) -> Bit {
+        self.0[self.width() - 1]
+    }
+
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        BigBinary::to_integer(self, fraction)
+    }
+
+    fn saturated(_sign: Bit) -> Option<Self> {
+        None
+    }
+
+    fn widen(&self) -> Option<BigBinary> {
+        Some(self.clone())
+    }
+
+    fn narrow_saturating(wide: &BigBinary) -> Option<Self> {
+        Some(wide.clone())
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        BigBinary::add_using(self, other, adder)
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        BigBinary::sub_using(self, other, adder)
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        BigBinary::mul_using(self, other, multiplier)
+    }
+
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        BigBinary::div_rem_using(self, other, divider)
+    }
+
+    fn mul_fixed_using(&self, other: &Self, multiplier: &dyn Multiplier, fraction: usize) -> (Self, Flags, usize) {
+        BigBinary::mul_fixed_using(self, other, multiplier, fraction)
+    }
+
+    fn div_fixed_using(&self, other: &Self, divider: &dyn Divider, fraction: usize) -> (Self, Flags) {
+        BigBinary::div_fixed_using(self, other, divider, fraction)
+    }
+
+    fn pow_with_flags(&self, exp: &Self) -> (Self, Flags) {
+        BigBinary::pow_with_flags(self, exp)
+    }
+
+    fn pow_using(&self, exp: &Self, multiplier: &dyn Multiplier, divider: &dyn Divider) -> (Self, Flags, usize) {
+        BigBinary::pow_using(self, exp, multiplier, divider)
+    }
+
+    fn and_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self & other;
+        let flags = result.flags();
+
+        (result, flags)
+    }
+
+    fn or_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self | other;
+        let flags = result.flags();
+
+        (result, flags)
+    }
+
+    fn xor_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self ^ other;
+        let flags = result.flags();
+
+        (result, flags)
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        let result = !self;
+        let flags = result.flags();
+
+        (result, flags)
+    }
+
+    fn shift_with_flags(&self, amount: &Self, shift: Shift) -> (Self, Flags) {
+        BigBinary::shift_with_flags(self, amount, shift)
+    }
+
+    fn sqrt(&self) -> Self {
+        BigBinary::sqrt(self)
+    }
+
+    fn root(&self, degree: &Self) -> Self {
+        BigBinary::root(self, degree)
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        BigBinary::from_bits(vec![Bit::Off, bit])
+    }
+
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        BigBinary::compare(self, other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigBinary, Shift};
+    use super::super::Bit::{Off, On};
+
+    fn big(n: i128) -> BigBinary {
+        BigBinary::from_int(n)
+    }
+
+    #[test]
+    fn test_from_bits() {
+        assert_eq!(BigBinary::from_bits(vec![]), BigBinary::zero());
+        assert_eq!(BigBinary::from_bits(vec![Off, Off, Off, On]), BigBinary::one());
+        assert_eq!(BigBinary::from_bits(vec![On, On, On]).width(), 1);
+        assert_eq!(big(-1).width(), 1);
+        assert_eq!(big(i64::MAX as i128).width(), 64);
+        assert_eq!(big(i64::MIN as i128).width(), 64);
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let args = [
+            (0, 0),
+            (0, -1),
+            (7, 3),
+            (-7, 3),
+            (7, -3),
+            (-7, -3),
+            (i64::MAX as i128, i64::MAX as i128),
+            (i64::MIN as i128, -1),
+            (i64::MIN as i128, i64::MIN as i128),
+            (98237498237498237, -2398472938),
+        ];
+
+        for (x, y) in args.iter() {
+            let (a, b) = (big(*x), big(*y));
+
+            assert_eq!((&a + &b).to_int(), Ok(x + y), "{} + {}", x, y);
+            assert_eq!((&a - &b).to_int(), Ok(x - y), "{} - {}", x, y);
+            assert_eq!((&a * &b).to_int(), Ok(x * y), "{} * {}", x, y);
+            assert_eq!((-&a).to_int(), Ok(-x), "-{}", x);
+
+            if *y != 0 {
+                assert_eq!((&a / &b).to_int(), Ok(x / y), "{} / {}", x, y);
+                assert_eq!((&a % &b).to_int(), Ok(x % y), "{} % {}", x, y);
+            }
+        }
+
+        assert_eq!(big(5).div_rem_with_flags(&big(0)).2.divide_by_zero, On);
+
+        let power = big(2).pow_with_flags(&big(2050)).0;
+        assert_eq!(power.mul_with_flags(&power).1.overflow, On);
+        assert_eq!(power.add_with_flags(&power).1.overflow, Off);
+        let power = big(1).shift_with_flags(&big(4094), Shift::Left).0;
+        assert_eq!(power.add_with_flags(&power).1.overflow, On);
+    }
+
+    #[test]
+    fn test_to_integer() {
+        assert_eq!(big(-12).to_integer(2), Some(big(-3)));
+        assert_eq!(big(-13).to_integer(2), None);
+        assert_eq!(big(5).to_integer(0), Some(big(5)));
+        assert_eq!(big(0).to_integer(70), Some(big(0)));
+    }
+
+    #[test]
+    fn test_pow() {
+        let pow = |x: i128, y: i128| big(x).pow_with_flags(&big(y)).0.to_int::<i128>();
+
+        assert_eq!(pow(0, 0), Ok(1), "0 ^ 0");
+        assert_eq!(pow(2, 126), Ok(1 << 126), "2 ^ 126");
+        assert_eq!(pow(-3, 79), Ok(-3i128.pow(79)), "-3 ^ 79");
+        assert_eq!(pow(-1, -7), Ok(-1), "-1 ^ -7");
+        assert_eq!(pow(2, -3), Ok(0), "2 ^ -3");
+        assert_eq!(big(0).pow_with_flags(&big(-1)).1.divide_by_zero, On, "0 ^ -1");
+
+        let (result, flags) = big(2).pow_with_flags(&big(1 << 20));
+        assert_eq!((result.width() <= BigBinary::MAX_BITS, flags.overflow), (true, On));
+        assert_eq!(big(-3).pow_with_flags(&big(2600)).1.overflow, On);
+        assert_eq!(big(2).pow_with_flags(&big(-(1 << 20))).0, big(0));
+    }
+
+    #[test]
+    fn test_decimal() {
+        let n = BigBinary::from_decimal("1606938044258990275541962092341162602522202993782792835301376");
+
+        assert_eq!(n, Some(big(2).pow_with_flags(&big(200)).0));
+        assert_eq!(
+            n.unwrap().to_string(),
+            "1606938044258990275541962092341162602522202993782792835301376"
+        );
+        assert_eq!(BigBinary::from_decimal("12a"), None);
+        assert_eq!(BigBinary::from_decimal(&format!("1{}", "0".repeat(1300))), None);
+
+        let power = big(10).pow_with_flags(&big(1000)).0;
+        assert_eq!(power.to_string(), format!("1{}", "0".repeat(1000)));
+        assert_eq!((-&power).to_string(), format!("-1{}", "0".repeat(1000)));
+        assert_eq!(big(i64::MIN as i128 * 1000 - 7).to_string(), (i64::MIN as i128 * 1000 - 7).to_string());
+        assert_eq!(big(-123).times_ten(), big(-1230));
+        assert_eq!(big(i64::MAX as i128).times_ten(), big(i64::MAX as i128 * 10));
+        assert_eq!(BigBinary::from_radix("ff", 16), Some(big(255)));
+        assert_eq!(BigBinary::from_radix("0017", 8), Some(big(15)));
+        assert_eq!(BigBinary::from_radix("10100101", 2), Some(big(0b1010_0101)));
+        assert_eq!(BigBinary::from_radix("12", 2), None);
+        assert_eq!(big(0).to_string(), "0");
+        assert_eq!(big(-1203).to_string(), "-1203");
+        assert_eq!(big(i128::MIN).to_string(), i128::MIN.to_string());
+    }
+
+    #[test]
+    fn test_bitwise() {
+        assert_eq!(&big(0b1100) & &big(-0b1010), big(0b1100 & -0b1010));
+        assert_eq!(&big(0b1100) | &big(-0b1010), big(0b1100 | -0b1010));
+        assert_eq!(&big(i128::MAX) ^ &big(-1), big(i128::MIN));
+        assert_eq!(!&big(0), big(-1));
+    }
+
+    #[test]
+    fn test_compare() {
+        let compare = |x: i128, y: i128| big(x).compare(&big(y));
+
+        assert_eq!(compare(3, 3), (Off, On, Off));
+        assert_eq!(compare(-1, 0), (On, Off, Off));
+        assert_eq!(compare(i128::MAX, -1), (Off, Off, On));
+        assert_eq!(compare(-(1 << 100), -5), (On, Off, Off));
+        assert_eq!(compare(1 << 100, 1 << 99), (Off, Off, On));
+    }
+
+    #[test]
+    fn test_roots() {
+        assert_eq!(big(0).sqrt(), big(0));
+        assert_eq!(big(1).sqrt(), big(1));
+        assert_eq!(big(i128::MAX).sqrt(), big(13043817825332782212));
+        assert_eq!(big(1 << 100).sqrt(), big(1 << 50));
+        assert_eq!(big((1 << 100) - 1).sqrt(), big((1 << 50) - 1));
+
+        assert_eq!(big(i128::MAX).root(&big(3)), big(5541191377756));
+        assert_eq!(big(1 << 120).root(&big(40)), big(8));
+        assert_eq!(big(1000).root(&big(1 << 100)), big(1));
+        assert_eq!(big(0).root(&big(7)), big(0));
+    }
+
+    #[test]
+    fn test_shift() {
+        let shift = |x: i128, k: i128, shift| {
+            let (shifted, flags) = big(x).shift_with_flags(&big(k), shift);
+            (shifted, flags.overflow)
+        };
+
+        assert_eq!(shift(-3, 125, Shift::Left), (big(-3 << 125), Off));
+        assert_eq!(shift(-1000, 3, Shift::ArithmeticRight), (big(-1000 >> 3), Off));
+        assert_eq!(shift(1000, 3, Shift::LogicalRight), (big(1000 >> 3), Off));
+        assert_eq!(shift(1000, 1 << 100, Shift::ArithmeticRight), (big(0), Off));
+        assert_eq!(shift(-1000, 1 << 100, Shift::ArithmeticRight), (big(-1), Off));
+
+        assert_eq!(shift(1, BigBinary::MAX_BITS as i128, Shift::Left).1, On);
+        assert_eq!(shift(1, -1, Shift::ArithmeticRight).1, On);
+        assert_eq!(shift(-1000, 3, Shift::LogicalRight).1, On);
+        assert_eq!(shift(1000, 3, Shift::RotateLeft).1, On);
+    }
+
+    #[test]
+    fn test_resize() {
+        let resize = |n: i128| {
+            let (resized, flags) = big(n).resize_with_flags::<8>();
+            (resized.to_int::<i8>(), flags.overflow)
+        };
+
+        assert_eq!(resize(127), (Ok(127), Off), "127");
+        assert_eq!(resize(-128), (Ok(-128), Off), "-128");
+        assert_eq!(resize(128), (Ok(-128), On), "128");
+        assert_eq!(resize(1 << 100), (Ok(0), On), "2 ^ 100");
+        assert_eq!(resize(-(1 << 100) - 1), (Ok(-1), On), "-2 ^ 100 - 1");
+    }
+}
@@ -1,49 +1,114 @@
-use std::cmp::PartialEq;
+use std::cmp::{Ord, Ordering, PartialEq, PartialOrd};
 use std::fmt;
-use std::ops::{Add, Div, Mul, Neg, Shl, Shr, Sub};
+use std::ops::{Add, BitAnd, BitOr, BitXor, Div, Mul, Neg, Not, Rem, Shl, Shr, Sub};
 
-use super::{Bit, ParseResult};
+use std::num::ParseIntError;
 
-/// Binary: Sequence of Bits, ordered from most to least significant
+use super::adder::{Adder, RippleCarry};
+use super::divider::{Divider, Restoring};
+use super::multiplier::{Array, Multiplier};
+use super::number;
+use super::{BigBinary, Bit, Flags, Number};
+
+/// Native signed integers that can be loaded into or read out of a Binary
+pub trait Int: Copy + fmt::Binary {
+    const BITS: usize;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+/// Native unsigned integers that can be loaded into or read out of a Binary
+pub trait Uint: Copy + fmt::Binary {
+    const BITS: usize;
+
+    fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError>;
+}
+
+macro_rules! impl_int {
+    ( $trait:ident: $( $t:ty ),* ) => {
+        $(
+            impl $trait for $t {
+                const BITS: usize = <$t>::BITS as usize;
+
+                fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseIntError> {
+                    <$t>::from_str_radix(s, radix)
+                }
+            }
+        )*
+    };
+}
+
+impl_int!(Int: i8, i16, i32, i64, i128);
+impl_int!(Uint: u8, u16, u32, u64, u128);
+
+/// The ways a barrel shifter can move Bits, which differ in what fills the
+/// positions left vacated
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Shift {
+    /// Move toward the most significant Bit, filling with zeros
+    Left,
+    /// Move toward the least significant Bit, filling with zeros
+    LogicalRight,
+    /// Move toward the least significant Bit, filling with copies of the sign
+    ArithmeticRight,
+    /// Move toward the most significant Bit, with Bits moved off the top
+    /// coming back in at the bottom
+    RotateLeft,
+    /// Move toward the least significant Bit, with Bits moved off the bottom
+    /// coming back in at the top
+    RotateRight,
+}
+
+/// Binary: Sequence of N Bits, ordered from most to least significant, which
+/// defaults to a 64-bit register
 #[derive(Clone)]
-pub struct Binary([Bit; 64]);
+pub struct Binary<const N: usize = 64>([Bit; N]);
 
-impl Binary {
+impl<const N: usize> Binary<N> {
     /// Create a Binary filled with provided Bit
-    pub fn of(b: Bit) -> Binary {
-        Binary([b; 64])
+    pub fn of(b: Bit) -> Binary<N> {
+        Binary([b; N])
     }
 
     /// Create a Binary representing 0
-    pub fn zero() -> Binary {
+    pub fn zero() -> Binary<N> {
         Binary::of(Bit::Off)
     }
 
     /// Create a Binary representing 1
-    pub fn one() -> Binary {
+    pub fn one() -> Binary<N> {
         let mut binary = Binary::zero();
-        binary.set(63, Bit::On);
+        binary.set(N - 1, Bit::On);
         binary
     }
 
-    /// Create a Binary from an int
-    pub fn from_int(n: i64) -> Binary {
-        // Need 66 chars to represent 64bit, since it adds "0b" to beginning
-        let bit_string = format!("{:#066b}", n);
-        let mut binary = Binary::zero();
+    /// Create the largest Binary if the sign is off, or the smallest if on
+    pub fn saturated(sign: Bit) -> Binary<N> {
+        let mut binary = Binary::of(!sign);
+        binary.set(0, sign);
+        binary
+    }
 
-        bit_string
+    /// Create a Binary from an int, sign-extending or truncating it to fit
+    pub fn from_int<T: Int>(n: T) -> Binary<N> {
+        // Zero-padding to the int's own width gives its two's complement,
+        // without the "0b" that the alternate flag would add
+        let bit_string: Vec<_> = format!("{:0width$b}", n, width = T::BITS)
             .chars()
-            .skip(2)
             .map(|c| if c == '1' { Bit::On } else { Bit::Off })
-            .enumerate()
-            .for_each(|(i, bit)| binary.set(i, bit));
+            .collect();
 
-        binary
+        Binary(::std::array::from_fn(|i| {
+            // Positions above the int's most significant Bit copy its sign
+            match (i + T::BITS).checked_sub(N) {
+                Some(j) => bit_string[j],
+                None => bit_string[0],
+            }
+        }))
     }
 
-    /// Attempt to convert Binary to an int
-    pub fn to_int(self) -> ParseResult {
+    /// Attempt to convert Binary to an int, failing if it doesn't fit
+    pub fn to_int<T: Int>(self) -> Result<T, ParseIntError> {
         // Rust is a little inconsistent with how it handles negative binary numbers...
         //
         //   - Literals use negative, eg. -0b0000_0011 for -3)
@@ -55,7 +120,7 @@ impl Binary {
         // from_str_radix. If negative, need to convert from two's complement to negative literal
 
         // Capacity should fit full number and potentially "-" sign
-        let mut s = String::with_capacity(65);
+        let mut s = String::with_capacity(N + 1);
         let mut binary = self;
 
         if binary.is_negative() {
@@ -64,11 +129,73 @@ impl Binary {
             binary = -&binary;
         }
 
-        for i in 0..64 {
+        for i in 0..N {
             s.push(if binary.is_on_at(i) { '1' } else { '0' });
         }
 
-        i64::from_str_radix(s.as_str(), 2)
+        T::from_str_radix(s.as_str(), 2)
+    }
+
+    /// Create a Binary from an unsigned int, zero-extending or truncating it
+    /// to fit
+    pub fn from_uint<T: Uint>(n: T) -> Binary<N> {
+        let bit_string: Vec<_> = format!("{:0width$b}", n, width = T::BITS)
+            .chars()
+            .map(|c| if c == '1' { Bit::On } else { Bit::Off })
+            .collect();
+
+        Binary(::std::array::from_fn(|i| {
+            match (i + T::BITS).checked_sub(N) {
+                Some(j) => bit_string[j],
+                None => Bit::Off,
+            }
+        }))
+    }
+
+    /// Attempt to convert Binary to an unsigned int, reading the top Bit as
+    /// part of the value rather than a sign
+    pub fn to_uint<T: Uint>(self) -> Result<T, ParseIntError> {
+        let s: String = self.0
+            .iter()
+            .map(|&b| if b == Bit::On { '1' } else { '0' })
+            .collect();
+
+        T::from_str_radix(s.as_str(), 2)
+    }
+
+    /// Sign-extends or truncates to a register of another width, raising
+    /// overflow if the value doesn't fit
+    pub fn resize_with_flags<const M: usize>(&self) -> (Binary<M>, Flags) {
+        let mut resized = Binary::<M>::zero();
+        let mut overflow = Bit::Off;
+
+        for i in 0..M {
+            // Positions above the most significant Bit copy the sign
+            let bit = match (i + N).checked_sub(M) {
+                Some(j) => self.get(j),
+                None => self.get(0),
+            };
+
+            resized.set(i, bit);
+        }
+
+        // Any Bits truncated off the top must have matched the new sign
+        for i in 0..N.saturating_sub(M) {
+            overflow = overflow | (self.get(i) ^ resized.get(0));
+        }
+
+        let flags = Flags {
+            overflow,
+            ..Flags::from_result(&resized)
+        };
+
+        (resized, flags)
+    }
+
+    /// Sign-extends or truncates to a register of another width, see
+    /// `resize_with_flags`
+    pub fn resize<const M: usize>(&self) -> Binary<M> {
+        self.resize_with_flags().0
     }
 
     /// Returns Bit at given position
@@ -83,24 +210,611 @@ impl Binary {
 
     /// Returns whether or not Bit at given position is on
     pub fn is_on_at(&self, i: usize) -> bool {
-        match self.0[i] {
-            Bit::On => true,
-            _ => false,
-        }
+        self.0[i] == Bit::On
     }
 
     /// Returns whether or not Binary represents negative number
     pub fn is_negative(&self) -> bool {
         self.is_on_at(0)
     }
+
+    /// Returns On if any Bit is on, ie. the Binary is not 0
+    pub fn any(&self) -> Bit {
+        self.0.iter().fold(Bit::Off, |acc, &b| acc | b)
+    }
+
+    /// Returns the absolute value, which for the minimum value is only
+    /// correct when read as unsigned
+    pub fn magnitude(&self) -> Binary<N> {
+        if self.is_negative() {
+            -self
+        } else {
+            self.clone()
+        }
+    }
+
+    /// Magnitude comparator circuit, returning whether self is less than,
+    /// equal to or greater than other as `(less, equal, greater)`
+    ///
+    /// Scanning from the most significant Bit, the first position where the
+    /// two differ latches the result. A signed comparison inverts both sign
+    /// Bits first, since an on sign makes a value smaller rather than larger.
+    pub fn comparator(&self, other: &Binary<N>, signed: Bit) -> (Bit, Bit, Bit) {
+        let mut less = Bit::Off;
+        let mut equal = Bit::On;
+        let mut greater = Bit::Off;
+
+        for i in 0..N {
+            let (mut a, mut b) = (self.get(i), other.get(i));
+
+            if i == 0 {
+                a = a ^ signed;
+                b = b ^ signed;
+            }
+
+            less = less | (equal & !a & b);
+            greater = greater | (equal & a & !b);
+            equal = equal & !(a ^ b);
+        }
+
+        (less, equal, greater)
+    }
+
+    /// Compares as two's complement values, see `comparator`
+    pub fn compare(&self, other: &Binary<N>) -> (Bit, Bit, Bit) {
+        self.comparator(other, Bit::On)
+    }
+
+    /// Compares as unsigned values, see `comparator`
+    pub fn compare_unsigned(&self, other: &Binary<N>) -> (Bit, Bit, Bit) {
+        self.comparator(other, Bit::Off)
+    }
+
+    /// Orders as unsigned values, see `comparator`
+    pub fn cmp_unsigned(&self, other: &Binary<N>) -> Ordering {
+        ordering(self.compare_unsigned(other))
+    }
+
+    /// Flips every Bit, see `Not`
+    fn invert(&self) -> Binary<N> {
+        let mut inverted = Binary::zero();
+
+        for i in 0..N {
+            inverted.set(i, !self.get(i));
+        }
+
+        inverted
+    }
+
+    /// Full addition circuit, leaving the carries to the adder and reading
+    /// the flags off what it returns
+    fn add_with_carry(&self, other: &Binary<N>, carry_in: Bit, adder: &dyn Adder) -> (Binary<N>, Flags) {
+        let (bits, carry, carry_into_sign) = adder.add(&self.0, &other.0, carry_in);
+        let sum = Binary(std::array::from_fn(|i| bits[i]));
+
+        // Signed overflow happened if the sign Bit received a different
+        // carry than it sent out
+        let flags = Flags {
+            carry,
+            overflow: carry_into_sign ^ carry,
+            ..Flags::from_result(&sum)
+        };
+
+        (sum, flags)
+    }
+
+    /// Adds other with a ripple-carry adder, raising carry and signed
+    /// overflow
+    pub fn add_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        self.add_using(other, &RippleCarry)
+    }
+
+    /// Adds other with the given adder, raising carry and signed overflow
+    pub fn add_using(&self, other: &Binary<N>, adder: &dyn Adder) -> (Binary<N>, Flags) {
+        self.add_with_carry(other, Bit::Off, adder)
+    }
+
+    /// Adds other as unsigned values, where overflow is the carry out of
+    /// the most significant Bit
+    pub fn add_unsigned_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        self.add_unsigned_using(other, &RippleCarry)
+    }
+
+    /// Adds other as unsigned values with the given adder
+    pub fn add_unsigned_using(&self, other: &Binary<N>, adder: &dyn Adder) -> (Binary<N>, Flags) {
+        let (sum, flags) = self.add_with_carry(other, Bit::Off, adder);
+
+        (sum, Flags { overflow: flags.carry, ..flags })
+    }
+
+    /// Subtracts other by adding its complement with an initial carry
+    ///
+    /// The carry flag is the adder's raw carry out, so it is On when no
+    /// borrow was needed.
+    pub fn sub_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        self.sub_using(other, &RippleCarry)
+    }
+
+    /// Subtracts other with the given adder, see `sub_with_flags`
+    pub fn sub_using(&self, other: &Binary<N>, adder: &dyn Adder) -> (Binary<N>, Flags) {
+        self.add_with_carry(&other.invert(), Bit::On, adder)
+    }
+
+    /// Subtracts other as unsigned values, where overflow is the borrow
+    /// needed when other is the larger of the two
+    pub fn sub_unsigned_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        self.sub_unsigned_using(other, &RippleCarry)
+    }
+
+    /// Subtracts other as unsigned values with the given adder
+    pub fn sub_unsigned_using(&self, other: &Binary<N>, adder: &dyn Adder) -> (Binary<N>, Flags) {
+        let (difference, flags) = self.sub_using(other, adder);
+
+        (difference, Flags { overflow: !flags.carry, ..flags })
+    }
+
+    /// Inverts the sign, raising overflow for the minimum value
+    pub fn neg_with_flags(&self) -> (Binary<N>, Flags) {
+        Binary::zero().sub_with_flags(self)
+    }
+
+    /// Negates as an unsigned value, which overflows for anything but 0
+    pub fn neg_unsigned_with_flags(&self) -> (Binary<N>, Flags) {
+        Binary::zero().sub_unsigned_with_flags(self)
+    }
+
+    /// Multiplier that uses basic series of shifts and adding partial
+    /// products of the magnitudes, raising overflow if any Bit of the full
+    /// product is lost
+    pub fn mul_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        let (product, flags, _) = self.mul_using(other, &Array);
+
+        (product, flags)
+    }
+
+    /// Multiplies the magnitudes with the given multiplier, also returning
+    /// how many partial products it added, see `mul_with_flags`
+    pub fn mul_using(&self, other: &Binary<N>, multiplier: &dyn Multiplier) -> (Binary<N>, Flags, usize) {
+        let negate = self.get(0) ^ other.get(0);
+        let (magnitude, flags, additions) = self.magnitude().mul_unsigned_using(&other.magnitude(), multiplier);
+
+        let product = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude.clone()
+        };
+
+        // The magnitude only fits if its top Bit is off, save for the minimum
+        // value which keeps its sign when negated
+        let overflow = flags.overflow | (magnitude.get(0) & !(negate & product.get(0)));
+
+        let flags = Flags {
+            carry: overflow,
+            overflow,
+            ..Flags::from_result(&product)
+        };
+
+        (product, flags, additions)
+    }
+
+    /// Multiplies as unsigned values with a series of shifts and adds of
+    /// partial products, raising overflow if any Bit of the full product is
+    /// lost
+    pub fn mul_unsigned_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Flags) {
+        let (product, flags, _) = self.mul_unsigned_using(other, &Array);
+
+        (product, flags)
+    }
+
+    /// Multiplies as unsigned values with the given multiplier, also
+    /// returning how many partial products it added
+    pub fn mul_unsigned_using(&self, other: &Binary<N>, multiplier: &dyn Multiplier) -> (Binary<N>, Flags, usize) {
+        let (bits, additions) = multiplier.multiply(&self.0, &other.0);
+
+        // The product is twice as wide as the register, so any Bit on in
+        // the upper half is lost
+        let lost = bits[..N].iter().fold(Bit::Off, |lost, &bit| lost | bit);
+        let product = Binary(std::array::from_fn(|i| bits[N + i]));
+
+        let flags = Flags {
+            carry: lost,
+            overflow: lost,
+            ..Flags::from_result(&product)
+        };
+
+        (product, flags, additions)
+    }
+
+    /// Multiplies as fixed-point values with the given number of fraction
+    /// Bits, keeping the Bits of the full product of the magnitudes that sit
+    /// just above as many fraction Bits again, which truncates toward zero
+    pub fn mul_fixed_using(&self, other: &Binary<N>, multiplier: &dyn Multiplier, fraction: usize) -> (Binary<N>, Flags, usize) {
+        let negate = self.get(0) ^ other.get(0);
+        let (bits, additions) = multiplier.multiply(&self.magnitude().0, &other.magnitude().0);
+        let (magnitude, lost) = Binary::window(&bits, fraction);
+
+        let product = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude.clone()
+        };
+
+        // As with integers, only the minimum value keeps its top Bit
+        let overflow = lost | (magnitude.get(0) & !(negate & product.get(0)));
+
+        let flags = Flags {
+            carry: overflow,
+            overflow,
+            ..Flags::from_result(&product)
+        };
+
+        (product, flags, additions)
+    }
+
+    /// Multiplies as unsigned fixed-point values, see `mul_fixed_using`
+    pub fn mul_unsigned_fixed_using(&self, other: &Binary<N>, multiplier: &dyn Multiplier, fraction: usize) -> (Binary<N>, Flags, usize) {
+        let (bits, additions) = multiplier.multiply(&self.0, &other.0);
+        let (product, lost) = Binary::window(&bits, fraction);
+
+        let flags = Flags {
+            carry: lost,
+            overflow: lost,
+            ..Flags::from_result(&product)
+        };
+
+        (product, flags, additions)
+    }
+
+    /// Divides as fixed-point values with the given number of fraction Bits,
+    /// shifting the dividend's magnitude up by as many in a register twice
+    /// as wide first, so the quotient keeps them
+    pub fn div_fixed_using(&self, other: &Binary<N>, divider: &dyn Divider, fraction: usize) -> (Binary<N>, Flags) {
+        let negate = self.get(0) ^ other.get(0);
+        let (magnitude, flags) = self
+            .magnitude()
+            .div_unsigned_fixed_using(&other.magnitude(), divider, fraction);
+
+        let quotient = if negate == Bit::On {
+            -&magnitude
+        } else {
+            magnitude.clone()
+        };
+
+        let overflow = flags.overflow | (magnitude.get(0) & !(negate & quotient.get(0)));
+
+        let flags = Flags {
+            overflow,
+            divide_by_zero: flags.divide_by_zero,
+            ..Flags::from_result(&quotient)
+        };
+
+        (quotient, flags)
+    }
+
+    /// Divides as unsigned fixed-point values, see `div_fixed_using`
+    pub fn div_unsigned_fixed_using(&self, other: &Binary<N>, divider: &dyn Divider, fraction: usize) -> (Binary<N>, Flags) {
+        let mut dividend = vec![Bit::Off; 2 * N];
+        dividend[N - fraction..2 * N - fraction].copy_from_slice(&self.0);
+
+        let mut divisor = vec![Bit::Off; N];
+        divisor.extend_from_slice(&other.0);
+
+        let (bits, _) = divider.divide(&dividend, &divisor);
+        let (quotient, lost) = Binary::window(&bits, 0);
+
+        let flags = Flags {
+            overflow: lost,
+            divide_by_zero: !other.any(),
+            ..Flags::from_result(&quotient)
+        };
+
+        (quotient, flags)
+    }
+
+    /// Keeps the N Bits of a double-width result that sit just above the
+    /// given number of Bits, along with whether any Bit above them was on
+    fn window(bits: &[Bit], below: usize) -> (Binary<N>, Bit) {
+        let top = N - below;
+        let lost = bits[..top].iter().fold(Bit::Off, |lost, &bit| lost | bit);
+
+        (Binary(std::array::from_fn(|i| bits[top + i])), lost)
+    }
+
+    /// Emulates long division in a comically long fashion, returning both
+    /// the quotient and the remainder left over from the same pass
+    ///
+    /// As with Rust's `/` and `%`, the quotient truncates toward zero and the
+    /// remainder takes the sign of the dividend. The flags describe the
+    /// quotient, with overflow raised when it cannot be represented (the
+    /// minimum value divided by -1).
+    pub fn div_rem_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Binary<N>, Flags) {
+        self.div_rem_using(other, &Restoring)
+    }
+
+    /// Divides with the given divider, see `div_rem_with_flags`
+    pub fn div_rem_using(&self, other: &Binary<N>, divider: &dyn Divider) -> (Binary<N>, Binary<N>, Flags) {
+        // Take "absolute value" of the binaries for simpler math,
+        // storing whether final quotient should be negative
+        let negate = self.get(0) ^ other.get(0);
+        let (quotient, remainder, flags) = self
+            .magnitude()
+            .div_rem_unsigned_using(&other.magnitude(), divider);
+
+        // A positive quotient with its top Bit on is too large to represent
+        let overflow = !negate & quotient.get(0);
+
+        let quotient = if negate == Bit::On {
+            -&quotient
+        } else {
+            quotient
+        };
+
+        let remainder = if self.is_negative() {
+            -&remainder
+        } else {
+            remainder
+        };
+
+        let flags = Flags {
+            overflow,
+            divide_by_zero: flags.divide_by_zero,
+            ..Flags::from_result(&quotient)
+        };
+
+        (quotient, remainder, flags)
+    }
+
+    /// Restoring long division of unsigned values, which can never overflow
+    pub fn div_rem_unsigned_with_flags(&self, other: &Binary<N>) -> (Binary<N>, Binary<N>, Flags) {
+        self.div_rem_unsigned_using(other, &Restoring)
+    }
+
+    /// Divides as unsigned values with the given divider
+    pub fn div_rem_unsigned_using(&self, other: &Binary<N>, divider: &dyn Divider) -> (Binary<N>, Binary<N>, Flags) {
+        let (quotient, remainder) = divider.divide(&self.0, &other.0);
+        let quotient = Binary(std::array::from_fn(|i| quotient[i]));
+        let remainder = Binary(std::array::from_fn(|i| remainder[i]));
+
+        let flags = Flags {
+            divide_by_zero: !other.any(),
+            ..Flags::from_result(&quotient)
+        };
+
+        (quotient, remainder, flags)
+    }
+
+    /// Raises to the power of the exponent with a square-and-multiply
+    /// circuit, raising overflow if any multiplication along the way did
+    ///
+    /// Following integer division, a negative exponent yields the truncated
+    /// reciprocal `1 / (self ^ -exp)`, which raises divide-by-zero for a base
+    /// of 0, and `0 ^ 0` is defined as 1.
+    pub fn pow_with_flags(&self, exp: &Binary<N>) -> (Binary<N>, Flags) {
+        let (power, flags, _) = self.pow_using(exp, &Array, &Restoring);
+
+        (power, flags)
+    }
+
+    /// Raises to the power with the given multiplier and divider, also
+    /// returning how many partial products the multiplier added, see
+    /// `pow_with_flags`
+    pub fn pow_using(&self, exp: &Binary<N>, multiplier: &dyn Multiplier, divider: &dyn Divider) -> (Binary<N>, Flags, usize) {
+        // Negating the minimum value leaves it unchanged, but its bit pattern
+        // is still the correct magnitude when read as unsigned
+        let (result, overflow, additions) = self.square_and_multiply(&exp.magnitude(), |a, b| a.mul_using(b, multiplier));
+
+        if exp.is_negative() {
+            // A power too large to represent has a reciprocal that truncates
+            // to 0, so only divide if the power didn't overflow
+            if overflow == Bit::On {
+                let zero = Binary::zero();
+                let flags = Flags::from_result(&zero);
+
+                return (zero, flags, additions);
+            }
+
+            let (quotient, _, flags) = Binary::one().div_rem_using(&result, divider);
+
+            (quotient, flags, additions)
+        } else {
+            let flags = Flags {
+                carry: overflow,
+                overflow,
+                ..Flags::from_result(&result)
+            };
+
+            (result, flags, additions)
+        }
+    }
+
+    /// Raises to the power of the exponent as unsigned values, raising
+    /// overflow if any multiplication along the way did
+    pub fn pow_unsigned_with_flags(&self, exp: &Binary<N>) -> (Binary<N>, Flags) {
+        let (power, flags, _) = self.pow_unsigned_using(exp, &Array);
+
+        (power, flags)
+    }
+
+    /// Raises to the power as unsigned values with the given multiplier,
+    /// also returning how many partial products it added
+    pub fn pow_unsigned_using(&self, exp: &Binary<N>, multiplier: &dyn Multiplier) -> (Binary<N>, Flags, usize) {
+        let (result, overflow, additions) = self.square_and_multiply(exp, |a, b| a.mul_unsigned_using(b, multiplier));
+
+        let flags = Flags {
+            carry: overflow,
+            overflow,
+            ..Flags::from_result(&result)
+        };
+
+        (result, flags, additions)
+    }
+
+    /// Scans the exponent from its most significant Bit, squaring the
+    /// running result for every position and multiplying in the base
+    /// whenever the Bit is on, returning whether any multiplication
+    /// overflowed and how many partial products they added
+    fn square_and_multiply<M>(&self, exp: &Binary<N>, multiply: M) -> (Binary<N>, Bit, usize)
+    where
+        M: Fn(&Binary<N>, &Binary<N>) -> (Binary<N>, Flags, usize),
+    {
+        let mut result = Binary::one();
+        let mut overflow = Bit::Off;
+        let mut additions = 0;
+
+        // Squaring 1 for every leading off Bit would change nothing
+        let start = (0..N).find(|&i| exp.is_on_at(i)).unwrap_or(N);
+
+        for i in start..N {
+            let (squared, flags, added) = multiply(&result, &result);
+
+            result = squared;
+            overflow = overflow | flags.overflow;
+            additions += added;
+
+            if exp.is_on_at(i) {
+                let (product, flags, added) = multiply(&result, self);
+
+                result = product;
+                overflow = overflow | flags.overflow;
+                additions += added;
+            }
+        }
+
+        (result, overflow, additions)
+    }
+
+    /// Digit-by-digit square root of the value read as unsigned, which like
+    /// long division brings down two Bits at a time and subtracts the root
+    /// found so far, with "01" appended, whenever it fits
+    pub fn sqrt_unsigned(&self) -> Binary<N> {
+        let mut root = Binary::zero();
+        let mut remainder = Binary::zero();
+
+        // An odd width starts with a pair holding only the top Bit
+        for pair in 0..N.div_ceil(2) {
+            let low = 2 * pair + 1 - N % 2;
+
+            remainder = &remainder << 2;
+            remainder.set(N - 2, if low > 0 { self.get(low - 1) } else { Bit::Off });
+            remainder.set(N - 1, self.get(low));
+
+            let mut trial = &root << 2;
+            trial.set(N - 1, Bit::On);
+
+            // Keep the difference if the trial fits, exactly as the restoring
+            // divider does, and append whether it did to the root
+            let (less, _, _) = remainder.compare_unsigned(&trial);
+            let difference = &remainder - &trial;
+
+            for j in 0..N {
+                remainder.set(j, Bit::multiplexer(less, difference.get(j), remainder.get(j)));
+            }
+
+            root = &root << 1;
+            root.set(N - 1, !less);
+        }
+
+        root
+    }
+
+    /// Integer root of the given degree of the value read as unsigned,
+    /// settling one Bit at a time from the top by keeping it on only if the
+    /// multiplier raises the candidate to a power the comparator finds no
+    /// greater than the value
+    pub fn root_unsigned(&self, degree: &Binary<N>) -> Binary<N> {
+        let mut root = Binary::zero();
+
+        for i in 0..N {
+            let mut candidate = root.clone();
+            candidate.set(i, Bit::On);
+
+            let (power, flags) = candidate.pow_unsigned_with_flags(degree);
+            let (_, _, greater) = power.compare_unsigned(self);
+
+            root.set(i, !(flags.overflow | greater));
+        }
+
+        root
+    }
+
+    /// Barrel shifter that moves by the amount in one pass through a stage
+    /// of multiplexers per Bit of the amount, where each stage moves by the
+    /// next power of two when its Bit is on
+    ///
+    /// Shifting by an amount with any Bit on above those the stages use
+    /// (for widths that are a power of two, anything negative or not less
+    /// than the width) moves every Bit out and raises overflow. Rotations
+    /// only depend on the amount modulo the width, so they never overflow.
+    pub fn shift_with_flags(&self, amount: &Binary<N>, shift: Shift) -> (Binary<N>, Flags) {
+        let fill = match shift {
+            Shift::ArithmeticRight => self.get(0),
+            _ => Bit::Off,
+        };
+
+        let mut shifted = self.clone();
+        let mut stages = 0;
+
+        while 1 << stages < N {
+            let distance = 1 << stages;
+            let select = amount.get(N - 1 - stages);
+            let mut next = Binary::zero();
+
+            for i in 0..N {
+                let moved = match shift {
+                    Shift::Left if i + distance < N => shifted.get(i + distance),
+                    Shift::Left => fill,
+                    Shift::LogicalRight | Shift::ArithmeticRight if i >= distance => {
+                        shifted.get(i - distance)
+                    }
+                    Shift::LogicalRight | Shift::ArithmeticRight => fill,
+                    Shift::RotateLeft => shifted.get((i + distance) % N),
+                    Shift::RotateRight => shifted.get((i + N - distance) % N),
+                };
+
+                next.set(i, Bit::multiplexer(select, shifted.get(i), moved));
+            }
+
+            shifted = next;
+            stages += 1;
+        }
+
+        let overflow = match shift {
+            Shift::RotateLeft | Shift::RotateRight => Bit::Off,
+            _ => (0..N - stages).fold(Bit::Off, |acc, i| acc | amount.get(i)),
+        };
+
+        for i in 0..N {
+            shifted.set(i, Bit::multiplexer(overflow, shifted.get(i), fill));
+        }
+
+        let flags = Flags {
+            overflow,
+            ..Flags::from_result(&shifted)
+        };
+
+        (shifted, flags)
+    }
+
+    /// Raises to the power of the exponent, see `pow_with_flags`
+    pub fn pow(&self, exp: &Binary<N>) -> Binary<N> {
+        self.pow_with_flags(exp).0
+    }
+
+    /// Divides by other, returning both the quotient and the remainder,
+    /// see `div_rem_with_flags`
+    pub fn div_rem(&self, other: &Binary<N>) -> (Binary<N>, Binary<N>) {
+        let (quotient, remainder, _) = self.div_rem_with_flags(other);
+
+        (quotient, remainder)
+    }
 }
 
 // FIXME this and to_int should share some codez?
-impl fmt::Debug for Binary {
+impl<const N: usize> fmt::Debug for Binary<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "0b")?;
 
-        for i in 0..64 {
+        for i in 0..N {
             if i % 4 == 0 {
                 write!(f, "_")?;
             }
@@ -110,191 +824,319 @@ impl fmt::Debug for Binary {
     }
 }
 
-impl PartialEq for Binary {
-    fn eq(&self, other: &Binary) -> bool {
-        for i in 0..64 {
+impl<const N: usize> fmt::Display for Binary<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.clone().to_int::<i128>() {
+            Ok(n) => write!(f, "{}", n),
+            Err(_) => write!(f, "{:?}", self),
+        }
+    }
+}
+
+impl<const N: usize> PartialEq for Binary<N> {
+    fn eq(&self, other: &Binary<N>) -> bool {
+        for i in 0..N {
             if self.get(i) != other.get(i) {
                 return false;
             }
         }
 
-        return true;
+        true
+    }
+}
+
+impl<const N: usize> Eq for Binary<N> {}
+
+impl<const N: usize> PartialOrd for Binary<N> {
+    fn partial_cmp(&self, other: &Binary<N>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Orders as two's complement values, see `cmp_unsigned` otherwise
+impl<const N: usize> Ord for Binary<N> {
+    fn cmp(&self, other: &Binary<N>) -> Ordering {
+        ordering(self.compare(other))
+    }
+}
+
+/// Reads the outputs of a comparator as an Ordering
+fn ordering((less, equal, _): (Bit, Bit, Bit)) -> Ordering {
+    match (less, equal) {
+        (Bit::On, _) => Ordering::Less,
+        (_, Bit::On) => Ordering::Equal,
+        _ => Ordering::Greater,
     }
 }
 
-impl Shl<usize> for &Binary {
-    type Output = Binary;
+impl<const N: usize> Shl<usize> for &Binary<N> {
+    type Output = Binary<N>;
 
-    fn shl(self, rhs: usize) -> Binary {
+    fn shl(self, rhs: usize) -> Binary<N> {
         let mut shifted = Binary::zero();
 
-        for i in 0..(64 - rhs) {
-            shifted.set(i, self.get(i + 1));
+        for (i, j) in (0..N).zip(rhs..N) {
+            shifted.set(i, self.get(j));
         }
 
         shifted
     }
 }
 
-impl Shr<usize> for &Binary {
-    type Output = Binary;
+impl<const N: usize> Shr<usize> for &Binary<N> {
+    type Output = Binary<N>;
 
-    fn shr(self, rhs: usize) -> Binary {
+    fn shr(self, rhs: usize) -> Binary<N> {
         let mut shifted = Binary::zero();
 
-        for i in rhs..64 {
-            shifted.set(i, self.get(i - 1));
+        for (i, j) in (rhs..N).zip(0..N) {
+            shifted.set(i, self.get(j));
         }
 
         shifted
     }
 }
 
-impl Neg for &Binary {
-    type Output = Binary;
+impl<const N: usize> BitAnd<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-    /// Inverts the sign of a Binary by flipping bits and adding 1
-    fn neg(self) -> Self::Output {
-        let mut negated = Binary::zero();
+    fn bitand(self, other: &Binary<N>) -> Binary<N> {
+        let mut result = Binary::zero();
 
-        for i in 0..64 {
-            negated.set(i, !self.get(i));
+        for i in 0..N {
+            result.set(i, self.get(i) & other.get(i));
         }
 
-        &negated + &Binary::one()
+        result
     }
 }
 
-impl<'a, 'b> Add<&'b Binary> for &'a Binary {
-    type Output = Binary;
+impl<const N: usize> BitOr<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-    /// Basic implementation of full addition circuit
-    fn add(self, other: &'b Binary) -> Binary {
-        let mut rval = Binary::zero();
-        let mut carry = Bit::Off;
+    fn bitor(self, other: &Binary<N>) -> Binary<N> {
+        let mut result = Binary::zero();
 
-        for i in (0..64).rev() {
-            let result = match i {
-                63 => Bit::half_adder(self.get(i), other.get(i)),
-                _ => Bit::full_adder(self.get(i), other.get(i), carry),
-            };
+        for i in 0..N {
+            result.set(i, self.get(i) | other.get(i));
+        }
+
+        result
+    }
+}
 
-            rval.set(i, result.0);
-            carry = result.1;
+impl<const N: usize> BitXor<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
+
+    fn bitxor(self, other: &Binary<N>) -> Binary<N> {
+        let mut result = Binary::zero();
+
+        for i in 0..N {
+            result.set(i, self.get(i) ^ other.get(i));
         }
 
-        rval
+        result
     }
 }
 
-impl<'a, 'b> Sub<&'b Binary> for &'a Binary {
-    type Output = Binary;
+impl<const N: usize> Not for &Binary<N> {
+    type Output = Binary<N>;
 
-    /// Simple subtractor that takes complement of other and adds to self
-    fn sub(self, other: &'b Binary) -> Binary {
-        self + &(-other)
+    fn not(self) -> Binary<N> {
+        self.invert()
     }
 }
 
-impl<'a, 'b> Mul<&'b Binary> for &'a Binary {
-    type Output = Binary;
+impl<const N: usize> Shl<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-    /// Multiplier that uses basic series of shifts and adding partial products
-    fn mul(self, other: &'b Binary) -> Binary {
-        let mut accumulator = Binary::zero();
+    fn shl(self, amount: &Binary<N>) -> Binary<N> {
+        self.shift_with_flags(amount, Shift::Left).0
+    }
+}
 
-        // For each Bit in the multiplier, starting at least significant...
-        for i in (0..64).rev() {
-            let multiplier = other.get(i);
+impl<const N: usize> Shr<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-            // ... create a zeroed Binary to hold the partial product...
-            let mut partial = Binary::zero();
+    /// Shifts right arithmetically, as with Rust's signed integers
+    fn shr(self, amount: &Binary<N>) -> Binary<N> {
+        self.shift_with_flags(amount, Shift::ArithmeticRight).0
+    }
+}
 
-            // ... and then iterate through each Bit in multiplicand,
-            // starting with least significant (truncating off more
-            // significant Bits as necessary due to storage size)...
-            for j in ((63 - i)..64).rev() {
-                let product = Bit::multiplier(self.get(j), multiplier);
+impl<const N: usize> Neg for &Binary<N> {
+    type Output = Binary<N>;
 
-                // ... and with each multiplier, the index for where the least
-                // significant Bit gets copied into the partial shifts more and
-                // more to the left...
-                partial.set(j - (63 - i), product);
-            }
+    /// Inverts the sign of a Binary by flipping bits and adding 1
+    fn neg(self) -> Self::Output {
+        &self.invert() + &Binary::one()
+    }
+}
 
-            accumulator = &accumulator + &partial;
-        }
+impl<const N: usize> Add<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-        accumulator
+    fn add(self, other: &Binary<N>) -> Binary<N> {
+        self.add_with_flags(other).0
     }
 }
 
-impl<'a, 'b> Div<&'b Binary> for &'a Binary {
-    type Output = Binary;
+impl<const N: usize> Sub<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-    /// Emulates long division in a comically long fashion
-    fn div(self, other: &'b Binary) -> Binary {
-        // Take "absolute value" of the binaries for simpler math,
-        // storing whether final quotient should be negative
-        let negate = self.is_negative() != other.is_negative();
+    fn sub(self, other: &Binary<N>) -> Binary<N> {
+        self.sub_with_flags(other).0
+    }
+}
 
-        let dividend = if self.is_negative() {
-            -self
-        } else {
-            self.clone()
-        };
+impl<const N: usize> Mul<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-        let divisor = if other.is_negative() {
-            -other
-        } else {
-            other.clone()
-        };
+    fn mul(self, other: &Binary<N>) -> Binary<N> {
+        self.mul_with_flags(other).0
+    }
+}
 
-        let mut quotient = Binary::zero();
+impl<const N: usize> Div<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-        // The partial dividend starts at "zero", with each successive round
-        // shifting the next bit from the original number
-        let mut partial_dividend = Binary::zero();
+    fn div(self, other: &Binary<N>) -> Binary<N> {
+        self.div_rem(other).0
+    }
+}
 
-        for i in 0..64 {
-            // Shift partial left and assign next bit to least significant
-            partial_dividend = &partial_dividend << 1;
-            partial_dividend.set(63, dividend.get(i));
+impl<const N: usize> Rem<&Binary<N>> for &Binary<N> {
+    type Output = Binary<N>;
 
-            // Now go bit by bit and check if either is greater and, if so,
-            // breaking the loop and determining what value to assign to quot
-            let mut result = Bit::On;
+    fn rem(self, other: &Binary<N>) -> Binary<N> {
+        self.div_rem(other).1
+    }
+}
 
-            for j in 0..64 {
-                if partial_dividend.get(j) != divisor.get(j) {
-                    if divisor.get(j) == Bit::On {
-                        result = Bit::Off;
-                    }
+impl<const N: usize> Number for Binary<N> {
+    const SIGNED: bool = true;
 
-                    break;
-                }
-            }
+    type Wide = BigBinary;
 
-            quotient.set(i, result);
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        n.resize_with_flags()
+    }
 
-            // If the result was a "1" then we need to create a new dividend by
-            // subtracting the divisor from the prior dividend
-            if result == Bit::On {
-                partial_dividend = &partial_dividend - &divisor;
-            }
-        }
+    fn sign(&self) -> Bit {
+        self.get(0)
+    }
 
-        if negate {
-            -&quotient
-        } else {
-            quotient
-        }
+    fn low_bit(&self) -> Bit {
+        self.get(N - 1)
+    }
+
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        BigBinary::from_binary(self).to_integer(fraction)
+    }
+
+    fn saturated(sign: Bit) -> Option<Self> {
+        Some(Binary::saturated(sign))
+    }
+
+    fn widen(&self) -> Option<BigBinary> {
+        Some(BigBinary::from_binary(self))
+    }
+
+    fn narrow_saturating(wide: &BigBinary) -> Option<Self> {
+        number::saturating(wide)
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        Binary::add_using(self, other, adder)
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        Binary::sub_using(self, other, adder)
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        Binary::mul_using(self, other, multiplier)
+    }
+
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        Binary::div_rem_using(self, other, divider)
+    }
+
+    fn mul_fixed_using(&self, other: &Self, multiplier: &dyn Multiplier, fraction: usize) -> (Self, Flags, usize) {
+        Binary::mul_fixed_using(self, other, multiplier, fraction)
+    }
+
+    fn div_fixed_using(&self, other: &Self, divider: &dyn Divider, fraction: usize) -> (Self, Flags) {
+        Binary::div_fixed_using(self, other, divider, fraction)
+    }
+
+    fn pow_with_flags(&self, exp: &Self) -> (Self, Flags) {
+        Binary::pow_with_flags(self, exp)
+    }
+
+    fn pow_using(&self, exp: &Self, multiplier: &dyn Multiplier, divider: &dyn Divider) -> (Self, Flags, usize) {
+        Binary::pow_using(self, exp, multiplier, divider)
+    }
+
+    fn and_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self & other;
+        let flags = Flags::from_result(&result);
+
+        (result, flags)
+    }
+
+    fn or_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self | other;
+        let flags = Flags::from_result(&result);
+
+        (result, flags)
+    }
+
+    fn xor_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let result = self ^ other;
+        let flags = Flags::from_result(&result);
+
+        (result, flags)
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        let result = !self;
+        let flags = Flags::from_result(&result);
+
+        (result, flags)
+    }
+
+    fn shift_with_flags(&self, amount: &Self, shift: Shift) -> (Self, Flags) {
+        Binary::shift_with_flags(self, amount, shift)
+    }
+
+    fn sqrt(&self) -> Self {
+        self.sqrt_unsigned()
+    }
+
+    fn root(&self, degree: &Self) -> Self {
+        self.root_unsigned(degree)
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        let mut binary = Binary::zero();
+        binary.set(N - 1, bit);
+        binary
+    }
+
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        Binary::compare(self, other)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{Binary, Bit};
+    use super::{Bit, Flags, Shift};
+    use std::cmp::Ordering;
+    use super::Bit::{Off, On};
+
+    // Unless a test says otherwise, use the default 64-bit register
+    type Binary = super::Binary;
 
     #[test]
     fn test_shl() {
@@ -319,6 +1161,339 @@ mod tests {
         assert_eq!(shifted, one);
     }
 
+    #[test]
+    fn test_pow() {
+        let pow = |x: i64, y: i64| Binary::from_int(x).pow(&Binary::from_int(y)).to_int();
+
+        assert_eq!(pow(0, 0), Ok(1), "0 ^ 0");
+        assert_eq!(pow(5, 0), Ok(1), "5 ^ 0");
+        assert_eq!(pow(0, 5), Ok(0), "0 ^ 5");
+        assert_eq!(pow(2, 62), Ok(1i64 << 62), "2 ^ 62");
+        assert_eq!(pow(2, 64), Ok(0), "2 ^ 64");
+        assert_eq!(pow(-3, 3), Ok(-27), "-3 ^ 3");
+
+        // Negative exponents truncate toward zero like division does
+        assert_eq!(pow(1, -7), Ok(1), "1 ^ -7");
+        assert_eq!(pow(-1, -7), Ok(-1), "-1 ^ -7");
+        assert_eq!(pow(-1, -8), Ok(1), "-1 ^ -8");
+        assert_eq!(pow(2, -1), Ok(0), "2 ^ -1");
+        assert_eq!(pow(-1, i64::MIN), Ok(1), "-1 ^ min");
+    }
+
+    #[test]
+    fn test_roots() {
+        for x in 0..=255u8 {
+            let binary = super::Binary::<8>::from_uint(x);
+            let root = |n: u8| binary.root_unsigned(&super::Binary::<8>::from_uint(n)).to_uint::<u8>().unwrap();
+
+            let sqrt = binary.sqrt_unsigned().to_uint::<u8>().unwrap();
+            assert!(sqrt as u16 * sqrt as u16 <= x as u16 && (sqrt as u16 + 1).pow(2) > x as u16, "sqrt({})", x);
+
+            for n in 1..=9u32 {
+                let r = root(n as u8) as u32;
+                assert!(r.pow(n) <= x as u32 && (r + 1).pow(n) > x as u32, "root({}, {})", x, n);
+            }
+        }
+
+        // Odd widths start with a pair holding only the top Bit
+        assert_eq!(super::Binary::<7>::from_uint(121u8).sqrt_unsigned().to_uint::<u8>(), Ok(11));
+        assert_eq!(Binary::from_uint(u64::MAX).sqrt_unsigned().to_uint::<u64>(), Ok(u32::MAX as u64));
+        assert_eq!(
+            Binary::from_uint(u64::MAX).root_unsigned(&Binary::from_uint(5u64)).to_uint::<u64>(),
+            Ok(7131)
+        );
+    }
+
+    #[test]
+    fn test_div_rem() {
+        let div_rem = |x: i64, y: i64| {
+            let (q, r) = Binary::from_int(x).div_rem(&Binary::from_int(y));
+            (q.to_int(), r.to_int())
+        };
+
+        assert_eq!(div_rem(17, 5), (Ok(3), Ok(2)), "17 / 5");
+        assert_eq!(div_rem(-17, 5), (Ok(-3), Ok(-2)), "-17 / 5");
+        assert_eq!(div_rem(17, -5), (Ok(-3), Ok(2)), "17 / -5");
+        assert_eq!(div_rem(-17, -5), (Ok(3), Ok(-2)), "-17 / -5");
+    }
+
+    #[test]
+    fn test_add_flags() {
+        let add = |x: i64, y: i64| Binary::from_int(x).add_with_flags(&Binary::from_int(y)).1;
+
+        let flags = add(1, 2);
+        assert_eq!(flags, Flags::from_result(&Binary::from_int(3)), "1 + 2");
+
+        let flags = add(-1, 1);
+        assert_eq!((flags.carry, flags.overflow, flags.zero), (On, Off, On), "-1 + 1");
+
+        let flags = add(i64::MAX, 1);
+        assert_eq!((flags.carry, flags.overflow, flags.negative), (Off, On, On), "max + 1");
+
+        let flags = add(i64::MIN, -1);
+        assert_eq!((flags.carry, flags.overflow, flags.negative), (On, On, Off), "min + -1");
+    }
+
+    #[test]
+    fn test_sub_flags() {
+        let sub = |x: i64, y: i64| Binary::from_int(x).sub_with_flags(&Binary::from_int(y)).1;
+
+        let flags = sub(5, 5);
+        assert_eq!((flags.carry, flags.overflow, flags.zero), (On, Off, On), "5 - 5");
+
+        let flags = sub(3, 5);
+        assert_eq!((flags.carry, flags.overflow, flags.negative), (Off, Off, On), "3 - 5");
+
+        let flags = sub(0, i64::MIN);
+        assert_eq!(flags.overflow, On, "0 - min");
+
+        let flags = sub(-1, i64::MIN);
+        assert_eq!(flags.overflow, Off, "-1 - min");
+
+        let flags = Binary::from_int(i64::MIN).neg_with_flags().1;
+        assert_eq!(flags.overflow, On, "-min");
+    }
+
+    #[test]
+    fn test_mul_flags() {
+        let mul = |x: i64, y: i64| {
+            let (product, flags) = Binary::from_int(x).mul_with_flags(&Binary::from_int(y));
+            (product.to_int(), flags.overflow)
+        };
+
+        assert_eq!(mul(3, -5), (Ok(-15), Off), "3 * -5");
+        assert_eq!(mul(0, i64::MIN), (Ok(0), Off), "0 * min");
+        assert_eq!(mul(i64::MIN, 1), (Ok(i64::MIN), Off), "min * 1");
+        assert_eq!(mul(i64::MIN / 2, 2), (Ok(i64::MIN), Off), "min / 2 * 2");
+        assert_eq!(mul(i64::MAX / 2 + 1, 2), (Ok(i64::MIN), On), "(max / 2 + 1) * 2");
+        assert_eq!(mul(i64::MIN, -1), (Ok(i64::MIN), On), "min * -1");
+        assert_eq!(mul(1 << 32, 1 << 32), (Ok(0), On), "2^32 * 2^32");
+        assert_eq!(mul(-(1 << 40), 1 << 30), (Ok(0), On), "-2^40 * 2^30");
+    }
+
+    #[test]
+    fn test_div_flags() {
+        let div = |x: i64, y: i64| Binary::from_int(x).div_rem_with_flags(&Binary::from_int(y)).2;
+
+        let flags = div(7, 2);
+        assert_eq!((flags.overflow, flags.divide_by_zero), (Off, Off), "7 / 2");
+
+        let flags = div(7, 0);
+        assert_eq!(flags.divide_by_zero, On, "7 / 0");
+
+        let flags = div(i64::MIN, -1);
+        assert_eq!((flags.overflow, flags.divide_by_zero), (On, Off), "min / -1");
+
+        let flags = div(i64::MIN, 1);
+        assert_eq!(flags.overflow, Off, "min / 1");
+    }
+
+    #[test]
+    fn test_pow_flags() {
+        let pow = |x: i64, y: i64| Binary::from_int(x).pow_with_flags(&Binary::from_int(y)).1;
+
+        assert_eq!(pow(2, 62).overflow, Off, "2 ^ 62");
+        assert_eq!(pow(2, 63).overflow, On, "2 ^ 63");
+        assert_eq!(pow(-2, 63).overflow, Off, "-2 ^ 63");
+        assert_eq!(pow(3, 1000).overflow, On, "3 ^ 1000");
+        assert_eq!(pow(0, -1).divide_by_zero, On, "0 ^ -1");
+        assert_eq!(pow(2, -64), Flags::from_result(&Binary::zero()), "2 ^ -64");
+    }
+
+    #[test]
+    fn test_8_bit_circuits() {
+        type Binary = super::Binary<8>;
+
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                let (a, b) = (Binary::from_int(x), Binary::from_int(y));
+
+                let (sum, flags) = a.add_with_flags(&b);
+                assert_eq!(sum.to_int(), Ok(x.wrapping_add(y)), "{} + {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_add(y).is_none(), "{} + {}", x, y);
+
+                let (difference, flags) = a.sub_with_flags(&b);
+                assert_eq!(difference.to_int(), Ok(x.wrapping_sub(y)), "{} - {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_sub(y).is_none(), "{} - {}", x, y);
+
+                let (product, flags) = a.mul_with_flags(&b);
+                assert_eq!(product.to_int(), Ok(x.wrapping_mul(y)), "{} * {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_mul(y).is_none(), "{} * {}", x, y);
+
+                if y != 0 {
+                    let (quotient, remainder, flags) = a.div_rem_with_flags(&b);
+                    assert_eq!(quotient.to_int(), Ok(x.wrapping_div(y)), "{} / {}", x, y);
+                    assert_eq!(remainder.to_int(), Ok(x.wrapping_rem(y)), "{} % {}", x, y);
+                    assert_eq!(flags.overflow == On, x.checked_div(y).is_none(), "{} / {}", x, y);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_128_bit_circuits() {
+        type Binary = super::Binary<128>;
+
+        let args = [
+            (0, 0),
+            (1, -1),
+            (i128::MAX, 1),
+            (i128::MIN, -1),
+            (i64::MAX as i128, i64::MAX as i128),
+            (-92837498237498237498, 3298472398472398),
+            (123456789012345678901234567890, -987654321),
+        ];
+
+        for (x, y) in args.iter() {
+            let (a, b) = (Binary::from_int(*x), Binary::from_int(*y));
+
+            assert_eq!((&a + &b).to_int(), Ok(x.wrapping_add(*y)), "{} + {}", x, y);
+            assert_eq!((&a - &b).to_int(), Ok(x.wrapping_sub(*y)), "{} - {}", x, y);
+            assert_eq!((&a * &b).to_int(), Ok(x.wrapping_mul(*y)), "{} * {}", x, y);
+
+            if *y != 0 {
+                assert_eq!((&a / &b).to_int(), Ok(x.wrapping_div(*y)), "{} / {}", x, y);
+                assert_eq!((&a % &b).to_int(), Ok(x.wrapping_rem(*y)), "{} % {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_8_bit_fixed() {
+        use super::{Array, Restoring};
+        type Binary = super::Binary<8>;
+
+        // Q4.4, where a product or quotient truncated toward zero has to
+        // fit back in 8 Bits
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                let (a, b) = (Binary::from_int(x), Binary::from_int(y));
+
+                let expected = (x as i32 * y as i32) / 16;
+                let (product, flags, _) = a.mul_fixed_using(&b, &Array, 4);
+                assert_eq!(product.to_int(), Ok(expected as i8), "{} * {}", x, y);
+                assert_eq!(flags.overflow == On, !(-128..=127).contains(&expected), "{} * {}", x, y);
+
+                if y != 0 {
+                    let expected = (x as i32 * 16) / y as i32;
+                    let (quotient, flags) = a.div_fixed_using(&b, &Restoring, 4);
+                    assert_eq!(quotient.to_int(), Ok(expected as i8), "{} / {}", x, y);
+                    assert_eq!(flags.overflow == On, !(-128..=127).contains(&expected), "{} / {}", x, y);
+                }
+
+                let (x, y) = (x as u8, y as u8);
+
+                let expected = (x as u32 * y as u32) >> 4;
+                let (product, flags, _) = a.mul_unsigned_fixed_using(&b, &Array, 4);
+                assert_eq!(product.to_uint(), Ok(expected as u8), "{} * {}", x, y);
+                assert_eq!(flags.overflow == On, expected > 0xFF, "{} * {}", x, y);
+
+                if y != 0 {
+                    let expected = (x as u32) * 16 / y as u32;
+                    let (quotient, flags) = a.div_unsigned_fixed_using(&b, &Restoring, 4);
+                    assert_eq!(quotient.to_uint(), Ok(expected as u8), "{} / {}", x, y);
+                    assert_eq!(flags.overflow == On, expected > 0xFF, "{} / {}", x, y);
+                }
+            }
+        }
+
+        let (_, flags) = Binary::one().div_fixed_using(&Binary::zero(), &Restoring, 4);
+        assert_eq!(flags.divide_by_zero, On);
+    }
+
+    #[test]
+    fn test_resize() {
+        let resize = |n: i64| {
+            let (resized, flags) = Binary::from_int(n).resize_with_flags::<8>();
+            (resized.to_int::<i8>(), flags.overflow)
+        };
+
+        assert_eq!(resize(127), (Ok(127), Off), "127");
+        assert_eq!(resize(-128), (Ok(-128), Off), "-128");
+        assert_eq!(resize(128), (Ok(-128), On), "128");
+        assert_eq!(resize(-129), (Ok(127), On), "-129");
+        assert_eq!(resize(256), (Ok(0), On), "256");
+
+        let widened: super::Binary<128> = Binary::from_int(-5).resize();
+        assert_eq!(widened.to_int(), Ok(-5i128), "-5");
+
+        assert_eq!(super::Binary::<16>::from_int(-2i64).to_int(), Ok(-2i16), "-2");
+        assert!(Binary::from_int(i64::MAX).to_int::<i32>().is_err(), "max");
+    }
+
+    #[test]
+    fn test_bitwise() {
+        let (a, b) = (Binary::from_int(0b1100), Binary::from_int(0b1010));
+
+        assert_eq!((&a & &b).to_int(), Ok(0b1000i64));
+        assert_eq!((&a | &b).to_int(), Ok(0b1110i64));
+        assert_eq!((&a ^ &b).to_int(), Ok(0b0110i64));
+        assert_eq!((!&a).to_int(), Ok(!0b1100i64));
+    }
+
+    #[test]
+    fn test_barrel_shifter() {
+        type Binary = super::Binary<8>;
+
+        for x in i8::MIN..=i8::MAX {
+            for k in i8::MIN..=i8::MAX {
+                let (a, amount) = (Binary::from_int(x), Binary::from_int(k));
+                let shift = |shift| {
+                    let (shifted, flags) = a.shift_with_flags(&amount, shift);
+                    (shifted.to_int::<i8>().unwrap(), flags.overflow)
+                };
+
+                // Rust's shifts mask the amount rather than shifting every
+                // Bit out, so only compare the amounts in range
+                if (0..8).contains(&k) {
+                    let k = k as u32;
+
+                    assert_eq!(shift(Shift::Left), (x << k, Off), "{} << {}", x, k);
+                    assert_eq!(shift(Shift::ArithmeticRight), (x >> k, Off), "{} >> {}", x, k);
+                    assert_eq!(shift(Shift::LogicalRight), (((x as u8) >> k) as i8, Off), "{} >>> {}", x, k);
+                } else {
+                    assert_eq!(shift(Shift::Left), (0, On), "{} << {}", x, k);
+                    assert_eq!(shift(Shift::ArithmeticRight), (x >> 7, On), "{} >> {}", x, k);
+                    assert_eq!(shift(Shift::LogicalRight), (0, On), "{} >>> {}", x, k);
+                }
+
+                let k = k.rem_euclid(8) as u32;
+
+                assert_eq!(shift(Shift::RotateLeft), (x.rotate_left(k), Off), "{} rotl {}", x, k);
+                assert_eq!(shift(Shift::RotateRight), (x.rotate_right(k), Off), "{} rotr {}", x, k);
+            }
+        }
+    }
+
+    #[test]
+    fn test_shift_operators() {
+        let (x, k) = (Binary::from_int(-1000), Binary::from_int(3));
+
+        assert_eq!((&x << &k).to_int(), Ok(-8000i64));
+        assert_eq!((&x >> &k).to_int(), Ok(-125i64));
+        assert_eq!((&x << &Binary::from_int(64)).to_int(), Ok(0i64));
+    }
+
+    #[test]
+    fn test_comparator() {
+        type Binary = super::Binary<8>;
+
+        for x in i8::MIN..=i8::MAX {
+            for y in i8::MIN..=i8::MAX {
+                let (a, b) = (Binary::from_int(x), Binary::from_int(y));
+                let expected = |ordering: Ordering| {
+                    let bit = |b| if b { On } else { Off };
+                    (bit(ordering.is_lt()), bit(ordering.is_eq()), bit(ordering.is_gt()))
+                };
+
+                assert_eq!(a.compare(&b), expected(x.cmp(&y)), "{} cmp {}", x, y);
+                assert_eq!(a.compare_unsigned(&b), expected((x as u8).cmp(&(y as u8))), "{} cmp {}", x, y);
+                assert_eq!(a.cmp(&b), x.cmp(&y), "{} cmp {}", x, y);
+                assert_eq!(a.cmp_unsigned(&b), (x as u8).cmp(&(y as u8)), "{} cmp {}", x, y);
+            }
+        }
+    }
+
     #[test]
     fn test_partial_eq() {
         assert_ne!(Binary::zero(), Binary::one());
@@ -345,8 +1520,8 @@ mod tests {
         assert_eq!(Binary::from_int(0), zero, "0");
         assert_eq!(Binary::from_int(1), one, "1");
         assert_eq!(Binary::from_int(-1), negative_one, "-1");
-        assert_eq!(Binary::from_int(-9223372036854775808), min, "min");
-        assert_eq!(Binary::from_int(9223372036854775807), max, "max");
+        assert_eq!(Binary::from_int(-9223372036854775808i64), min, "min");
+        assert_eq!(Binary::from_int(9223372036854775807i64), max, "max");
     }
 
     #[test]
@@ -370,7 +1545,18 @@ mod tests {
         assert_eq!(Binary::to_int(zero), Ok(0), "0");
         assert_eq!(Binary::to_int(one), Ok(1), "1");
         assert_eq!(Binary::to_int(negative_one), Ok(-1), "-1");
-        assert_eq!(Binary::to_int(min), Ok(::std::i64::MIN), "min");
-        assert_eq!(Binary::to_int(max), Ok(::std::i64::MAX), "max");
+        assert_eq!(Binary::to_int(min), Ok(i64::MIN), "min");
+        assert_eq!(Binary::to_int(max), Ok(i64::MAX), "max");
+    }
+
+    #[test]
+    fn uint_test() {
+        assert_eq!(Binary::from_uint(u64::MAX), Binary::of(Bit::On));
+        assert_eq!(Binary::from_uint(u64::MAX).to_uint(), Ok(u64::MAX));
+        assert_eq!(Binary::from_uint(1u8).to_uint(), Ok(1u64));
+        assert_eq!(Binary::from_uint(255u8), Binary::from_int(255));
+        assert_eq!(Binary::from_uint(u128::MAX).to_uint(), Ok(u64::MAX));
+        assert!(Binary::from_uint(1u64 << 32).to_uint::<u32>().is_err());
+        assert_eq!(Binary::from_int(-1).to_uint::<u64>(), Ok(u64::MAX));
     }
 }
@@ -29,6 +29,12 @@ impl Bit {
     pub fn multiplier(b1: Bit, b2: Bit) -> Bit {
         b1 & b2
     }
+
+    /// Two-input multiplexer, passing through the first input when the
+    /// select line is off and the second when it is on
+    pub fn multiplexer(select: Bit, b1: Bit, b2: Bit) -> Bit {
+        (b1 & !select) | (b2 & select)
+    }
 }
 
 impl fmt::Debug for Bit {
@@ -110,6 +116,14 @@ mod tests {
         assert_eq!(Bit::half_adder(On, On), (Off, On));
     }
 
+    #[test]
+    fn test_multiplexer() {
+        assert_eq!(Bit::multiplexer(Off, Off, On), Off);
+        assert_eq!(Bit::multiplexer(Off, On, Off), On);
+        assert_eq!(Bit::multiplexer(On, Off, On), On);
+        assert_eq!(Bit::multiplexer(On, On, Off), Off);
+    }
+
     #[test]
     fn test_full_adder() {
         // No sum, no carry
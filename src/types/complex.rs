@@ -0,0 +1,366 @@
+use super::adder::{Adder, RippleCarry};
+use super::divider::Divider;
+use super::multiplier::{Array, Multiplier};
+use super::{BigBinary, Bit, Decimal, Flags, Number, Shift};
+
+/// Complex: A real and an imaginary part held in another representation,
+/// eg. 3+4i, where products and quotients combine the parts through that
+/// representation's own circuits
+#[derive(Clone, Debug, PartialEq)]
+pub struct Complex<T> {
+    pub re: T,
+    pub im: T,
+}
+
+/// Runs a circuit on two parts, also returning how many partial products
+/// it added
+type Product<'a, T> = &'a dyn Fn(&T, &T) -> (T, Flags, usize);
+
+/// Runs a division circuit on two parts
+type Quotient<'a, T> = &'a dyn Fn(&T, &T) -> (T, Flags);
+
+impl<T: Number> Complex<T> {
+    /// Creates the real number n + 0i
+    pub fn real(n: T) -> Complex<T> {
+        Complex {
+            re: n,
+            im: T::from_bit(Bit::Off),
+        }
+    }
+
+    /// (a + bi)(c + di) = (ac - bd) + (ad + bc)i, with the given circuit for
+    /// each product and the adder for the rest
+    fn product(&self, other: &Self, mul: Product<T>, adder: &dyn Adder) -> (Self, Flags, usize) {
+        let (ac, f1, n1) = mul(&self.re, &other.re);
+        let (bd, f2, n2) = mul(&self.im, &other.im);
+        let (ad, f3, n3) = mul(&self.re, &other.im);
+        let (bc, f4, n4) = mul(&self.im, &other.re);
+
+        let (re, f5) = ac.sub_using(&bd, adder);
+        let (im, f6) = ad.add_using(&bc, adder);
+
+        let product = Complex { re, im };
+        let flags = product.flags(&[f1, f2, f3, f4, f5, f6]);
+
+        (product, flags, n1 + n2 + n3 + n4)
+    }
+
+    /// (a + bi)/(c + di) = ((ac + bd) + (bc - ad)i) / (c² + d²), with the
+    /// given circuits for the products and quotients and the adder for the
+    /// rest, which for integer parts truncates each part toward zero
+    fn quotient(&self, other: &Self, mul: Product<T>, div: Quotient<T>, adder: &dyn Adder) -> (Self, Flags) {
+        if other.is_zero() == Bit::On {
+            let flags = Flags {
+                divide_by_zero: Bit::On,
+                ..Flags::from_condition(Bit::Off)
+            };
+
+            return (Complex::real(T::from_bit(Bit::Off)), flags);
+        }
+
+        let (ac, f1, _) = mul(&self.re, &other.re);
+        let (bd, f2, _) = mul(&self.im, &other.im);
+        let (bc, f3, _) = mul(&self.im, &other.re);
+        let (ad, f4, _) = mul(&self.re, &other.im);
+        let (cc, f5, _) = mul(&other.re, &other.re);
+        let (dd, f6, _) = mul(&other.im, &other.im);
+
+        let (re, f7) = ac.add_using(&bd, adder);
+        let (im, f8) = bc.sub_using(&ad, adder);
+        let (norm, f9) = cc.add_using(&dd, adder);
+
+        let (re, f10) = div(&re, &norm);
+        let (im, f11) = div(&im, &norm);
+
+        let quotient = Complex { re, im };
+        let flags = quotient.flags(&[f1, f2, f3, f4, f5, f6, f7, f8, f9, f10, f11]);
+
+        (quotient, flags)
+    }
+
+    /// Returns On if both parts are 0
+    fn is_zero(&self) -> Bit {
+        let zero = T::from_bit(Bit::Off);
+        self.re.compare(&zero).1 & self.im.compare(&zero).1
+    }
+
+    /// Gathers the overflow of every circuit that made the value, with the
+    /// zero flag only raised if both parts are 0 and the negative flag
+    /// following the real part
+    fn flags(&self, parts: &[Flags]) -> Flags {
+        let overflow = parts.iter().fold(Bit::Off, |overflow, flags| overflow | flags.overflow);
+        let divide_by_zero = parts.iter().fold(Bit::Off, |divide, flags| divide | flags.divide_by_zero);
+
+        Flags {
+            carry: overflow,
+            overflow,
+            zero: self.is_zero(),
+            negative: self.re.sign(),
+            divide_by_zero,
+        }
+    }
+
+    /// Runs a circuit that takes two parts on both pairs of parts in turn
+    fn each(&self, other: &Self, circuit: Quotient<T>) -> (Self, Flags) {
+        let (re, f1) = circuit(&self.re, &other.re);
+        let (im, f2) = circuit(&self.im, &other.im);
+
+        let result = Complex { re, im };
+        let flags = result.flags(&[f1, f2]);
+
+        (result, flags)
+    }
+
+    fn with_flags(self) -> (Self, Flags) {
+        let flags = self.flags(&[]);
+        (self, flags)
+    }
+}
+
+impl<T: Number> Number for Complex<T> {
+    const SIGNED: bool = T::SIGNED;
+    const INTEGRAL: bool = false;
+
+    type Wide = Complex<T::Wide>;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        let (loaded, flags) = T::from_literal(n);
+        (Complex::real(loaded), flags)
+    }
+
+    fn from_decimal(d: &Decimal) -> (Self, Flags) {
+        let (loaded, flags) = T::from_decimal(d);
+        (Complex::real(loaded), flags)
+    }
+
+    fn from_fixed_decimal(d: &Decimal, fraction: usize) -> (Self, Flags) {
+        let (loaded, flags) = T::from_fixed_decimal(d, fraction);
+        (Complex::real(loaded), flags)
+    }
+
+    /// The sign of the real part, which is all that ordering looks at
+    fn sign(&self) -> Bit {
+        self.re.sign()
+    }
+
+    fn low_bit(&self) -> Bit {
+        self.re.low_bit()
+    }
+
+    /// Only a real number can be a whole one
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        match self.im.compare(&T::from_bit(Bit::Off)).1 {
+            Bit::On => self.re.to_integer(fraction),
+            Bit::Off => None,
+        }
+    }
+
+    /// A limit on the real line, which is all a single sign can point to
+    fn saturated(sign: Bit) -> Option<Self> {
+        T::saturated(sign).map(Complex::real)
+    }
+
+    fn widen(&self) -> Option<Complex<T::Wide>> {
+        Some(Complex {
+            re: self.re.widen()?,
+            im: self.im.widen()?,
+        })
+    }
+
+    /// Each part saturates toward its own sign, while one that fits keeps
+    /// its value
+    fn narrow_saturating(wide: &Complex<T::Wide>) -> Option<Self> {
+        Some(Complex {
+            re: T::narrow_saturating(&wide.re)?,
+            im: T::narrow_saturating(&wide.im)?,
+        })
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.each(other, &|x, y| x.add_using(y, adder))
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.each(other, &|x, y| x.sub_using(y, adder))
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        self.product(other, &|x, y| x.mul_using(y, multiplier), &RippleCarry)
+    }
+
+    fn mul_fixed_using(&self, other: &Self, multiplier: &dyn Multiplier, fraction: usize) -> (Self, Flags, usize) {
+        self.product(other, &|x, y| x.mul_fixed_using(y, multiplier, fraction), &RippleCarry)
+    }
+
+    /// Divides as in `quotient`, along with the remainder `self - q * other`
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        let (quotient, flags) = self.quotient(
+            other,
+            &|x, y| x.mul_using(y, &Array),
+            &|x, y| {
+                let (quotient, _, flags) = x.div_rem_using(y, divider);
+                (quotient, flags)
+            },
+            &RippleCarry,
+        );
+
+        let (whole, _) = quotient.mul_with_flags(other);
+        let (remainder, _) = self.sub_with_flags(&whole);
+
+        (quotient, remainder, flags)
+    }
+
+    fn div_fixed_using(&self, other: &Self, divider: &dyn Divider, fraction: usize) -> (Self, Flags) {
+        self.quotient(
+            other,
+            &|x, y| x.mul_fixed_using(y, &Array, fraction),
+            &|x, y| x.div_fixed_using(y, divider, fraction),
+            &RippleCarry,
+        )
+    }
+
+    // Complex numbers have no circuits for the operations that need
+    // integers, which are turned away before getting here, or for powers,
+    // which are built from products instead, so these all leave the value
+    // as it is
+
+    fn pow_with_flags(&self, _exp: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn and_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn or_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn xor_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn shift_with_flags(&self, _amount: &Self, _shift: Shift) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    // Roots are turned away too, as they only work on real numbers
+
+    fn sqrt(&self) -> Self {
+        self.clone()
+    }
+
+    fn root(&self, _degree: &Self) -> Self {
+        self.clone()
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        Complex::real(T::from_bit(bit))
+    }
+
+    /// Orders by the real parts and then the imaginary ones, though only
+    /// equality means anything for complex numbers
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        let (less, equal, greater) = self.re.compare(&other.re);
+        let (im_less, im_equal, im_greater) = self.im.compare(&other.im);
+
+        (less | (equal & im_less), equal & im_equal, greater | (equal & im_greater))
+    }
+
+    /// (a + bi)i = -b + ai, where negating b can overflow
+    fn mul_i(&self, adder: &dyn Adder) -> Option<(Self, Flags)> {
+        let (re, flags) = self.im.neg_using(adder);
+        let result = Complex { re, im: self.re.clone() };
+        let flags = result.flags(&[flags]);
+
+        Some((result, flags))
+    }
+
+    fn re(&self) -> Self {
+        Complex::real(self.re.clone())
+    }
+
+    fn im(&self) -> Self {
+        Complex::real(self.im.clone())
+    }
+
+    fn conj_using(&self, adder: &dyn Adder) -> (Self, Flags) {
+        let (im, flags) = self.im.neg_using(adder);
+        let result = Complex { re: self.re.clone(), im };
+        let flags = result.flags(&[flags]);
+
+        (result, flags)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Complex;
+    use crate::types::adder::RippleCarry;
+    use crate::types::{Binary, Bit, Number, Rational, F64};
+
+    type Z = Complex<Binary<8>>;
+
+    fn z(re: i8, im: i8) -> Z {
+        Complex {
+            re: Binary::from_int(re),
+            im: Binary::from_int(im),
+        }
+    }
+
+    #[test]
+    fn test_integer_parts() {
+        assert_eq!(z(3, 4).add_with_flags(&z(1, -2)).0, z(4, 2));
+        assert_eq!(z(3, 4).sub_with_flags(&z(1, -2)).0, z(2, 6));
+        assert_eq!(z(3, 4).mul_with_flags(&z(1, -2)).0, z(11, -2));
+        assert_eq!(z(0, 1).mul_with_flags(&z(0, 1)).0, z(-1, 0));
+
+        let (quotient, remainder, flags) = z(11, -2).div_rem_with_flags(&z(1, -2));
+        assert_eq!((quotient, remainder, flags.overflow), (z(3, 4), z(0, 0), Bit::Off));
+
+        // Each part of a quotient of Gaussian integers truncates
+        let (quotient, remainder, _) = z(7, 3).div_rem_with_flags(&z(2, 0));
+        assert_eq!((quotient, remainder), (z(3, 1), z(1, 1)));
+
+        let (_, _, flags) = z(1, 1).div_rem_with_flags(&z(0, 0));
+        assert_eq!(flags.divide_by_zero, Bit::On);
+
+        let (_, flags) = z(100, 100).mul_with_flags(&z(1, 1));
+        assert_eq!(flags.overflow, Bit::On);
+    }
+
+    #[test]
+    fn test_parts() {
+        assert_eq!(z(3, 4).mul_i(&RippleCarry).map(|(n, _)| n), Some(z(-4, 3)));
+        assert_eq!(z(3, 4).conj_using(&RippleCarry).0, z(3, -4));
+        assert_eq!((z(3, 4).re(), z(3, 4).im()), (z(3, 0), z(4, 0)));
+
+        let (_, flags) = z(3, -128).conj_using(&RippleCarry);
+        assert_eq!(flags.overflow, Bit::On);
+
+        assert_eq!(z(1, 2).compare(&z(1, 2)), (Bit::Off, Bit::On, Bit::Off));
+        assert_eq!(z(1, 2).compare(&z(1, 3)), (Bit::On, Bit::Off, Bit::Off));
+    }
+
+    #[test]
+    fn test_other_parts() {
+        let q = |n: i64| Rational::whole(Binary::<64>::from_int(n));
+        let third = q(1).div_rem_with_flags(&q(3)).0;
+
+        // (1 + i) / (1 - 2i) = -1/5 + 3/5 i
+        let (quotient, _, _) = Complex { re: q(1), im: q(1) }.div_rem_with_flags(&Complex { re: q(1), im: q(-2) });
+        assert_eq!(quotient.re.to_string(), "-1/5");
+        assert_eq!(quotient.im.to_string(), "3/5");
+
+        let (sum, _) = Complex::real(third.clone()).add_with_flags(&Complex { re: third.clone(), im: third });
+        assert_eq!((sum.re.to_string(), sum.im.to_string()), ("2/3".to_string(), "1/3".to_string()));
+
+        let f = |n: f64| F64::from_f64(n);
+        let (product, _) = Complex { re: f(0.5), im: f(1.5) }.mul_with_flags(&Complex { re: f(2.0), im: f(-1.0) });
+        assert_eq!((product.re.to_f64(), product.im.to_f64()), (2.5, 2.5));
+    }
+}
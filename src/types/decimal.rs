@@ -0,0 +1,139 @@
+use std::fmt;
+
+use super::{BigBinary, Bit, Shift};
+
+/// Decimal: A literal with a fractional part or an exponent, held exactly as
+/// its digits read as one integer and scaled by a power of ten, eg. `1.5e-3`
+/// as 15 times ten to the -4
+#[derive(Clone, Debug, PartialEq)]
+pub struct Decimal {
+    /// Every digit of the literal, before and after the decimal point
+    pub digits: BigBinary,
+    /// The power of ten the digits are scaled by
+    pub exponent: BigBinary,
+}
+
+impl Decimal {
+    pub fn new(digits: BigBinary, exponent: BigBinary) -> Decimal {
+        Decimal { digits, exponent }
+    }
+
+    /// Reads the digits before and after the decimal point and the exponent
+    /// as written in a literal, where the exponent may start with a sign and
+    /// any of the three may be empty
+    pub fn from_parts(whole: &str, fraction: &str, exponent: &str) -> Option<Decimal> {
+        let digits = BigBinary::from_decimal(&format!("{}{}", whole, fraction))?;
+
+        let (negative, exponent) = match exponent.chars().next() {
+            Some('-') => (true, &exponent[1..]),
+            Some('+') => (false, &exponent[1..]),
+            _ => (false, exponent),
+        };
+
+        let mut exponent = BigBinary::from_decimal(exponent)?;
+
+        if negative {
+            exponent = -&exponent;
+        }
+
+        // Each digit after the point is another tenth
+        let places = BigBinary::from_decimal(&fraction.len().to_string())?;
+
+        Some(Decimal::new(digits, &exponent - &places))
+    }
+
+    /// Cuts off the fractional part, or returns None if the power of ten
+    /// grows too large to build
+    pub fn truncate(&self) -> Option<BigBinary> {
+        let ten = BigBinary::from_int(10i8);
+        let (power, flags) = ten.pow_with_flags(&self.exponent.magnitude());
+
+        match (self.exponent.is_negative(), flags.overflow) {
+            // Dividing by a power of ten too large to build leaves nothing
+            (true, Bit::On) => Some(BigBinary::zero()),
+            (true, Bit::Off) => Some(&self.digits / &power),
+            (false, Bit::On) => None,
+            (false, Bit::Off) => Some(&self.digits * &power),
+        }
+    }
+
+    /// Scales by two to the power of the given number of fraction Bits and
+    /// rounds to the nearest whole number, with ties away from zero, or
+    /// returns None if the power of ten grows too large to build
+    pub fn to_fixed(&self, fraction: usize) -> Option<BigBinary> {
+        let bits = |n: usize| BigBinary::from_int(n as i64);
+
+        // One Bit more than needed is a half in the last place kept, which
+        // rounds up when added before shifting it back out
+        let (digits, _) = self.digits.shift_with_flags(&bits(fraction + 1), Shift::Left);
+        let scaled = Decimal::new(digits, self.exponent.clone()).truncate()?;
+        let (rounded, _) = (&scaled + &BigBinary::one()).shift_with_flags(&bits(1), Shift::ArithmeticRight);
+
+        Some(rounded)
+    }
+}
+
+impl fmt::Display for Decimal {
+    /// Puts the decimal point back between the digits for a negative
+    /// exponent, and writes out any other exponent
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let digits = self.digits.to_string();
+
+        match self.exponent.to_int::<i64>() {
+            Ok(0) => write!(f, "{}.0", digits),
+            Ok(k) if k < 0 => {
+                let places = k.unsigned_abs() as usize;
+                let padded = format!("{:0>width$}", digits, width = places + 1);
+                let (whole, fraction) = padded.split_at(padded.len() - places);
+
+                write!(f, "{}.{}", whole, fraction)
+            }
+            _ => write!(f, "{}e{}", digits, self.exponent),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigBinary, Decimal};
+
+    fn decimal(whole: &str, fraction: &str, exponent: &str) -> Decimal {
+        Decimal::from_parts(whole, fraction, exponent).unwrap()
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let d = decimal("1", "5", "-3");
+
+        assert_eq!(d, Decimal::new(BigBinary::from_int(15), BigBinary::from_int(-4)));
+        assert_eq!(decimal("2", "", "+10").exponent, BigBinary::from_int(10));
+        assert_eq!(decimal("0", "001", "").digits, BigBinary::one());
+        assert!(Decimal::from_parts("1", "x", "").is_none());
+    }
+
+    #[test]
+    fn test_truncate() {
+        assert_eq!(decimal("12", "75", "").truncate(), Some(BigBinary::from_int(12)));
+        assert_eq!(decimal("1", "5", "3").truncate(), Some(BigBinary::from_int(1500)));
+        assert_eq!(decimal("9", "", "-99999").truncate(), Some(BigBinary::zero()));
+        assert_eq!(decimal("9", "", "99999").truncate(), None);
+    }
+
+    #[test]
+    fn test_to_fixed() {
+        assert_eq!(decimal("1", "5", "").to_fixed(16), Some(BigBinary::from_int(0x18000)));
+        assert_eq!(decimal("0", "1", "").to_fixed(16), Some(BigBinary::from_int(6554)));
+        assert_eq!(decimal("0", "75", "").to_fixed(1), Some(BigBinary::from_int(2)));
+        assert_eq!(decimal("2", "", "3").to_fixed(0), Some(BigBinary::from_int(2000)));
+        assert_eq!(decimal("9", "", "99999").to_fixed(8), None);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(decimal("1", "5", "-3").to_string(), "0.0015");
+        assert_eq!(decimal("3", "14", "").to_string(), "3.14");
+        assert_eq!(decimal("1", "0", "").to_string(), "1.0");
+        assert_eq!(decimal("25", "", "").to_string(), "25.0");
+        assert_eq!(decimal("2", "", "10").to_string(), "2e10");
+    }
+}
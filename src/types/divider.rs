@@ -0,0 +1,254 @@
+use std::str::FromStr;
+
+use super::adder::{Adder, RippleCarry};
+use super::Bit;
+
+/// Divider: A circuit that divides two equally wide sequences of Bits as
+/// unsigned values, ordered from most to least significant like Binary
+pub trait Divider {
+    /// Divides, returning the quotient and remainder at the same width
+    ///
+    /// Dividing by 0 yields a quotient with every Bit on and the dividend as
+    /// the remainder, leaving it to the caller to raise divide-by-zero.
+    fn divide(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>);
+}
+
+/// Subtracts the divisor from the partial remainder for every Bit of the
+/// dividend, adding it back whenever the difference goes negative
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Restoring;
+
+/// Lets the partial remainder go negative and makes up for it on the next
+/// step by adding the divisor instead of subtracting, so every step needs
+/// exactly one addition
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NonRestoring;
+
+/// Normalizes the divisor and picks each quotient digit from -1, 0 and 1
+/// by looking at only the top three Bits of the partial remainder, skipping
+/// the addition entirely for a 0
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Srt;
+
+/// The divider a calculation is run with, picked at runtime
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DividerKind {
+    Restoring,
+    NonRestoring,
+    Srt,
+}
+
+/// Zero-extends the Bits to the given width
+fn extend(bits: &[Bit], width: usize) -> Vec<Bit> {
+    let mut extended = vec![Bit::Off; width - bits.len()];
+
+    extended.extend_from_slice(bits);
+    extended
+}
+
+/// Shifts the register left by one, moving the Bit into the bottom
+fn shift_in(register: &[Bit], bit: Bit) -> Vec<Bit> {
+    let mut shifted = register[1..].to_vec();
+
+    shifted.push(bit);
+    shifted
+}
+
+/// Adds or subtracts the divisor in a single pass through the adder, by
+/// complementing it and carrying in whenever the Bit says to subtract
+fn add_or_subtract(register: &[Bit], divisor: &[Bit], subtract: Bit) -> Vec<Bit> {
+    let operand: Vec<_> = divisor.iter().map(|&bit| bit ^ subtract).collect();
+
+    RippleCarry.add(register, &operand, subtract).0
+}
+
+/// Keeps the least significant Bits of the register
+fn low(bits: &[Bit], width: usize) -> Vec<Bit> {
+    bits[bits.len() - width..].to_vec()
+}
+
+impl Divider for Restoring {
+    fn divide(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>) {
+        // The partial remainder can reach twice the divisor before it is
+        // subtracted, and needs a sign Bit above that
+        let width = a.len() + 2;
+        let divisor = extend(b, width);
+        let mut remainder = vec![Bit::Off; width];
+        let mut quotient = Vec::with_capacity(a.len());
+
+        for &bit in a {
+            remainder = shift_in(&remainder, bit);
+
+            // If the divisor fits then the quotient gets a "1" and the
+            // difference becomes the new partial remainder, otherwise the
+            // partial remainder is restored
+            let difference = add_or_subtract(&remainder, &divisor, Bit::On);
+            let less = difference[0];
+
+            quotient.push(!less);
+            remainder = difference
+                .iter()
+                .zip(remainder.iter())
+                .map(|(&d, &r)| Bit::multiplexer(less, d, r))
+                .collect();
+        }
+
+        (quotient, low(&remainder, a.len()))
+    }
+}
+
+impl Divider for NonRestoring {
+    fn divide(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>) {
+        let width = a.len() + 2;
+        let divisor = extend(b, width);
+        let mut remainder = vec![Bit::Off; width];
+        let mut quotient = Vec::with_capacity(a.len());
+
+        for &bit in a {
+            // A negative partial remainder gets the divisor added back after
+            // being doubled, which is the same as restoring it first and then
+            // subtracting
+            let negative = remainder[0];
+
+            remainder = add_or_subtract(&shift_in(&remainder, bit), &divisor, !negative);
+            quotient.push(!remainder[0]);
+        }
+
+        // Only the remainder needs correcting at the end, if it was left
+        // negative
+        let negative = remainder[0];
+        let correction: Vec<_> = divisor.iter().map(|&bit| bit & negative).collect();
+        let remainder = RippleCarry.add(&remainder, &correction, Bit::Off).0;
+
+        (quotient, low(&remainder, a.len()))
+    }
+}
+
+impl Divider for Srt {
+    fn divide(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>) {
+        let n = a.len();
+
+        // Normalizing needs an on Bit to move to the top
+        if b.iter().all(|&bit| bit == Bit::Off) {
+            return (vec![Bit::On; n], a.to_vec());
+        }
+
+        // Shifting both operands left until the divisor's top Bit is on keeps
+        // the quotient the same, but scales the remainder by as much
+        let shift = b.iter().take_while(|&&bit| bit == Bit::Off).count();
+        let width = n + 2;
+        let mut divisor = extend(&b[shift..], width - shift);
+
+        divisor.extend(vec![Bit::Off; shift]);
+
+        let mut dividend = a.to_vec();
+
+        dividend.extend(vec![Bit::Off; shift]);
+
+        // The quotient is kept as separate positive and negative digits
+        let mut remainder = vec![Bit::Off; width];
+        let mut positive = vec![Bit::Off; n + shift];
+        let mut negative = vec![Bit::Off; n + shift];
+
+        for (i, &bit) in dividend.iter().enumerate() {
+            remainder = shift_in(&remainder, bit);
+
+            // The normalized divisor is at least half the range of the
+            // register, so the sign and the two Bits below it are enough to
+            // tell whether it has to be subtracted, added or neither
+            let (sign, top, next) = (remainder[0], remainder[1], remainder[2]);
+            let subtract = !sign & (top | next);
+            let add = sign & !(top & next);
+
+            positive[i] = subtract;
+            negative[i] = add;
+
+            if (subtract | add) == Bit::On {
+                remainder = add_or_subtract(&remainder, &divisor, subtract);
+            }
+        }
+
+        // Converting the digits to an ordinary quotient takes a subtraction,
+        // and a negative remainder borrows one more from it
+        let inverted: Vec<_> = negative.iter().map(|&bit| !bit).collect();
+        let mut quotient = RippleCarry.add(&positive, &inverted, Bit::On).0;
+
+        let below = remainder[0];
+        let borrow: Vec<_> = quotient.iter().map(|_| below).collect();
+
+        quotient = RippleCarry.add(&quotient, &borrow, Bit::Off).0;
+
+        let correction: Vec<_> = divisor.iter().map(|&bit| bit & below).collect();
+        let remainder = RippleCarry.add(&remainder, &correction, Bit::Off).0;
+
+        // Undoing the normalization drops the Bits shifted in at the bottom
+        let remainder = extend(&remainder[..width - shift], width);
+
+        (low(&quotient, n), low(&remainder, n))
+    }
+}
+
+impl Divider for DividerKind {
+    fn divide(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, Vec<Bit>) {
+        match self {
+            DividerKind::Restoring => Restoring.divide(a, b),
+            DividerKind::NonRestoring => NonRestoring.divide(a, b),
+            DividerKind::Srt => Srt.divide(a, b),
+        }
+    }
+}
+
+impl FromStr for DividerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::DividerKind::*;
+
+        Ok(match s {
+            "restoring" => Restoring,
+            "non-restoring" => NonRestoring,
+            "srt" => Srt,
+            _ => return Err(format!("Unknown divider '{}'", s)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Divider, DividerKind};
+    use crate::types::circuits::{bits, for_8_bit_pairs, value};
+
+    #[test]
+    fn test_8_bit_agreement() {
+        for_8_bit_pairs(|x, y, a, b| {
+            let expected = DividerKind::Restoring.divide(a, b);
+
+            if let (Some(quotient), Some(remainder)) = (x.checked_div(y), x.checked_rem(y)) {
+                assert_eq!((value(&expected.0), value(&expected.1)), (quotient, remainder), "{} / {}", x, y);
+            }
+
+            for divider in [DividerKind::NonRestoring, DividerKind::Srt].iter() {
+                assert_eq!(divider.divide(a, b), expected, "{:?}: {} / {}", divider, x, y);
+            }
+        });
+    }
+
+    #[test]
+    fn test_wide_agreement() {
+        let operands = [
+            (u64::MAX as u128, 1),
+            (u64::MAX as u128, u64::MAX as u128),
+            (0x5555_5555_5555_5555, 0xaaaa_aaaa),
+            (12_345_678_901_234, 98_765),
+            (1, 0x8000_0000_0000_0000),
+        ];
+
+        for divider in [DividerKind::Restoring, DividerKind::NonRestoring, DividerKind::Srt].iter() {
+            for (x, y) in operands.iter() {
+                let (quotient, remainder) = divider.divide(&bits(*x, 64), &bits(*y, 64));
+
+                assert_eq!((value(&quotient), value(&remainder)), (x / y, x % y), "{:?}: {} / {}", divider, x, y);
+            }
+        }
+    }
+}
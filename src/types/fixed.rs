@@ -0,0 +1,88 @@
+use std::fmt;
+
+use super::{BigBinary, Binary, Bit, Shift};
+
+/// Fixed: Result of a fixed-point calculation, held as the integer its
+/// register read as along with how many of its Bits are fraction Bits, eg.
+/// 0x18000 with 16 of them for 1.5
+#[derive(Clone, Debug, PartialEq)]
+pub struct Fixed {
+    pub value: BigBinary,
+    pub fraction: usize,
+}
+
+impl Fixed {
+    pub fn new(value: BigBinary, fraction: usize) -> Fixed {
+        Fixed { value, fraction }
+    }
+
+    /// Reads a register as a signed fixed-point value
+    pub fn from_binary<const N: usize>(binary: &Binary<N>, fraction: usize) -> Fixed {
+        Fixed::new(BigBinary::from_binary(binary), fraction)
+    }
+
+    /// Reads a register as an unsigned fixed-point value
+    pub fn from_unsigned<const N: usize>(binary: &Binary<N>, fraction: usize) -> Fixed {
+        Fixed::new(BigBinary::from_unsigned(binary), fraction)
+    }
+}
+
+impl fmt::Display for Fixed {
+    /// Writes out the exact decimal expansion, which always ends since every
+    /// fraction Bit is a power of one half, by multiplying what's left of the
+    /// fraction by ten for every digit and taking the Bits that carry out
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let fraction = BigBinary::from_int(self.fraction as i64);
+        let (one, _) = BigBinary::one().shift_with_flags(&fraction, Shift::Left);
+        let mask = &one - &BigBinary::one();
+        let ten = BigBinary::from_int(10i8);
+
+        let magnitude = self.value.magnitude();
+        let (whole, _) = magnitude.shift_with_flags(&fraction, Shift::LogicalRight);
+        let mut rest = &magnitude & &mask;
+        let mut digits = String::new();
+
+        loop {
+            let scaled = &rest * &ten;
+            let (digit, _) = scaled.shift_with_flags(&fraction, Shift::LogicalRight);
+
+            digits.push_str(&digit.to_string());
+            rest = &scaled & &mask;
+
+            if rest.any() == Bit::Off {
+                break;
+            }
+        }
+
+        let sign = if self.value.is_negative() { "-" } else { "" };
+
+        write!(f, "{}{}.{}", sign, whole, digits)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigBinary, Binary, Fixed};
+
+    #[test]
+    fn test_display() {
+        let fixed = |n: i64, fraction: usize| Fixed::new(BigBinary::from_int(n), fraction);
+
+        assert_eq!(fixed(0x18000, 16).to_string(), "1.5");
+        assert_eq!(fixed(-0x18000, 16).to_string(), "-1.5");
+        assert_eq!(fixed(3, 0).to_string(), "3.0");
+        assert_eq!(fixed(0, 16).to_string(), "0.0");
+        assert_eq!(fixed(1, 2).to_string(), "0.25");
+        assert_eq!(fixed(0x5555, 16).to_string(), "0.3333282470703125");
+        assert_eq!(fixed(-1, 31).to_string(), "-0.0000000004656612873077392578125");
+    }
+
+    #[test]
+    fn test_registers() {
+        let min = Binary::<8>::from_int(i8::MIN);
+
+        assert_eq!(Fixed::from_binary(&min, 7).to_string(), "-1.0");
+        assert_eq!(Fixed::from_unsigned(&min, 7).to_string(), "1.0");
+        assert_eq!(Fixed::from_unsigned(&Binary::<8>::of(super::Bit::On), 4).to_string(), "15.9375");
+    }
+}
@@ -0,0 +1,42 @@
+use super::{Binary, Bit};
+
+/// Flags: Status word raised by an arithmetic circuit alongside its result
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Flags {
+    /// Carry out of the most significant Bit
+    pub carry: Bit,
+    /// Result did not fit in a signed Binary
+    pub overflow: Bit,
+    /// Result is 0
+    pub zero: Bit,
+    /// Result has its sign Bit on
+    pub negative: Bit,
+    /// Divisor was 0, so the result is meaningless
+    pub divide_by_zero: Bit,
+}
+
+impl Flags {
+    /// Derives the zero and negative flags from a circuit's result, with
+    /// every other flag off
+    pub fn from_result<const N: usize>(result: &Binary<N>) -> Flags {
+        Flags {
+            carry: Bit::Off,
+            overflow: Bit::Off,
+            zero: !result.any(),
+            negative: result.get(0),
+            divide_by_zero: Bit::Off,
+        }
+    }
+
+    /// Derives the flags for a condition's result of 1 or 0, which is
+    /// never negative
+    pub fn from_condition(condition: Bit) -> Flags {
+        Flags {
+            carry: Bit::Off,
+            overflow: Bit::Off,
+            zero: !condition,
+            negative: Bit::Off,
+            divide_by_zero: Bit::Off,
+        }
+    }
+}
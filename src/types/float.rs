@@ -0,0 +1,930 @@
+use std::fmt;
+use std::num::ParseIntError;
+
+use super::adder::Adder;
+use super::divider::Divider;
+use super::multiplier::Multiplier;
+use super::{BigBinary, Binary, Bit, Decimal, Flags, Number, Shift, Uint};
+
+/// Register significands are worked on in, wide enough for the product of
+/// two binary64 significands or one scaled up for division
+type Significand = Binary<128>;
+
+/// Register exponents are worked on in, wide enough for the exponent of any
+/// product, quotient or decimal literal before it is rounded
+type Exponent = Binary<32>;
+
+/// Number of Bits in a Significand
+const WIDTH: usize = 128;
+
+/// Number of Bits kept from an integer too wide for a Significand, which
+/// leaves plenty below the widest fraction to round from
+const KEPT: usize = 120;
+
+/// Float: IEEE-754 binary floating point number with an E-bit exponent and
+/// an M-bit fraction, whose arithmetic rounds to nearest, ties to even
+#[derive(Clone, Debug, PartialEq)]
+pub struct Float<const E: usize, const M: usize> {
+    sign: Bit,
+    exponent: Binary<E>,
+    fraction: Binary<M>,
+}
+
+/// IEEE-754 binary32, the same format as `f32`
+pub type F32 = Float<8, 23>;
+
+/// IEEE-754 binary64, the same format as `f64`
+pub type F64 = Float<11, 52>;
+
+/// Priority encoder counting the off Bits above the most significant on one
+fn leading_zeros<const N: usize>(binary: &Binary<N>) -> usize {
+    (0..N).take_while(|&i| binary.get(i) == Bit::Off).count()
+}
+
+/// Alignment shifter, moving the significand right through the barrel
+/// shifter and returning the guard Bit, ie. the last Bit shifted out, and the
+/// sticky Bit, which is on if any Bit below the guard was
+fn shift_right(significand: &Significand, amount: usize) -> (Significand, Bit, Bit) {
+    if amount == 0 {
+        return (significand.clone(), Bit::Off, Bit::Off);
+    }
+
+    if amount > WIDTH {
+        return (Significand::zero(), Bit::Off, significand.any());
+    }
+
+    let below_guard = !&(&Significand::of(Bit::On) << (amount - 1));
+    let sticky = (significand & &below_guard).any();
+    let guard = significand.get(WIDTH - amount);
+    let (shifted, _) = significand.shift_with_flags(&Significand::from_int(amount as i32), Shift::LogicalRight);
+
+    (shifted, guard, sticky)
+}
+
+/// Copies the lowest Bits of a register, to run through a circuit
+fn low_bits(significand: &Significand, width: usize) -> Vec<Bit> {
+    (WIDTH - width..WIDTH).map(|i| significand.get(i)).collect()
+}
+
+/// Places the Bits a circuit produced at the bottom of a register
+fn from_low_bits(bits: &[Bit]) -> Significand {
+    let mut significand = Significand::zero();
+
+    for (i, &bit) in bits.iter().rev().enumerate() {
+        significand.set(WIDTH - 1 - i, bit);
+    }
+
+    significand
+}
+
+impl<const E: usize, const M: usize> Float<E, M> {
+    /// Create 0 with the given sign
+    pub fn zero(sign: Bit) -> Self {
+        Float {
+            sign,
+            exponent: Binary::zero(),
+            fraction: Binary::zero(),
+        }
+    }
+
+    /// Create infinity with the given sign
+    pub fn infinity(sign: Bit) -> Self {
+        Float {
+            sign,
+            exponent: Binary::of(Bit::On),
+            fraction: Binary::zero(),
+        }
+    }
+
+    /// Create the quiet NaN that invalid operations give
+    pub fn nan() -> Self {
+        let mut fraction = Binary::zero();
+        fraction.set(0, Bit::On);
+
+        Float {
+            sign: Bit::Off,
+            exponent: Binary::of(Bit::On),
+            fraction,
+        }
+    }
+
+    /// Create a Float from the Bits of its IEEE-754 encoding, held in the
+    /// lowest Bits of an unsigned int
+    pub fn from_bits<T: Uint>(bits: T) -> Self {
+        let bits = Significand::from_uint(bits);
+        let start = WIDTH - 1 - E - M;
+        let mut float = Self::zero(bits.get(start));
+
+        for i in 0..E {
+            float.exponent.set(i, bits.get(start + 1 + i));
+        }
+
+        for i in 0..M {
+            float.fraction.set(i, bits.get(start + 1 + E + i));
+        }
+
+        float
+    }
+
+    /// Attempt to convert to the Bits of the IEEE-754 encoding, failing if
+    /// the unsigned int is too narrow to hold them
+    pub fn to_bits<T: Uint>(&self) -> Result<T, ParseIntError> {
+        let start = WIDTH - 1 - E - M;
+        let mut bits = Significand::zero();
+
+        bits.set(start, self.sign);
+
+        for i in 0..E {
+            bits.set(start + 1 + i, self.exponent.get(i));
+        }
+
+        for i in 0..M {
+            bits.set(start + 1 + E + i, self.fraction.get(i));
+        }
+
+        bits.to_uint()
+    }
+
+    /// Returns On for NaN, whose exponent is all ones and fraction isn't 0
+    pub fn is_nan(&self) -> Bit {
+        self.top_exponent() & self.fraction.any()
+    }
+
+    /// Returns On for either infinity, whose exponent is all ones and
+    /// fraction is 0
+    pub fn is_infinite(&self) -> Bit {
+        self.top_exponent() & !self.fraction.any()
+    }
+
+    /// Returns On for either 0
+    pub fn is_zero(&self) -> Bit {
+        !(self.exponent.any() | self.fraction.any())
+    }
+
+    /// Returns On if every Bit of the exponent is on
+    fn top_exponent(&self) -> Bit {
+        !(!&self.exponent).any()
+    }
+
+    /// Exponent and fraction side by side, which for finite values orders
+    /// the same way as the magnitude
+    fn magnitude(&self) -> Significand {
+        let mut magnitude = Significand::zero();
+
+        for i in 0..E {
+            magnitude.set(WIDTH - E - M + i, self.exponent.get(i));
+        }
+
+        for i in 0..M {
+            magnitude.set(WIDTH - M + i, self.fraction.get(i));
+        }
+
+        magnitude
+    }
+
+    /// Copy with the sign flipped, which is all negation takes
+    fn negated(&self) -> Self {
+        Float {
+            sign: !self.sign,
+            ..self.clone()
+        }
+    }
+
+    /// The bias stored exponents are offset by
+    fn bias() -> Exponent {
+        Exponent::from_int((1i32 << (E - 1)) - 1)
+    }
+
+    /// The exponent of the smallest normal numbers, which subnormals share
+    fn smallest() -> Exponent {
+        &Exponent::one() - &Self::bias()
+    }
+
+    /// Splits a finite value into its significand, with the hidden Bit in
+    /// place for normal numbers, and the exponent of the significand's least
+    /// significant Bit
+    fn unpack(&self) -> (Significand, Exponent) {
+        let normal = self.exponent.any();
+        let mut significand = Significand::zero();
+
+        significand.set(WIDTH - 1 - M, normal);
+
+        for i in 0..M {
+            significand.set(WIDTH - M + i, self.fraction.get(i));
+        }
+
+        let mut exponent = Exponent::zero();
+
+        for i in 0..E {
+            exponent.set(32 - E + i, self.exponent.get(i));
+        }
+
+        // Subnormals are stored with an exponent of 0 but mean 1
+        exponent.set(31, exponent.get(31) | !normal);
+
+        let exponent = &(&exponent - &Self::bias()) - &Exponent::from_int(M as i32);
+
+        (significand, exponent)
+    }
+
+    /// Shifts a subnormal significand left until its leading one is where
+    /// the hidden Bit would be, lowering the exponent to match
+    fn normalize(significand: Significand, exponent: Exponent) -> (Significand, Exponent) {
+        let shift = leading_zeros(&significand) - (WIDTH - 1 - M);
+
+        (&significand << shift, &exponent - &Exponent::from_int(shift as i32))
+    }
+
+    /// Rounds a significand, scaled by two to the power of the exponent, to
+    /// the nearest Float with ties going to even, where the sticky Bit says
+    /// whether anything was already dropped below the significand
+    ///
+    /// Normalizing leaves M Bits below the leading one, except that
+    /// subnormals stop at the smallest exponent and keep fewer. The first
+    /// Bit shifted out is the guard, and rounding up is needed when it is on
+    /// along with either a lower Bit or the lowest kept one.
+    fn round(sign: Bit, significand: Significand, exponent: Exponent, sticky: Bit) -> Self {
+        if significand.any() == Bit::Off {
+            return Self::zero(sign);
+        }
+
+        let lead = Exponent::from_int((WIDTH - 1 - leading_zeros(&significand)) as i32);
+        let top = &exponent + &lead;
+        let (below, _, _) = top.compare(&Self::smallest());
+        let top = if below == Bit::On { Self::smallest() } else { top };
+        let mut lowest = &top - &Exponent::from_int(M as i32);
+        let shift = (&lowest - &exponent).to_int::<i32>().unwrap_or(i32::MAX);
+
+        let (mut significand, guard, sticky) = if shift > 0 {
+            let (shifted, guard, dropped) = shift_right(&significand, shift as usize);
+            (shifted, guard, sticky | dropped)
+        } else {
+            (&significand << shift.unsigned_abs() as usize, Bit::Off, sticky)
+        };
+
+        let mut increment = Significand::zero();
+        increment.set(WIDTH - 1, guard & (sticky | significand.get(WIDTH - 1)));
+        significand = significand.add_unsigned_with_flags(&increment).0;
+
+        // Rounding up from all ones carries into a new leading Bit
+        if significand.get(WIDTH - 2 - M) == Bit::On {
+            significand = &significand >> 1;
+            lowest = &lowest + &Exponent::one();
+        }
+
+        // Subnormals have no leading one and are stored with an exponent of 0
+        let biased = if significand.get(WIDTH - 1 - M) == Bit::On {
+            &(&lowest + &Exponent::from_int(M as i32)) + &Self::bias()
+        } else {
+            Exponent::zero()
+        };
+
+        let largest = Exponent::from_int((1i32 << E) - 2);
+        let (_, _, greater) = biased.compare(&largest);
+
+        if greater == Bit::On {
+            return Self::infinity(sign);
+        }
+
+        let mut float = Self::zero(sign);
+
+        for i in 0..E {
+            float.exponent.set(i, biased.get(32 - E + i));
+        }
+
+        for i in 0..M {
+            float.fraction.set(i, significand.get(WIDTH - M + i));
+        }
+
+        float
+    }
+
+    /// Rounds a non-negative BigBinary scaled by two to the power of the
+    /// exponent, keeping only its top Bits in the register and folding the
+    /// rest into the sticky Bit
+    fn from_magnitude(sign: Bit, n: &BigBinary, exponent: Exponent, sticky: Bit) -> Self {
+        let width = n.width();
+        let kept = width.min(KEPT);
+        let mut significand = Significand::zero();
+
+        for i in 0..kept {
+            significand.set(WIDTH - kept + i, n.get(i));
+        }
+
+        let sticky = (kept..width).fold(sticky, |acc, i| acc | n.get(i));
+        let exponent = &exponent + &Exponent::from_int((width - kept) as i32);
+
+        Self::round(sign, significand, exponent, sticky)
+    }
+
+    /// Converts a decimal literal, dividing by the power of ten with enough
+    /// Bits to spare when the exponent is negative, so that the result is
+    /// only rounded once
+    fn round_decimal(d: &Decimal) -> Self {
+        if d.digits.any() == Bit::Off {
+            return Self::zero(Bit::Off);
+        }
+
+        // Past these bounds every format overflows or underflows, and the
+        // power of ten would only take longer to build
+        let k = match d.exponent.to_int::<i32>() {
+            Ok(k) if k > 400 => return Self::infinity(Bit::Off),
+            Ok(k) if k + (d.digits.width() as i32) < -400 => return Self::zero(Bit::Off),
+            Ok(k) => k,
+            Err(_) if d.exponent.is_negative() => return Self::zero(Bit::Off),
+            Err(_) => return Self::infinity(Bit::Off),
+        };
+
+        let ten = BigBinary::from_int(10i8);
+        let (power, _) = ten.pow_with_flags(&BigBinary::from_int(k.abs()));
+
+        if k >= 0 {
+            return Self::from_magnitude(Bit::Off, &(&d.digits * &power), Exponent::zero(), Bit::Off);
+        }
+
+        // Scaling the digits up first leaves the quotient with more Bits than
+        // the fraction needs, and the remainder only decides the sticky Bit
+        let scale = power.width() + M + 3;
+        let (scaled, _) = d.digits.shift_with_flags(&BigBinary::from_int(scale as i32), Shift::Left);
+        let (quotient, remainder, _) = scaled.div_rem_with_flags(&power);
+
+        Self::from_magnitude(Bit::Off, &quotient, Exponent::from_int(-(scale as i32)), remainder.any())
+    }
+
+    /// Aligns the smaller significand with the larger one and adds or
+    /// subtracts them, depending on whether the signs differ
+    fn sum(&self, other: &Self, adder: &dyn Adder) -> Self {
+        if (self.is_nan() | other.is_nan()) == Bit::On {
+            return Self::nan();
+        }
+
+        match (self.is_infinite(), other.is_infinite()) {
+            (Bit::On, Bit::On) if self.sign != other.sign => return Self::nan(),
+            (Bit::On, _) => return self.clone(),
+            (_, Bit::On) => return other.clone(),
+            _ => {}
+        }
+
+        if (self.is_zero() & other.is_zero()) == Bit::On {
+            return Self::zero(self.sign & other.sign);
+        }
+
+        // The larger magnitude goes on top, so that subtracting never takes
+        // the significand below 0
+        let (less, _, _) = self.magnitude().compare_unsigned(&other.magnitude());
+        let (top, bottom) = if less == Bit::On { (other, self) } else { (self, other) };
+        let (a, a_exponent) = top.unpack();
+        let (b, b_exponent) = bottom.unpack();
+
+        // Three more Bits at the bottom hold the guard, round and sticky Bits
+        // of the smaller significand once it's aligned, and anything shifted
+        // out past them is only sticky
+        let (a, b) = (&a << 3, &b << 3);
+        let distance = (&a_exponent - &b_exponent).to_int::<i32>().unwrap_or(i32::MAX);
+        let (mut b, guard, sticky) = shift_right(&b, distance as usize);
+
+        b.set(WIDTH - 1, b.get(WIDTH - 1) | guard | sticky);
+
+        let (sum, _) = if (top.sign ^ bottom.sign) == Bit::On {
+            a.sub_unsigned_using(&b, adder)
+        } else {
+            a.add_unsigned_using(&b, adder)
+        };
+
+        // Cancelling out exactly gives +0 when rounding to nearest
+        if sum.any() == Bit::Off {
+            return Self::zero(Bit::Off);
+        }
+
+        Self::round(top.sign, sum, &a_exponent - &Exponent::from_int(3), Bit::Off)
+    }
+
+    /// Multiplies the significands and adds the exponents
+    fn product(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, usize) {
+        let sign = self.sign ^ other.sign;
+        let infinite = self.is_infinite() | other.is_infinite();
+        let zero = self.is_zero() | other.is_zero();
+
+        if (self.is_nan() | other.is_nan() | (infinite & zero)) == Bit::On {
+            return (Self::nan(), 0);
+        }
+
+        if infinite == Bit::On {
+            return (Self::infinity(sign), 0);
+        }
+
+        if zero == Bit::On {
+            return (Self::zero(sign), 0);
+        }
+
+        let (a, a_exponent) = self.unpack();
+        let (b, b_exponent) = other.unpack();
+        let (product, additions) = multiplier.multiply(&low_bits(&a, M + 1), &low_bits(&b, M + 1));
+
+        let product = Self::round(sign, from_low_bits(&product), &a_exponent + &b_exponent, Bit::Off);
+
+        (product, additions)
+    }
+
+    /// Divides the significands and subtracts the exponents
+    fn quotient(&self, other: &Self, divider: &dyn Divider) -> Self {
+        let sign = self.sign ^ other.sign;
+
+        if (self.is_nan() | other.is_nan()) == Bit::On {
+            return Self::nan();
+        }
+
+        match (self.is_infinite(), other.is_infinite()) {
+            (Bit::On, Bit::On) => return Self::nan(),
+            (Bit::On, _) => return Self::infinity(sign),
+            (_, Bit::On) => return Self::zero(sign),
+            _ => {}
+        }
+
+        match (self.is_zero(), other.is_zero()) {
+            (Bit::On, Bit::On) => return Self::nan(),
+            (Bit::On, _) => return Self::zero(sign),
+            (_, Bit::On) => return Self::infinity(sign),
+            _ => {}
+        }
+
+        let (a, a_exponent) = self.unpack();
+        let (b, b_exponent) = other.unpack();
+        let (a, a_exponent) = Self::normalize(a, a_exponent);
+        let (b, b_exponent) = Self::normalize(b, b_exponent);
+
+        // With both leading ones lined up, scaling the dividend by M + 3
+        // Bits leaves at least that many in the quotient, and the remainder
+        // only decides the sticky Bit
+        let width = 2 * M + 4;
+        let dividend = low_bits(&(&a << (M + 3)), width);
+        let (quotient, remainder) = divider.divide(&dividend, &low_bits(&b, width));
+        let sticky = remainder.iter().fold(Bit::Off, |acc, &bit| acc | bit);
+
+        let exponent = &(&a_exponent - &b_exponent) - &Exponent::from_int((M + 3) as i32);
+
+        Self::round(sign, from_low_bits(&quotient), exponent, sticky)
+    }
+
+    /// Remainder of dividing with the quotient truncated, like `%` on `f64`,
+    /// which is always exact
+    ///
+    /// The exponents can be too far apart to line the significands up in a
+    /// register, so the partial remainder is worked out one Bit at a time as
+    /// in a restoring divider, bringing down a 0 for every step the
+    /// dividend's exponent is above the divisor's.
+    fn remainder(&self, other: &Self) -> Self {
+        if (self.is_nan() | other.is_nan() | self.is_infinite() | other.is_zero()) == Bit::On {
+            return Self::nan();
+        }
+
+        let (less, _, _) = self.magnitude().compare_unsigned(&other.magnitude());
+
+        if (other.is_infinite() | self.is_zero() | less) == Bit::On {
+            return self.clone();
+        }
+
+        let (a, a_exponent) = self.unpack();
+        let (b, b_exponent) = other.unpack();
+        let distance = (&a_exponent - &b_exponent).to_int::<i32>().unwrap_or(0) as usize;
+
+        let brought_down = low_bits(&a, M + 1).into_iter().chain((0..distance).map(|_| Bit::Off));
+        let mut remainder = Significand::zero();
+
+        for bit in brought_down {
+            remainder = &remainder << 1;
+            remainder.set(WIDTH - 1, bit);
+
+            let (less, _, _) = remainder.compare_unsigned(&b);
+
+            if less == Bit::Off {
+                remainder = remainder.sub_unsigned_with_flags(&b).0;
+            }
+        }
+
+        Self::round(self.sign, remainder, b_exponent, Bit::Off)
+    }
+
+    /// Square root of the significand, scaled up so that the root has Bits
+    /// to spare, with an even exponent to halve
+    fn square_root(&self) -> Self {
+        if (self.is_nan() | self.is_zero()) == Bit::On {
+            return self.clone();
+        }
+
+        if self.sign == Bit::On {
+            return Self::nan();
+        }
+
+        if self.is_infinite() == Bit::On {
+            return self.clone();
+        }
+
+        let (a, exponent) = self.unpack();
+        let (a, exponent) = Self::normalize(a, exponent);
+
+        // An odd exponent moves a Bit into the significand instead
+        let (a, exponent) = if exponent.get(31) == Bit::On {
+            (&a << 1, &exponent - &Exponent::one())
+        } else {
+            (a, exponent)
+        };
+
+        let scale = 2 * (M + 3);
+        let (value, _) = BigBinary::from_binary(&a).shift_with_flags(&BigBinary::from_int(scale as i32), Shift::Left);
+        let root = value.sqrt();
+        let (_, exact, _) = (&root * &root).compare(&value);
+
+        let (exponent, _) = (&exponent - &Exponent::from_int(scale as i32))
+            .shift_with_flags(&Exponent::one(), Shift::ArithmeticRight);
+
+        Self::from_magnitude(Bit::Off, &root, exponent, !exact)
+    }
+
+    /// Derives the zero and negative flags, with every other flag off, since
+    /// results too small become zeros instead
+    fn flags(&self) -> Flags {
+        Flags {
+            carry: Bit::Off,
+            overflow: Bit::Off,
+            zero: self.is_zero(),
+            negative: self.sign & !self.is_nan(),
+            divide_by_zero: Bit::Off,
+        }
+    }
+
+    /// Pairs with the flags for the value
+    fn with_flags(self) -> (Self, Flags) {
+        let flags = self.flags();
+
+        (self, flags)
+    }
+
+    /// Pairs with the flags for the value, raising overflow for an infinity
+    /// that came from finite operands, which was too large to hold rather
+    /// than infinite to begin with
+    fn with_flags_of(self, operands: &[&Self]) -> (Self, Flags) {
+        let finite = operands.iter().fold(Bit::On, |acc, n| acc & !n.is_infinite() & !n.is_nan());
+        let flags = Flags {
+            overflow: self.is_infinite() & finite,
+            ..self.flags()
+        };
+
+        (self, flags)
+    }
+}
+
+impl Float<8, 23> {
+    /// Create a Float from an `f32`
+    pub fn from_f32(n: f32) -> Self {
+        Self::from_bits(n.to_bits())
+    }
+
+    /// Convert to an `f32`, which holds the same Bits
+    pub fn to_f32(&self) -> f32 {
+        f32::from_bits(self.to_bits().unwrap())
+    }
+}
+
+impl Float<11, 52> {
+    /// Create a Float from an `f64`
+    pub fn from_f64(n: f64) -> Self {
+        Self::from_bits(n.to_bits())
+    }
+
+    /// Convert to an `f64`, which holds the same Bits
+    pub fn to_f64(&self) -> f64 {
+        f64::from_bits(self.to_bits().unwrap())
+    }
+}
+
+impl fmt::Display for Float<8, 23> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.to_f32())
+    }
+}
+
+impl fmt::Display for Float<11, 52> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.to_f64())
+    }
+}
+
+impl<const E: usize, const M: usize> Number for Float<E, M> {
+    const SIGNED: bool = true;
+    const INTEGRAL: bool = false;
+
+    type Wide = Self;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        let sign = if n.is_negative() { Bit::On } else { Bit::Off };
+
+        Self::from_magnitude(sign, &n.magnitude(), Exponent::zero(), Bit::Off).with_flags()
+    }
+
+    fn from_decimal(d: &Decimal) -> (Self, Flags) {
+        Self::round_decimal(d).with_flags_of(&[])
+    }
+
+    fn sign(&self) -> Bit {
+        self.sign
+    }
+
+    /// Only whole numbers are odd or even, which `to_integer` finds
+    fn low_bit(&self) -> Bit {
+        Bit::Off
+    }
+
+    /// Shifts the significand by its exponent, as long as that doesn't drop
+    /// any on Bits below the point
+    fn to_integer(&self, _fraction: usize) -> Option<BigBinary> {
+        if (self.is_nan() | self.is_infinite()) == Bit::On {
+            return None;
+        }
+
+        let (significand, exponent) = self.unpack();
+        let exponent = exponent.to_int::<i32>().ok()?;
+        let significand = BigBinary::from_unsigned(&significand);
+
+        let magnitude = match exponent {
+            0.. => significand.shift_with_flags(&BigBinary::from_int(exponent), Shift::Left).0,
+            _ => significand.to_integer(exponent.unsigned_abs() as usize)?,
+        };
+
+        match self.sign {
+            Bit::On => Some(-&magnitude),
+            Bit::Off => Some(magnitude),
+        }
+    }
+
+    /// The largest finite value, which an infinity from overflow saturates
+    /// to
+    fn saturated(sign: Bit) -> Option<Self> {
+        let mut exponent = Binary::of(Bit::On);
+        exponent.set(E - 1, Bit::Off);
+
+        Some(Float {
+            sign,
+            exponent,
+            fraction: Binary::of(Bit::On),
+        })
+    }
+
+    /// Overflow is already infinity, so there's nothing to find again
+    fn widen(&self) -> Option<Self> {
+        None
+    }
+
+    fn narrow_saturating(_wide: &Self) -> Option<Self> {
+        None
+    }
+
+    fn neg_using(&self, _adder: &dyn Adder) -> (Self, Flags) {
+        self.negated().with_flags()
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.sum(other, adder).with_flags_of(&[self, other])
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.sum(&other.negated(), adder).with_flags_of(&[self, other])
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        let (product, additions) = self.product(other, multiplier);
+        let (product, flags) = product.with_flags_of(&[self, other]);
+
+        (product, flags, additions)
+    }
+
+    /// Dividing a finite value by 0 gives an infinity without overflowing
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        let quotient = self.quotient(other, divider);
+        let (quotient, flags) = match other.is_zero() {
+            Bit::On => quotient.with_flags(),
+            Bit::Off => quotient.with_flags_of(&[self, other]),
+        };
+
+        (quotient, self.remainder(other), flags)
+    }
+
+    /// The remainder is always exact, however large the quotient
+    fn rem_using(&self, other: &Self, _divider: &dyn Divider) -> (Self, Flags) {
+        self.remainder(other).with_flags()
+    }
+
+    // Floats have no circuits for the operations that need integers, which
+    // are turned away before getting here, or for powers, which are built
+    // from products instead, so these all give NaN
+
+    fn pow_with_flags(&self, _exp: &Self) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    fn and_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    fn or_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    fn xor_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    fn shift_with_flags(&self, _amount: &Self, _shift: Shift) -> (Self, Flags) {
+        Self::nan().with_flags()
+    }
+
+    /// Correctly rounded square root, rather than an integer one
+    fn sqrt(&self) -> Self {
+        self.square_root()
+    }
+
+    fn root(&self, _degree: &Self) -> Self {
+        Self::nan()
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        Self::from_literal(&BigBinary::from_bits(vec![Bit::Off, bit])).0
+    }
+
+    /// Compares as IEEE-754 does, where the zeros are equal and NaN is
+    /// neither less than, equal to nor greater than anything
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        if (self.is_nan() | other.is_nan()) == Bit::On {
+            return (Bit::Off, Bit::Off, Bit::Off);
+        }
+
+        if (self.is_zero() & other.is_zero()) == Bit::On {
+            return (Bit::Off, Bit::On, Bit::Off);
+        }
+
+        let (less, equal, greater) = self.magnitude().compare_unsigned(&other.magnitude());
+
+        match (self.sign, other.sign) {
+            (Bit::Off, Bit::Off) => (less, equal, greater),
+            (Bit::On, Bit::On) => (greater, equal, less),
+            (Bit::Off, Bit::On) => (Bit::Off, Bit::Off, Bit::On),
+            (Bit::On, Bit::Off) => (Bit::On, Bit::Off, Bit::Off),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Decimal, Number, F32, F64};
+    use crate::types::{BigBinary, DividerKind, MultiplierKind};
+    use crate::types::Bit::{Off, On};
+
+    const VALUES: [f64; 22] = [
+        0.0,
+        -0.0,
+        1.0,
+        -1.5,
+        0.1,
+        0.2,
+        0.3,
+        1.0 / 3.0,
+        -2.0 / 3.0,
+        1e15 + 0.3,
+        123_456_789.123_456_78,
+        9_007_199_254_740_993.0,
+        1e300,
+        -1e-300,
+        f64::MAX,
+        f64::MIN_POSITIVE,
+        f64::MIN_POSITIVE * 0.75,
+        -5e-324,
+        1.000_000_000_000_000_2,
+        f64::EPSILON,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+    ];
+
+    /// Asserts the Bits match, or that both are NaN, whose Bits the hardware
+    /// is free to pick
+    fn assert_same(actual: &F64, expected: f64, message: &str) {
+        if expected.is_nan() {
+            assert!(actual.to_f64().is_nan(), "{}: {:?}", message, actual.to_f64());
+        } else {
+            assert_eq!(actual.to_f64().to_bits(), expected.to_bits(), "{}: {:?} vs {:?}", message, actual.to_f64(), expected);
+        }
+    }
+
+    #[test]
+    fn test_bits_round_trip() {
+        for &x in VALUES.iter().chain([f64::NAN].iter()) {
+            assert_eq!(F64::from_f64(x).to_f64().to_bits(), x.to_bits());
+        }
+
+        assert_eq!(F32::from_f32(-0.1).to_f32(), -0.1);
+        assert!(F64::nan().to_f64().is_nan());
+    }
+
+    #[test]
+    fn test_arithmetic_matches_f64() {
+        for &x in VALUES.iter() {
+            for &y in VALUES.iter() {
+                let (a, b) = (F64::from_f64(x), F64::from_f64(y));
+                let message = format!("{:?} and {:?}", x, y);
+
+                assert_same(&a.add_with_flags(&b).0, x + y, &format!("sum of {}", message));
+                assert_same(&a.sub_with_flags(&b).0, x - y, &format!("difference of {}", message));
+                assert_same(&a.mul_with_flags(&b).0, x * y, &format!("product of {}", message));
+
+                let (quotient, remainder, _) = a.div_rem_with_flags(&b);
+
+                assert_same(&quotient, x / y, &format!("quotient of {}", message));
+                assert_same(&remainder, x % y, &format!("remainder of {}", message));
+            }
+        }
+    }
+
+    #[test]
+    fn test_rounding_ties_to_even() {
+        let (one, half_ulp) = (F64::from_f64(1.0), F64::from_f64(f64::EPSILON / 2.0));
+        let odd = F64::from_f64(1.0 + f64::EPSILON);
+
+        // Exactly halfway goes to whichever neighbour is even
+        assert_same(&one.add_with_flags(&half_ulp).0, 1.0, "1 + half an ulp");
+        assert_same(&odd.add_with_flags(&half_ulp).0, 1.0 + 2.0 * f64::EPSILON, "odd + half an ulp");
+
+        // Overflowing rounds to infinity, and underflowing to subnormals
+        let max = F64::from_f64(f64::MAX);
+        assert_same(&max.add_with_flags(&max).0, f64::INFINITY, "max + max");
+
+        let tiny = F64::from_f64(f64::MIN_POSITIVE);
+        assert_same(&tiny.mul_with_flags(&F64::from_f64(0.5)).0, f64::MIN_POSITIVE * 0.5, "tiny * 0.5");
+        assert_same(&F64::from_f64(5e-324).mul_with_flags(&F64::from_f64(0.5)).0, 0.0, "least / 2");
+    }
+
+    #[test]
+    fn test_circuits_agree() {
+        let (a, b) = (F64::from_f64(1.0 / 3.0), F64::from_f64(-7.25e-3));
+
+        for multiplier in [MultiplierKind::BoothRadix4, MultiplierKind::Wallace].iter() {
+            assert_same(&a.mul_using(&b, multiplier).0, (1.0 / 3.0) * -7.25e-3, "product");
+        }
+
+        for divider in [DividerKind::NonRestoring, DividerKind::Srt].iter() {
+            assert_same(&a.div_rem_using(&b, divider).0, (1.0 / 3.0) / -7.25e-3, "quotient");
+        }
+    }
+
+    #[test]
+    fn test_sqrt_and_compare() {
+        for &x in [0.0, 2.0, 1e-310, 0.5, 1e300, f64::MAX, 12345.678].iter() {
+            assert_same(&F64::from_f64(x).sqrt(), x.sqrt(), "square root");
+        }
+
+        let (nan, zero, negative_zero) = (F64::nan(), F64::from_f64(0.0), F64::from_f64(-0.0));
+
+        assert_eq!(nan.compare(&nan), (Off, Off, Off));
+        assert_eq!(zero.compare(&negative_zero), (Off, On, Off));
+        assert_eq!(F64::from_f64(-2.0).compare(&F64::from_f64(-1.0)), (On, Off, Off));
+        assert_eq!(F64::from_f64(-2.0).compare(&F64::from_f64(1e-300)), (On, Off, Off));
+    }
+
+    #[test]
+    fn test_literals() {
+        let decimal = |whole, fraction, exponent| F64::from_decimal(&Decimal::from_parts(whole, fraction, exponent).unwrap()).0;
+
+        for &s in ["1.5e-3", "0.1", "2.5e-324", "2.4703282292062328e-324", "1e309", "1.7976931348623157e308", "123456789012345678901234567890", "4.9406564584124654e-324", "1e-400"].iter() {
+            let (mantissa, exponent) = s.split_at(s.find('e').unwrap_or(s.len()));
+            let (whole, fraction) = mantissa.split_at(mantissa.find('.').unwrap_or(mantissa.len()));
+            let (fraction, exponent) = (fraction.trim_start_matches('.'), exponent.trim_start_matches('e'));
+
+            assert_same(&decimal(whole, fraction, exponent), s.parse().unwrap(), s);
+        }
+
+        let (integer, _) = F64::from_literal(&BigBinary::from_int(i64::MAX));
+        assert_same(&integer, i64::MAX as f64, "integer literal");
+
+        assert_eq!(F64::from_f64(-1.5e3).to_integer(0), Some(BigBinary::from_int(-1500)));
+        assert_eq!(F64::from_f64(2.5).to_integer(0), None);
+        assert_eq!(F64::from_f64(f64::INFINITY).to_integer(0), None);
+        assert_eq!(F64::from_f64(2f64.powi(100)).to_integer(0), Some(BigBinary::from_int(1i128 << 100)));
+    }
+
+    #[test]
+    fn test_binary32() {
+        let values = [0.1f32, -3.75, 1e-40, f32::MAX, 7.0, 1e-3];
+
+        for &x in values.iter() {
+            for &y in values.iter() {
+                let (a, b) = (F32::from_f32(x), F32::from_f32(y));
+
+                assert_eq!(a.add_with_flags(&b).0.to_f32().to_bits(), (x + y).to_bits(), "{} + {}", x, y);
+                assert_eq!(a.mul_with_flags(&b).0.to_f32().to_bits(), (x * y).to_bits(), "{} * {}", x, y);
+                assert_eq!(a.div_rem_with_flags(&b).0.to_f32().to_bits(), (x / y).to_bits(), "{} / {}", x, y);
+            }
+        }
+    }
+}
@@ -1,8 +1,82 @@
+pub mod adder;
+pub mod big_binary;
 pub mod binary;
 pub mod bit;
+pub mod complex;
+pub mod decimal;
+pub mod divider;
+pub mod fixed;
+pub mod flags;
+pub mod float;
+pub mod modular;
+pub mod multiplier;
+pub mod number;
+pub mod rational;
+pub mod unsigned;
+pub mod value;
 
-pub use self::binary::Binary;
+pub use self::adder::{Adder, AdderKind};
+pub use self::big_binary::BigBinary;
+pub use self::binary::{Binary, Int, Shift, Uint};
 pub use self::bit::Bit;
+pub use self::complex::Complex;
+pub use self::decimal::Decimal;
+pub use self::divider::{Divider, DividerKind};
+pub use self::fixed::Fixed;
+pub use self::flags::Flags;
+pub use self::float::{Float, F32, F64};
+pub use self::modular::Modular;
+pub use self::multiplier::{Multiplier, MultiplierKind};
+pub use self::number::Number;
+pub use self::rational::Rational;
+pub use self::unsigned::Unsigned;
+pub use self::value::Value;
 
 pub type Operation = fn(i64, i64) -> ParseResult;
 pub type ParseResult = Result<i64, ::std::num::ParseIntError>;
+
+/// Helpers shared by the tests of the adder, multiplier and divider circuits,
+/// which all work on Bits ordered from most to least significant
+#[cfg(test)]
+mod circuits {
+    use super::Bit::{self, Off, On};
+    use super::{AdderKind, DividerKind, MultiplierKind};
+
+    /// The lowest Bits of a number, as many as the width
+    pub fn bits(n: u128, width: usize) -> Vec<Bit> {
+        (0..width)
+            .rev()
+            .map(|i| if n >> i & 1 == 1 { On } else { Off })
+            .collect()
+    }
+
+    /// The number that Bits stand for when read as unsigned
+    pub fn value(bits: &[Bit]) -> u128 {
+        bits.iter().fold(0, |acc, &b| acc * 2 + if b == On { 1 } else { 0 })
+    }
+
+    /// Runs the check on every pair of 8-bit operands, given both as numbers
+    /// and as Bits
+    pub fn for_8_bit_pairs<F: FnMut(u128, u128, &[Bit], &[Bit])>(mut check: F) {
+        for x in 0..=255u128 {
+            for y in 0..=255u128 {
+                check(x, y, &bits(x, 8), &bits(y, 8));
+            }
+        }
+    }
+
+    #[test]
+    fn test_kinds_from_str() {
+        assert_eq!("kogge-stone".parse(), Ok(AdderKind::KoggeStone));
+        assert_eq!("select".parse(), Ok(AdderKind::CarrySelect));
+        assert!("brent-kung".parse::<AdderKind>().is_err());
+
+        assert_eq!("booth4".parse(), Ok(MultiplierKind::BoothRadix4));
+        assert_eq!("wallace".parse(), Ok(MultiplierKind::Wallace));
+        assert!("dadda".parse::<MultiplierKind>().is_err());
+
+        assert_eq!("non-restoring".parse(), Ok(DividerKind::NonRestoring));
+        assert_eq!("srt".parse(), Ok(DividerKind::Srt));
+        assert!("goldschmidt".parse::<DividerKind>().is_err());
+    }
+}
@@ -0,0 +1,258 @@
+use super::adder::Adder;
+use super::divider::Divider;
+use super::multiplier::Multiplier;
+use super::{Bit, Flags, Number, Shift};
+
+/// Arithmetic modulo a value of at least 2, where every result is brought
+/// back into `[0, modulus)` by the divider circuit, and sums and differences
+/// are arranged so they never leave the register on the way there.
+pub struct Modular<'a, T> {
+    pub modulus: T,
+    pub adder: &'a dyn Adder,
+    pub multiplier: &'a dyn Multiplier,
+    pub divider: &'a dyn Divider,
+}
+
+fn is_zero<T: Number>(n: &T) -> Bit {
+    n.compare(&T::from_bit(Bit::Off)).1
+}
+
+impl<'a, T: Number> Modular<'a, T> {
+    /// The remainder of the division by the modulus, moved up by the
+    /// modulus if the divider left it below zero
+    pub fn reduce(&self, n: &T) -> T {
+        let (_, remainder, _) = n.div_rem_using(&self.modulus, self.divider);
+
+        match remainder.sign() {
+            Bit::On => remainder.add_using(&self.modulus, self.adder).0,
+            Bit::Off => remainder,
+        }
+    }
+
+    /// Adds two reduced values as `a - (modulus - b)` whenever the plain sum
+    /// would reach the modulus, which it may not fit below
+    pub fn add(&self, a: &T, b: &T) -> T {
+        let gap = self.modulus.sub_using(b, self.adder).0;
+
+        match a.compare(&gap).0 {
+            Bit::On => a.add_using(b, self.adder).0,
+            Bit::Off => a.sub_using(&gap, self.adder).0,
+        }
+    }
+
+    /// Subtracts two reduced values as `modulus - (b - a)` whenever the
+    /// plain difference would go below zero
+    pub fn sub(&self, a: &T, b: &T) -> T {
+        match a.compare(b).0 {
+            Bit::On => self.modulus.sub_using(&b.sub_using(a, self.adder).0, self.adder).0,
+            Bit::Off => a.sub_using(b, self.adder).0,
+        }
+    }
+
+    /// Negates a reduced value, which leaves 0 as it is
+    pub fn neg(&self, n: &T) -> T {
+        self.sub(&T::from_bit(Bit::Off), n)
+    }
+
+    /// Multiplies two reduced values in a register twice as wide, which
+    /// always holds the product, so that the divider reduces all of it back
+    /// below the modulus. Representations without a wider form multiply in
+    /// their own register instead, raising overflow if the product doesn't
+    /// fit there. Also returns how many partial products the multiplier added
+    pub fn mul(&self, a: &T, b: &T) -> (T, Flags, usize) {
+        if let Some(product) = self.mul_wide(a, b) {
+            return product;
+        }
+
+        let (product, flags, additions) = a.mul_using(b, self.multiplier);
+        (self.reduce(&product), flags, additions)
+    }
+
+    fn mul_wide(&self, a: &T, b: &T) -> Option<(T, Flags, usize)> {
+        let wide = Modular {
+            modulus: self.modulus.widen()?,
+            adder: self.adder,
+            multiplier: self.multiplier,
+            divider: self.divider,
+        };
+
+        let (product, _, additions) = a.widen()?.mul_using(&b.widen()?, self.multiplier);
+        let reduced = T::narrow_saturating(&wide.reduce(&product))?;
+        let flags = Flags::from_condition(!is_zero(&reduced));
+
+        Some((reduced, flags, additions))
+    }
+
+    /// The multiplicative inverse by the extended Euclidean algorithm, which
+    /// carries the Bézout coefficient of the value along with each remainder,
+    /// keeping it reduced so it works without a sign, and raises overflow if
+    /// any product on the way did. Returns None when the value shares a
+    /// factor with the modulus, 0 included
+    pub fn inverse(&self, n: &T) -> Option<(T, Flags)> {
+        let (mut r0, mut r1) = (self.modulus.clone(), self.reduce(n));
+        let (mut t0, mut t1) = (T::from_bit(Bit::Off), T::from_bit(Bit::On));
+        let mut overflow = Bit::Off;
+
+        while is_zero(&r1) == Bit::Off {
+            let (quotient, remainder, _) = r0.div_rem_using(&r1, self.divider);
+            let (step, raised, _) = self.mul(&quotient, &t1);
+            let t2 = self.sub(&t0, &step);
+
+            r0 = std::mem::replace(&mut r1, remainder);
+            t0 = std::mem::replace(&mut t1, t2);
+            overflow = overflow | raised.overflow;
+        }
+
+        if r0.compare(&T::from_bit(Bit::On)).1 == Bit::Off {
+            return None;
+        }
+
+        let flags = Flags {
+            carry: overflow,
+            overflow,
+            zero: is_zero(&t0),
+            negative: Bit::Off,
+            divide_by_zero: Bit::Off,
+        };
+
+        Some((t0, flags))
+    }
+
+    /// Raises a value to a power by squaring and multiplying, one Bit of the
+    /// exponent at a time from the bottom, where a negative exponent raises
+    /// the inverse instead. Returns None when that inverse doesn't exist, and
+    /// otherwise raises overflow if any product doesn't fit in the register
+    pub fn pow(&self, base: &T, exp: &T) -> Option<(T, Flags, usize)> {
+        let one = T::from_bit(Bit::On);
+        let (mut base, mut exp, mut overflow) = match exp.sign() {
+            Bit::On => {
+                let (inverse, flags) = self.inverse(base)?;
+                (inverse, exp.neg_using(self.adder).0, flags.overflow)
+            }
+            Bit::Off => (self.reduce(base), exp.clone(), Bit::Off),
+        };
+
+        let mut result = self.reduce(&one);
+        let mut additions = 0;
+
+        while is_zero(&exp) == Bit::Off {
+            if exp.low_bit() == Bit::On {
+                let (product, raised, added) = self.mul(&result, &base);
+                result = product;
+                overflow = overflow | raised.overflow;
+                additions += added;
+            }
+
+            exp = exp.shift_with_flags(&one, Shift::LogicalRight).0;
+
+            if is_zero(&exp) == Bit::Off {
+                let (square, raised, added) = self.mul(&base, &base);
+                base = square;
+                overflow = overflow | raised.overflow;
+                additions += added;
+            }
+        }
+
+        let flags = Flags {
+            carry: overflow,
+            overflow,
+            zero: is_zero(&result),
+            negative: Bit::Off,
+            divide_by_zero: Bit::Off,
+        };
+
+        Some((result, flags, additions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Modular;
+    use crate::types::{AdderKind, Binary, DividerKind, MultiplierKind, Number, Unsigned};
+
+    fn modular<T: Number>(modulus: i64) -> Modular<'static, T> {
+        Modular {
+            modulus: T::from_literal(&crate::types::BigBinary::from_int(modulus)).0,
+            adder: &AdderKind::RippleCarry,
+            multiplier: &MultiplierKind::Array,
+            divider: &DividerKind::Restoring,
+        }
+    }
+
+    #[test]
+    fn test_reduce() {
+        let m = modular::<Binary<16>>(7);
+
+        for n in -50..50i16 {
+            assert_eq!(m.reduce(&Binary::from_int(n)).to_int::<i16>(), Ok(n.rem_euclid(7)));
+        }
+    }
+
+    #[test]
+    fn test_add_sub_near_limit() {
+        // Sums of values below 250 don't fit in 8 unsigned Bits
+        let m = modular::<Unsigned<Binary<8>>>(250);
+        let n = |n: u8| Unsigned(Binary::<8>::from_uint(n));
+
+        for a in (0..250u16).step_by(7) {
+            for b in (0..250u16).step_by(11) {
+                let (x, y) = (n(a as u8), n(b as u8));
+
+                assert_eq!(m.add(&x, &y).0.to_uint::<u8>(), Ok(((a + b) % 250) as u8));
+                assert_eq!(m.sub(&x, &y).0.to_uint::<u8>(), Ok(((a + 250 - b) % 250) as u8));
+            }
+        }
+    }
+
+    #[test]
+    fn test_mul_near_limit() {
+        // Products of values below 251 don't fit in 8 unsigned Bits
+        let m = modular::<Unsigned<Binary<8>>>(251);
+        let n = |n: u8| Unsigned(Binary::<8>::from_uint(n));
+
+        for a in (0..251u32).step_by(5) {
+            for b in (0..251u32).step_by(13) {
+                let (product, flags, _) = m.mul(&n(a as u8), &n(b as u8));
+
+                assert_eq!(product.0.to_uint::<u8>(), Ok((a * b % 251) as u8));
+                assert_eq!(flags.overflow, crate::types::Bit::Off);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inverse() {
+        let m = modular::<Binary<32>>(65537);
+
+        for n in [1, 2, 3, 1000, 65536] {
+            let inverse = m.inverse(&Binary::from_int(n)).unwrap().0.to_int::<i64>().unwrap();
+            assert_eq!(n * inverse % 65537, 1);
+        }
+
+        let m = modular::<Unsigned<Binary<8>>>(12);
+        assert!(m.inverse(&Unsigned(Binary::from_uint(8u8))).is_none());
+        assert!(m.inverse(&Unsigned(Binary::from_uint(0u8))).is_none());
+        let (inverse, _) = m.inverse(&Unsigned(Binary::from_uint(5u8))).unwrap();
+        assert_eq!(inverse.0.to_uint::<u8>(), Ok(5));
+    }
+
+    #[test]
+    fn test_pow() {
+        let m = modular::<Binary<64>>(65537);
+        let pow = |base: i64, exp: i64| {
+            let (result, _, _) = m.pow(&Binary::from_int(base), &Binary::from_int(exp)).unwrap();
+            result.to_int::<i64>().unwrap()
+        };
+
+        let mut expected = 1;
+        for _ in 0..1000 {
+            expected = expected * 3 % 65537;
+        }
+
+        assert_eq!(pow(3, 1000), expected);
+        assert_eq!(pow(3, 0), 1);
+        assert_eq!(pow(-2, 3), 65537 - 8);
+        assert_eq!(pow(3, -1) * 3 % 65537, 1);
+        assert!(m.pow(&Binary::from_int(0), &Binary::from_int(-1)).is_none());
+    }
+}
@@ -0,0 +1,318 @@
+use std::str::FromStr;
+
+use super::adder::{Adder, RippleCarry};
+use super::Bit;
+
+/// Multiplier: A circuit that multiplies two equally wide sequences of Bits
+/// as unsigned values, ordered from most to least significant like Binary
+pub trait Multiplier {
+    /// Multiplies, returning the full product at twice the width along with
+    /// the number of partial products that had to be added to produce it
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize);
+}
+
+/// Shifts the multiplicand once per Bit of the multiplier and adds every
+/// one of those partial products into an accumulator
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Array;
+
+/// Recodes the multiplier into digits of -1, 0 and 1, so that a run of on
+/// Bits costs one subtraction and one addition instead of an addition each
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoothRadix2;
+
+/// Recodes overlapping groups of three multiplier Bits into digits from -2
+/// to 2, which halves the number of partial products
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BoothRadix4;
+
+/// Reduces every partial product at once with layers of carry-save adders,
+/// three rows into two, leaving a single addition at the end
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Wallace;
+
+/// The multiplier a calculation is run with, picked at runtime
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MultiplierKind {
+    Array,
+    BoothRadix2,
+    BoothRadix4,
+    Wallace,
+}
+
+/// Zero-extends the Bits to the given width after shifting them left
+fn place(bits: &[Bit], shift: usize, width: usize) -> Vec<Bit> {
+    let mut placed = vec![Bit::Off; width];
+
+    for (i, &bit) in bits.iter().rev().enumerate() {
+        if i + shift < width {
+            placed[width - 1 - i - shift] = bit;
+        }
+    }
+
+    placed
+}
+
+/// Returns the Bit at the given weight of a sequence, or Off beyond it
+fn weight(bits: &[Bit], i: isize) -> Bit {
+    if i < 0 || i as usize >= bits.len() {
+        Bit::Off
+    } else {
+        bits[bits.len() - 1 - i as usize]
+    }
+}
+
+/// Keeps the least significant half of the Bits, where a product of
+/// unsigned values always fits
+fn low_half(bits: &[Bit], width: usize) -> Vec<Bit> {
+    bits[bits.len() - width..].to_vec()
+}
+
+impl Multiplier for Array {
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize) {
+        let width = a.len() * 2;
+        let mut accumulator = vec![Bit::Off; width];
+
+        // For each Bit in the multiplier, starting at least significant, the
+        // multiplicand passes through a row of AND gates and moves one more
+        // position to the left...
+        for i in 0..b.len() {
+            let multiplier = weight(b, i as isize);
+            let partial: Vec<_> = a.iter().map(|&bit| Bit::multiplier(bit, multiplier)).collect();
+
+            // ... before being added in, whether or not any Bit is on
+            accumulator = RippleCarry.add(&accumulator, &place(&partial, i, width), Bit::Off).0;
+        }
+
+        (accumulator, b.len())
+    }
+}
+
+impl Multiplier for BoothRadix2 {
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize) {
+        // Booth digits are signed, so the accumulator needs room for a sign
+        // above the product
+        let width = a.len() * 2 + 2;
+        let multiplicand = place(a, 0, width);
+        let mut accumulator = vec![Bit::Off; width];
+        let mut additions = 0;
+
+        // An extra Off Bit on top keeps the unsigned multiplier positive
+        for i in 0..=b.len() as isize {
+            let (current, previous) = (weight(b, i), weight(b, i - 1));
+
+            // The start of a run of on Bits subtracts, and the end adds
+            let add = !current & previous;
+            let subtract = current & !previous;
+
+            if (add | subtract) == Bit::Off {
+                continue;
+            }
+
+            // Subtracting adds the complement with an initial carry
+            let partial: Vec<_> = place(&multiplicand, i as usize, width)
+                .into_iter()
+                .map(|bit| bit ^ subtract)
+                .collect();
+
+            accumulator = RippleCarry.add(&accumulator, &partial, subtract).0;
+            additions += 1;
+        }
+
+        (low_half(&accumulator, a.len() * 2), additions)
+    }
+}
+
+impl Multiplier for BoothRadix4 {
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize) {
+        let width = a.len() * 2 + 4;
+        let multiplicand = place(a, 0, width);
+        let mut accumulator = vec![Bit::Off; width];
+        let mut additions = 0;
+
+        // Groups overlap by one Bit and must reach past the top of the
+        // multiplier, so that the last digit sees it as positive
+        for i in (0..=b.len() as isize).step_by(2) {
+            let (high, mid, low) = (weight(b, i + 1), weight(b, i), weight(b, i - 1));
+
+            // The digit is -2 * high + mid + low
+            let one = mid ^ low;
+            let two = (high & !mid & !low) | (!high & mid & low);
+            let negative = high & !(mid & low);
+
+            if (one | two) == Bit::Off {
+                continue;
+            }
+
+            let single = place(&multiplicand, i as usize, width);
+            let double = place(&multiplicand, i as usize + 1, width);
+
+            let partial: Vec<_> = single
+                .iter()
+                .zip(double.iter())
+                .map(|(&s, &d)| Bit::multiplexer(two, s, d) ^ negative)
+                .collect();
+
+            accumulator = RippleCarry.add(&accumulator, &partial, negative).0;
+            additions += 1;
+        }
+
+        (low_half(&accumulator, a.len() * 2), additions)
+    }
+}
+
+impl Multiplier for Wallace {
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize) {
+        let width = a.len() * 2;
+        let mut additions = 0;
+
+        // Every partial product is formed at once by its own row of AND gates
+        let mut rows: Vec<Vec<Bit>> = (0..b.len())
+            .map(|i| {
+                let multiplier = weight(b, i as isize);
+                let partial: Vec<_> = a.iter().map(|&bit| Bit::multiplier(bit, multiplier)).collect();
+
+                place(&partial, i, width)
+            })
+            .collect();
+
+        // Each layer passes every three rows through a row of full adders,
+        // keeping the sums and carries apart as two rows instead of waiting
+        // on the carries to ripple
+        while rows.len() > 2 {
+            let mut reduced = Vec::with_capacity(rows.len());
+
+            for group in rows.chunks(3) {
+                if group.len() < 3 {
+                    reduced.extend_from_slice(group);
+                    continue;
+                }
+
+                let mut sums = vec![Bit::Off; width];
+                let mut carries = vec![Bit::Off; width];
+
+                for j in 0..width {
+                    let (sum, carry) = Bit::full_adder(group[0][j], group[1][j], group[2][j]);
+
+                    sums[j] = sum;
+
+                    // Carries count one position further left, and any off
+                    // the top would have been beyond the full product
+                    if j > 0 {
+                        carries[j - 1] = carry;
+                    }
+                }
+
+                reduced.push(sums);
+                reduced.push(carries);
+                additions += 1;
+            }
+
+            rows = reduced;
+        }
+
+        // The last two rows need one ordinary addition
+        let product = match rows.len() {
+            0 => vec![Bit::Off; width],
+            1 => rows.remove(0),
+            _ => {
+                additions += 1;
+                RippleCarry.add(&rows[0], &rows[1], Bit::Off).0
+            }
+        };
+
+        (product, additions)
+    }
+}
+
+impl Multiplier for MultiplierKind {
+    fn multiply(&self, a: &[Bit], b: &[Bit]) -> (Vec<Bit>, usize) {
+        match self {
+            MultiplierKind::Array => Array.multiply(a, b),
+            MultiplierKind::BoothRadix2 => BoothRadix2.multiply(a, b),
+            MultiplierKind::BoothRadix4 => BoothRadix4.multiply(a, b),
+            MultiplierKind::Wallace => Wallace.multiply(a, b),
+        }
+    }
+}
+
+impl FromStr for MultiplierKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        use self::MultiplierKind::*;
+
+        Ok(match s {
+            "array" => Array,
+            "booth2" => BoothRadix2,
+            "booth4" => BoothRadix4,
+            "wallace" => Wallace,
+            _ => return Err(format!("Unknown multiplier '{}'", s)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Multiplier, MultiplierKind};
+    use crate::types::circuits::{bits, for_8_bit_pairs, value};
+
+    const MULTIPLIERS: [MultiplierKind; 4] = [
+        MultiplierKind::Array,
+        MultiplierKind::BoothRadix2,
+        MultiplierKind::BoothRadix4,
+        MultiplierKind::Wallace,
+    ];
+
+    #[test]
+    fn test_8_bit_agreement() {
+        for multiplier in MULTIPLIERS.iter() {
+            for_8_bit_pairs(|x, y, a, b| {
+                let (product, _) = multiplier.multiply(a, b);
+
+                assert_eq!(product.len(), 16);
+                assert_eq!(value(&product), x * y, "{:?}: {} * {}", multiplier, x, y);
+            });
+        }
+    }
+
+    #[test]
+    fn test_wide_agreement() {
+        let operands = [
+            (0, 0),
+            (u64::MAX as u128, u64::MAX as u128),
+            (0x5555_5555_5555_5555, 0xaaaa_aaaa_aaaa_aaaa),
+            (12_345_678_901_234, 98_765_432_109),
+            (0x8000_0000_0000_0000, 3),
+        ];
+
+        for multiplier in MULTIPLIERS.iter() {
+            for (x, y) in operands.iter() {
+                let (product, _) = multiplier.multiply(&bits(*x, 64), &bits(*y, 64));
+
+                assert_eq!(value(&product), x * y, "{:?}: {} * {}", multiplier, x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_additions() {
+        let additions = |multiplier: MultiplierKind, x, y| multiplier.multiply(&bits(x, 8), &bits(y, 8)).1;
+
+        // The array adds a partial product for every multiplier Bit
+        assert_eq!(additions(MultiplierKind::Array, 3, 0), 8);
+        assert_eq!(additions(MultiplierKind::Array, 3, 255), 8);
+
+        // Booth only pays at the edges of runs of on Bits...
+        assert_eq!(additions(MultiplierKind::BoothRadix2, 3, 0), 0);
+        assert_eq!(additions(MultiplierKind::BoothRadix2, 3, 0b0111_1110), 2);
+        assert_eq!(additions(MultiplierKind::BoothRadix2, 3, 0b0101_0101), 8);
+
+        // ... and radix 4 never needs more than one per pair of Bits
+        assert_eq!(additions(MultiplierKind::BoothRadix4, 3, 0b0101_0101), 4);
+        assert!((0..=255).all(|y| additions(MultiplierKind::BoothRadix4, 3, y) <= 5));
+
+        // The tree always reduces all eight rows down to two and adds them
+        assert_eq!(additions(MultiplierKind::Wallace, 3, 0), 7);
+    }
+}
@@ -0,0 +1,199 @@
+use super::adder::{Adder, RippleCarry};
+use super::divider::{Divider, Restoring};
+use super::multiplier::{Array, Multiplier};
+use super::{BigBinary, Bit, Decimal, Flags, Shift};
+
+/// Number: A representation that expressions can be evaluated with, where
+/// every operation is a circuit reporting its status through Flags
+pub trait Number: Clone + Sized {
+    /// Whether the top Bit is read as a sign, rather than as part of the value
+    const SIGNED: bool;
+
+    /// Whether every value is a whole number, which bitwise operations,
+    /// shifts, powers and roots of any degree need
+    const INTEGRAL: bool = true;
+
+    /// The same kind of value without a limit on its size, which an
+    /// overflowed operation is run again with to find what it should have
+    /// been
+    type Wide: Number;
+
+    /// Loads a literal, raising overflow if it doesn't fit
+    fn from_literal(n: &BigBinary) -> (Self, Flags);
+
+    /// Loads a decimal literal, which integers only keep the whole part of,
+    /// raising overflow if that doesn't fit
+    fn from_decimal(d: &Decimal) -> (Self, Flags) {
+        match d.truncate() {
+            Some(n) => Self::from_literal(&n),
+            None => overflowed(),
+        }
+    }
+
+    /// Loads a decimal literal as a fixed-point value with the given number
+    /// of fraction Bits, rounded to the nearest one, raising overflow if
+    /// that doesn't fit
+    fn from_fixed_decimal(d: &Decimal, fraction: usize) -> (Self, Flags) {
+        match d.to_fixed(fraction) {
+            Some(n) => Self::from_literal(&n),
+            None => overflowed(),
+        }
+    }
+
+    /// Returns the sign Bit, which is always off for unsigned numbers
+    fn sign(&self) -> Bit;
+
+    /// Returns the least significant Bit, ie. whether the number is odd
+    fn low_bit(&self) -> Bit;
+
+    /// The value as a whole number, reading the lowest `fraction` Bits as
+    /// those of a fixed-point fraction, or None if it isn't one
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary>;
+
+    /// Returns the largest value if the sign is off or the smallest if on,
+    /// or None for representations without a limit to saturate at
+    fn saturated(sign: Bit) -> Option<Self>;
+
+    /// The same value without a limit on its size, or None for
+    /// representations that can't give one
+    fn widen(&self) -> Option<Self::Wide>;
+
+    /// Loads a value that was found without a limit on its size, saturating
+    /// where it doesn't fit, or None if there's nothing to saturate at
+    fn narrow_saturating(wide: &Self::Wide) -> Option<Self>;
+
+    /// Adds with the given adder circuit
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags);
+
+    /// Subtracts with the given adder circuit
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags);
+
+    /// Negates with the given adder circuit by subtracting from 0
+    fn neg_using(&self, adder: &dyn Adder) -> (Self, Flags) {
+        Self::from_bit(Bit::Off).sub_using(self, adder)
+    }
+
+    fn neg_with_flags(&self) -> (Self, Flags) {
+        self.neg_using(&RippleCarry)
+    }
+
+    fn add_with_flags(&self, other: &Self) -> (Self, Flags) {
+        self.add_using(other, &RippleCarry)
+    }
+
+    fn sub_with_flags(&self, other: &Self) -> (Self, Flags) {
+        self.sub_using(other, &RippleCarry)
+    }
+
+    /// Multiplies with the given multiplier circuit, also returning how many
+    /// partial products it added
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize);
+
+    fn mul_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let (product, flags, _) = self.mul_using(other, &Array);
+        (product, flags)
+    }
+
+    /// Multiplies as fixed-point values with the given number of fraction
+    /// Bits, which representations without a fixed number of them ignore
+    fn mul_fixed_using(&self, other: &Self, multiplier: &dyn Multiplier, _fraction: usize) -> (Self, Flags, usize) {
+        self.mul_using(other, multiplier)
+    }
+
+    /// Divides as fixed-point values with the given number of fraction Bits,
+    /// which representations without a fixed number of them ignore
+    fn div_fixed_using(&self, other: &Self, divider: &dyn Divider, _fraction: usize) -> (Self, Flags) {
+        let (quotient, _, flags) = self.div_rem_using(other, divider);
+        (quotient, flags)
+    }
+
+    /// Divides with the given divider circuit, returning the quotient and
+    /// the remainder
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags);
+
+    fn div_rem_with_flags(&self, other: &Self) -> (Self, Self, Flags) {
+        self.div_rem_using(other, &Restoring)
+    }
+
+    /// Divides with the given divider circuit for the remainder alone,
+    /// which representations that don't find it from the quotient can
+    /// give without the quotient's overflow
+    fn rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Flags) {
+        let (_, remainder, flags) = self.div_rem_using(other, divider);
+        (remainder, flags)
+    }
+
+    fn pow_with_flags(&self, exp: &Self) -> (Self, Flags);
+
+    /// Raises to the power with the given multiplier and divider circuits,
+    /// also returning how many partial products the multiplier added, which
+    /// representations without such circuits leave to `pow_with_flags`
+    fn pow_using(&self, exp: &Self, _multiplier: &dyn Multiplier, _divider: &dyn Divider) -> (Self, Flags, usize) {
+        let (power, flags) = self.pow_with_flags(exp);
+        (power, flags, 0)
+    }
+
+    fn and_with_flags(&self, other: &Self) -> (Self, Flags);
+    fn or_with_flags(&self, other: &Self) -> (Self, Flags);
+    fn xor_with_flags(&self, other: &Self) -> (Self, Flags);
+    fn not_with_flags(&self) -> (Self, Flags);
+
+    /// Shifts or rotates, raising overflow for amounts the representation
+    /// can't shift by
+    fn shift_with_flags(&self, amount: &Self, shift: Shift) -> (Self, Flags);
+
+    /// Integer square root, rounded down, of a value that isn't negative
+    fn sqrt(&self) -> Self;
+
+    /// Integer root of the given degree, rounded down, of a value that isn't
+    /// negative, where the degree has to be above 0
+    fn root(&self, degree: &Self) -> Self;
+
+    /// Creates 1 if the Bit is on or 0 if off, as the result of a condition
+    fn from_bit(bit: Bit) -> Self;
+
+    /// Compares with a magnitude comparator, returning `(less, equal,
+    /// greater)`
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit);
+
+    /// Multiplies by the imaginary unit with the given adder circuit, or
+    /// returns None for representations without an imaginary part
+    fn mul_i(&self, _adder: &dyn Adder) -> Option<(Self, Flags)> {
+        None
+    }
+
+    /// The real part, which is the value itself for real numbers
+    fn re(&self) -> Self {
+        self.clone()
+    }
+
+    /// The imaginary part, which is 0 for real numbers
+    fn im(&self) -> Self {
+        Self::from_bit(Bit::Off)
+    }
+
+    /// The complex conjugate, which for real numbers is the value itself,
+    /// along with its flags from subtracting 0 with the given adder circuit
+    fn conj_using(&self, adder: &dyn Adder) -> (Self, Flags) {
+        self.sub_using(&Self::from_bit(Bit::Off), adder)
+    }
+}
+
+/// A literal too large to even build, left as 0 with overflow raised
+pub fn overflowed<T: Number>() -> (T, Flags) {
+    let flags = Flags {
+        overflow: Bit::On,
+        ..Flags::from_condition(Bit::Off)
+    };
+
+    (T::from_bit(Bit::Off), flags)
+}
+
+/// Loads a whole number, saturating at the limit on its side if it doesn't
+/// fit
+pub fn saturating<T: Number>(n: &BigBinary) -> Option<T> {
+    match T::from_literal(n) {
+        (_, flags) if flags.overflow == Bit::On => T::saturated(n.sign()),
+        (loaded, _) => Some(loaded),
+    }
+}
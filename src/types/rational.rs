@@ -0,0 +1,576 @@
+use std::fmt;
+
+use super::adder::{Adder, RippleCarry};
+use super::divider::{Divider, Restoring};
+use super::multiplier::{Array, Multiplier};
+use super::number::overflowed;
+use super::{BigBinary, Binary, Bit, Decimal, Flags, Number, Shift};
+
+/// Rational: Exact quotient of two integers held in another representation,
+/// kept in lowest terms by a binary GCD circuit with the sign on the
+/// numerator, eg. 1/3 + 1/6 as 1/2
+///
+/// Operations run through the circuits they're given, and through the
+/// default ones for the cross-multiplications and reductions they aren't.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Rational<T = Binary> {
+    pub numerator: T,
+    /// Always above zero
+    pub denominator: T,
+}
+
+/// Stein's binary GCD of two values that aren't negative, built from only
+/// the barrel shifter, comparator and subtractor: factors of two common to
+/// both are shifted out and counted, then the larger of the two odd values
+/// is replaced by their difference, with its own factors of two shifted
+/// out, until they meet
+pub fn gcd<T: Number>(a: &T, b: &T, adder: &dyn Adder) -> T {
+    let one = T::from_bit(Bit::On);
+    let halve = |n: &T| n.shift_with_flags(&one, Shift::LogicalRight).0;
+
+    if is_zero(a) == Bit::On {
+        return b.clone();
+    }
+
+    if is_zero(b) == Bit::On {
+        return a.clone();
+    }
+
+    let (mut a, mut b) = (a.clone(), b.clone());
+    let mut twos = 0;
+
+    while (a.low_bit() | b.low_bit()) == Bit::Off {
+        a = halve(&a);
+        b = halve(&b);
+        twos += 1;
+    }
+
+    while a.low_bit() == Bit::Off {
+        a = halve(&a);
+    }
+
+    loop {
+        while b.low_bit() == Bit::Off {
+            b = halve(&b);
+        }
+
+        // Keep the smaller of the two in a, so the difference can't go
+        // below zero
+        if a.compare(&b).2 == Bit::On {
+            std::mem::swap(&mut a, &mut b);
+        }
+
+        b = b.sub_using(&a, adder).0;
+
+        if is_zero(&b) == Bit::On {
+            break;
+        }
+    }
+
+    for _ in 0..twos {
+        a = a.shift_with_flags(&one, Shift::Left).0;
+    }
+
+    a
+}
+
+/// Returns On if the value is 0
+fn is_zero<T: Number>(n: &T) -> Bit {
+    n.compare(&T::from_bit(Bit::Off)).1
+}
+
+impl<T: Number> Rational<T> {
+    /// Creates the whole number n / 1
+    pub fn whole(n: T) -> Rational<T> {
+        Rational {
+            numerator: n,
+            denominator: T::from_bit(Bit::On),
+        }
+    }
+
+    /// Applies a conversion to both parts, eg. to read them out of a
+    /// register
+    pub fn map<U, F: Fn(&T) -> U>(&self, f: F) -> Rational<U> {
+        Rational {
+            numerator: f(&self.numerator),
+            denominator: f(&self.denominator),
+        }
+    }
+
+    /// Brings a quotient to lowest terms with a positive denominator by
+    /// dividing both parts by their GCD, raising overflow if any step so far
+    /// did, or otherwise divide-by-zero for a denominator of 0
+    fn reduce(numerator: T, denominator: T, overflow: Bit, adder: &dyn Adder, divider: &dyn Divider) -> (Self, Flags) {
+        // A denominator that only became 0 by overflowing is too large
+        // rather than missing
+        if is_zero(&denominator) == Bit::On {
+            let flags = Flags {
+                overflow,
+                divide_by_zero: !overflow,
+                ..Flags::from_condition(Bit::Off)
+            };
+
+            return (Rational::whole(T::from_bit(Bit::Off)), flags);
+        }
+
+        let (numerator, denominator, overflow) = if denominator.sign() == Bit::On {
+            let (numerator, n) = numerator.neg_using(adder);
+            let (denominator, d) = denominator.neg_using(adder);
+
+            (numerator, denominator, overflow | n.overflow | d.overflow)
+        } else {
+            (numerator, denominator, overflow)
+        };
+
+        let (magnitude, lost) = magnitude(&numerator, adder);
+        let divisor = gcd(&magnitude, &denominator, adder);
+        let (numerator, _, _) = numerator.div_rem_using(&divisor, divider);
+        let (denominator, _, _) = denominator.div_rem_using(&divisor, divider);
+
+        let reduced = Rational { numerator, denominator };
+        let flags = reduced.flags(overflow | lost);
+
+        (reduced, flags)
+    }
+
+    /// Adds or subtracts over the least common multiple of the denominators,
+    /// a/b ± c/d = (a·(d/g) ± c·(b/g)) / (b·(d/g)) where g = gcd(b, d), which
+    /// keeps the intermediate products as small as they can be
+    fn combine(&self, other: &Self, adder: &dyn Adder, subtract: bool) -> (Self, Flags) {
+        let g = gcd(&self.denominator, &other.denominator, adder);
+        let (d, _, _) = other.denominator.div_rem_with_flags(&g);
+        let (b, _, _) = self.denominator.div_rem_with_flags(&g);
+
+        let (ad, f1) = self.numerator.mul_with_flags(&d);
+        let (cb, f2) = other.numerator.mul_with_flags(&b);
+        let (bd, f3) = self.denominator.mul_with_flags(&d);
+
+        let (numerator, f4) = if subtract {
+            ad.sub_using(&cb, adder)
+        } else {
+            ad.add_using(&cb, adder)
+        };
+
+        let overflow = f1.overflow | f2.overflow | f3.overflow | f4.overflow;
+
+        Rational::reduce(numerator, bd, overflow, adder, &Restoring)
+    }
+
+    /// Multiplies after cancelling each numerator against the other
+    /// denominator, a/b · c/d = (a/g1 · c/g2) / (b/g2 · d/g1), so the
+    /// products only overflow if the result itself doesn't fit
+    fn product(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        let (a, lost_a) = magnitude(&self.numerator, &RippleCarry);
+        let (c, lost_c) = magnitude(&other.numerator, &RippleCarry);
+        let g1 = gcd(&a, &other.denominator, &RippleCarry);
+        let g2 = gcd(&c, &self.denominator, &RippleCarry);
+
+        let divide = |n: &T, g: &T| n.div_rem_with_flags(g).0;
+
+        let (numerator, f1, n) = divide(&self.numerator, &g1).mul_using(&divide(&other.numerator, &g2), multiplier);
+        let (denominator, f2, d) = divide(&self.denominator, &g2).mul_using(&divide(&other.denominator, &g1), multiplier);
+
+        let overflow = lost_a | lost_c | f1.overflow | f2.overflow;
+        let (product, flags) = Rational::reduce(numerator, denominator, overflow, &RippleCarry, &Restoring);
+
+        (product, flags, n + d)
+    }
+
+    /// Cuts off the fractional part, rounding toward zero
+    fn truncate(&self) -> T {
+        self.numerator.div_rem_with_flags(&self.denominator).0
+    }
+
+    fn flags(&self, overflow: Bit) -> Flags {
+        Flags {
+            overflow,
+            negative: self.numerator.sign(),
+            ..Flags::from_condition(!is_zero(&self.numerator))
+        }
+    }
+
+    fn with_flags(self) -> (Self, Flags) {
+        let flags = self.flags(Bit::Off);
+        (self, flags)
+    }
+}
+
+/// Negates through the adder, returning whether the value was the one
+/// minimum that can't be
+fn magnitude<T: Number>(n: &T, adder: &dyn Adder) -> (T, Bit) {
+    if n.sign() == Bit::On {
+        let (negated, flags) = n.neg_using(adder);
+        (negated, flags.overflow)
+    } else {
+        (n.clone(), Bit::Off)
+    }
+}
+
+impl<T: Number> Number for Rational<T> {
+    const SIGNED: bool = T::SIGNED;
+    const INTEGRAL: bool = false;
+
+    type Wide = Rational<T::Wide>;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        let (loaded, flags) = T::from_literal(n);
+        (Rational::whole(loaded), flags)
+    }
+
+    /// Loads the digits over the power of ten exactly, which only overflows
+    /// if either part doesn't fit once reduced
+    fn from_decimal(d: &Decimal) -> (Self, Flags) {
+        let ten = BigBinary::from_int(10i8);
+        let (power, flags) = ten.pow_with_flags(&d.exponent.magnitude());
+
+        if flags.overflow == Bit::On {
+            return overflowed();
+        }
+
+        let (numerator, denominator) = if d.exponent.is_negative() {
+            (d.digits.clone(), power)
+        } else {
+            (&d.digits * &power, BigBinary::one())
+        };
+
+        let g = gcd(&numerator, &denominator, &RippleCarry);
+        let (numerator, n) = T::from_literal(&(&numerator / &g));
+        let (denominator, d) = T::from_literal(&(&denominator / &g));
+
+        Rational::reduce(numerator, denominator, n.overflow | d.overflow, &RippleCarry, &Restoring)
+    }
+
+    fn sign(&self) -> Bit {
+        self.numerator.sign()
+    }
+
+    fn low_bit(&self) -> Bit {
+        self.numerator.low_bit()
+    }
+
+    /// Only a denominator of 1 leaves a whole number
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        match self.denominator.compare(&T::from_bit(Bit::On)).1 {
+            Bit::On => self.numerator.to_integer(fraction),
+            Bit::Off => None,
+        }
+    }
+
+    fn saturated(sign: Bit) -> Option<Self> {
+        T::saturated(sign).map(Rational::whole)
+    }
+
+    fn widen(&self) -> Option<Rational<T::Wide>> {
+        Some(Rational {
+            numerator: self.numerator.widen()?,
+            denominator: self.denominator.widen()?,
+        })
+    }
+
+    /// Saturates at the limit a value is past, while a value within range
+    /// whose parts don't fit needs more precision than there is, which
+    /// saturating can't give
+    fn narrow_saturating(wide: &Rational<T::Wide>) -> Option<Self> {
+        let (max, min) = (T::saturated(Bit::Off)?, T::saturated(Bit::On)?);
+
+        if wide.compare(&Rational::whole(max.widen()?)).2 == Bit::On {
+            return Some(Rational::whole(max));
+        }
+
+        if wide.compare(&Rational::whole(min.widen()?)).0 == Bit::On {
+            return Some(Rational::whole(min));
+        }
+
+        let narrowed = Rational {
+            numerator: T::narrow_saturating(&wide.numerator)?,
+            denominator: T::narrow_saturating(&wide.denominator)?,
+        };
+
+        // A part that saturated no longer holds the value
+        match narrowed.widen()?.compare(wide) {
+            (_, Bit::On, _) => Some(narrowed),
+            _ => None,
+        }
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.combine(other, adder, false)
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        self.combine(other, adder, true)
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        self.product(other, multiplier)
+    }
+
+    /// Multiplies by the reciprocal for an exact quotient, along with the
+    /// remainder left by its whole part, `self - trunc(self / other) * other`
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        let (reciprocal, flags) = Rational::reduce(
+            other.denominator.clone(),
+            other.numerator.clone(),
+            Bit::Off,
+            &RippleCarry,
+            divider,
+        );
+
+        if flags.divide_by_zero == Bit::On {
+            return (reciprocal.clone(), reciprocal, flags);
+        }
+
+        let (quotient, flags, _) = self.product(&reciprocal, &Array);
+        let (whole, _, _) = Rational::whole(quotient.truncate()).product(other, &Array);
+        let (remainder, _) = self.sub_with_flags(&whole);
+
+        (quotient, remainder, flags)
+    }
+
+    // Rationals have no circuits for the operations that need integers,
+    // which are turned away before getting here, or for powers, which are
+    // built from products instead, so these all leave the value as it is
+
+    fn pow_with_flags(&self, _exp: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn and_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn or_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn xor_with_flags(&self, _other: &Self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    fn shift_with_flags(&self, _amount: &Self, _shift: Shift) -> (Self, Flags) {
+        self.clone().with_flags()
+    }
+
+    /// Integer square root of the whole part, which is the same as that of
+    /// the value itself
+    fn sqrt(&self) -> Self {
+        Rational::whole(self.truncate().sqrt())
+    }
+
+    fn root(&self, degree: &Self) -> Self {
+        Rational::whole(self.truncate().root(&degree.truncate()))
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        Rational::whole(T::from_bit(bit))
+    }
+
+    /// Compares the terms of both continued fractions in turn, so no product
+    /// is needed that could overflow: the whole parts decide unless they're
+    /// equal, in which case the fractional parts are compared by flipping
+    /// both over, which also flips the outcome
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        match (self.sign(), other.sign()) {
+            (Bit::On, Bit::Off) => return (Bit::On, Bit::Off, Bit::Off),
+            (Bit::Off, Bit::On) => return (Bit::Off, Bit::Off, Bit::On),
+            (Bit::On, Bit::On) => {
+                let negate = |n: &Self| n.neg_with_flags().0;
+                return negate(other).compare(&negate(self));
+            }
+            _ => {}
+        }
+
+        let (mut a, mut b) = (self.numerator.clone(), self.denominator.clone());
+        let (mut c, mut d) = (other.numerator.clone(), other.denominator.clone());
+        let mut flipped = false;
+
+        loop {
+            let (p, r, _) = a.div_rem_with_flags(&b);
+            let (q, s, _) = c.div_rem_with_flags(&d);
+            let (less, equal, greater) = p.compare(&q);
+
+            let (less, equal, greater) = match (equal, is_zero(&r), is_zero(&s)) {
+                (Bit::Off, _, _) => (less, equal, greater),
+                (_, Bit::On, Bit::On) => (Bit::Off, Bit::On, Bit::Off),
+                (_, Bit::On, Bit::Off) => (Bit::On, Bit::Off, Bit::Off),
+                (_, Bit::Off, Bit::On) => (Bit::Off, Bit::Off, Bit::On),
+                (_, Bit::Off, Bit::Off) => {
+                    a = std::mem::replace(&mut b, r);
+                    c = std::mem::replace(&mut d, s);
+                    flipped = !flipped;
+                    continue;
+                }
+            };
+
+            return if flipped {
+                (greater, equal, less)
+            } else {
+                (less, equal, greater)
+            };
+        }
+    }
+}
+
+impl Rational<BigBinary> {
+    /// Writes out the decimal expansion, with the digits that repeat forever
+    /// in parentheses, eg. 0.1(6) for 1/6, or cut off with "..." after the
+    /// given number of places if it hasn't ended or repeated by then
+    pub fn to_decimal(&self, places: usize) -> String {
+        let ten = BigBinary::from_int(10i8);
+        let magnitude = self.numerator.magnitude();
+        let sign = if self.numerator.is_negative() { "-" } else { "" };
+
+        let whole = &magnitude / &self.denominator;
+        let mut rest = &magnitude % &self.denominator;
+        let mut digits = String::new();
+        let mut seen = Vec::new();
+
+        // Long division, where a remainder that comes around again starts
+        // the same digits over
+        let fraction = loop {
+            if rest.any() == Bit::Off {
+                break if digits.is_empty() { "0".to_string() } else { digits };
+            }
+
+            if let Some(i) = seen.iter().position(|r| *r == rest) {
+                break format!("{}({})", &digits[..i], &digits[i..]);
+            }
+
+            if digits.len() == places {
+                break format!("{}...", digits);
+            }
+
+            let scaled = &rest * &ten;
+            digits.push_str(&(&scaled / &self.denominator).to_string());
+            seen.push(rest);
+            rest = &scaled % &self.denominator;
+        };
+
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+impl<T: Number + fmt::Display> fmt::Display for Rational<T> {
+    /// Leaves off a denominator of 1
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.denominator.compare(&T::from_bit(Bit::On)) {
+            (_, Bit::On, _) => write!(f, "{}", self.numerator),
+            _ => write!(f, "{}/{}", self.numerator, self.denominator),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{gcd, Rational};
+    use crate::types::adder::RippleCarry;
+    use crate::types::{BigBinary, Binary, Bit, Number, Unsigned};
+
+    type Q8 = Rational<Binary<8>>;
+
+    fn q(n: i8, d: i8) -> Q8 {
+        let (quotient, _, _) = Q8::from_bit(Bit::On)
+            .mul_with_flags(&Q8::whole(Binary::from_int(n)))
+            .0
+            .div_rem_with_flags(&Q8::whole(Binary::from_int(d)));
+
+        quotient
+    }
+
+    fn parts(r: &Q8) -> (i8, i8) {
+        (r.numerator.clone().to_int().unwrap(), r.denominator.clone().to_int().unwrap())
+    }
+
+    fn gcd_of(a: i32, b: i32) -> i32 {
+        if b == 0 { a } else { gcd_of(b, a % b) }
+    }
+
+    #[test]
+    fn test_gcd() {
+        for x in 0..=i8::MAX {
+            for y in 0..=i8::MAX {
+                let g = gcd(&Binary::<8>::from_int(x), &Binary::<8>::from_int(y), &RippleCarry);
+                assert_eq!(g.to_int::<i8>(), Ok(gcd_of(x as i32, y as i32) as i8), "gcd({}, {})", x, y);
+            }
+        }
+
+        let big = |n: i64| BigBinary::from_int(n);
+        assert_eq!(gcd(&big(1 << 40), &big(3 << 38), &RippleCarry), big(1 << 38));
+    }
+
+    #[test]
+    fn test_reduce() {
+        assert_eq!(parts(&q(6, 8)), (3, 4));
+        assert_eq!(parts(&q(3, -6)), (-1, 2));
+        assert_eq!(parts(&q(-4, -2)), (2, 1));
+        assert_eq!(parts(&q(0, -5)), (0, 1));
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let (sum, flags) = q(1, 3).add_with_flags(&q(1, 6));
+        assert_eq!((parts(&sum), flags.overflow), ((1, 2), Bit::Off));
+
+        let (difference, _) = q(1, 3).sub_with_flags(&q(1, 2));
+        assert_eq!(parts(&difference), (-1, 6));
+
+        // The cross-cancelling keeps products inside 8 Bits
+        let (product, flags) = q(100, 3).mul_with_flags(&q(3, 100));
+        assert_eq!((parts(&product), flags.overflow), ((1, 1), Bit::Off));
+
+        let (_, flags) = q(100, 1).mul_with_flags(&q(2, 1));
+        assert_eq!(flags.overflow, Bit::On);
+
+        let (quotient, remainder, _) = q(7, 2).div_rem_with_flags(&q(-3, 4));
+        assert_eq!((parts(&quotient), parts(&remainder)), ((-14, 3), (1, 2)));
+
+        let (_, _, flags) = q(1, 2).div_rem_with_flags(&q(0, 1));
+        assert_eq!(flags.divide_by_zero, Bit::On);
+    }
+
+    #[test]
+    fn test_compare() {
+        let values = [q(-7, 2), q(-1, 3), q(0, 1), q(2, 7), q(1, 3), q(3, 8), q(8, 21), q(5, 13), q(1, 1), q(127, 1)];
+
+        for (i, x) in values.iter().enumerate() {
+            for (j, y) in values.iter().enumerate() {
+                let bit = |b| if b { Bit::On } else { Bit::Off };
+                assert_eq!(x.compare(y), (bit(i < j), bit(i == j), bit(i > j)), "{} vs {}", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn test_unsigned() {
+        let u = |n: u8, d: u8| Rational {
+            numerator: Unsigned(Binary::<8>::from_uint(n)),
+            denominator: Unsigned(Binary::<8>::from_uint(d)),
+        };
+
+        let (sum, flags) = u(200, 3).add_with_flags(&u(1, 3));
+        assert_eq!((sum.to_string(), flags.overflow), ("67".to_string(), Bit::Off));
+
+        let (_, flags) = u(1, 3).sub_with_flags(&u(1, 2));
+        assert_eq!(flags.overflow, Bit::On);
+    }
+
+    #[test]
+    fn test_display() {
+        let big = |n: i64, d: i64| Rational {
+            numerator: BigBinary::from_int(n),
+            denominator: BigBinary::from_int(d),
+        };
+
+        assert_eq!(q(1, 2).to_string(), "1/2");
+        assert_eq!(q(-6, 3).to_string(), "-2");
+        assert_eq!(big(1, 6).to_decimal(20), "0.1(6)");
+        assert_eq!(big(-22, 7).to_decimal(20), "-3.(142857)");
+        assert_eq!(big(5, 4).to_decimal(20), "1.25");
+        assert_eq!(big(3, 1).to_decimal(20), "3.0");
+        assert_eq!(big(1, 97).to_decimal(5), "0.01030...");
+    }
+}
@@ -0,0 +1,381 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+use super::number;
+use super::{Adder, BigBinary, Binary, Bit, Divider, Flags, Multiplier, Number, Shift};
+
+/// Unsigned: Reads the Bits of another representation as an unsigned value,
+/// so that the top Bit counts toward the value instead of the sign and
+/// every circuit overflows when leaving the range from zero upward
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Unsigned<T = Binary>(pub T);
+
+impl<const N: usize> Number for Unsigned<Binary<N>> {
+    const SIGNED: bool = false;
+
+    type Wide = BigBinary;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        let (loaded, flags) = n.resize_unsigned_with_flags();
+        (Unsigned(loaded), flags)
+    }
+
+    fn sign(&self) -> Bit {
+        Bit::Off
+    }
+
+    fn low_bit(&self) -> Bit {
+        self.0.get(N - 1)
+    }
+
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        BigBinary::from_unsigned(&self.0).to_integer(fraction)
+    }
+
+    /// The largest value has every Bit on, while the smallest is 0
+    fn saturated(sign: Bit) -> Option<Self> {
+        Some(Unsigned(Binary::of(!sign)))
+    }
+
+    fn widen(&self) -> Option<BigBinary> {
+        Some(BigBinary::from_unsigned(&self.0))
+    }
+
+    fn narrow_saturating(wide: &BigBinary) -> Option<Self> {
+        number::saturating(wide)
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        let (sum, flags) = self.0.add_unsigned_using(&other.0, adder);
+        (Unsigned(sum), flags)
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        let (difference, flags) = self.0.sub_unsigned_using(&other.0, adder);
+        (Unsigned(difference), flags)
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        let (product, flags, additions) = self.0.mul_unsigned_using(&other.0, multiplier);
+        (Unsigned(product), flags, additions)
+    }
+
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        let (quotient, remainder, flags) = self.0.div_rem_unsigned_using(&other.0, divider);
+        (Unsigned(quotient), Unsigned(remainder), flags)
+    }
+
+    fn mul_fixed_using(&self, other: &Self, multiplier: &dyn Multiplier, fraction: usize) -> (Self, Flags, usize) {
+        let (product, flags, additions) = self.0.mul_unsigned_fixed_using(&other.0, multiplier, fraction);
+        (Unsigned(product), flags, additions)
+    }
+
+    fn div_fixed_using(&self, other: &Self, divider: &dyn Divider, fraction: usize) -> (Self, Flags) {
+        let (quotient, flags) = self.0.div_unsigned_fixed_using(&other.0, divider, fraction);
+        (Unsigned(quotient), flags)
+    }
+
+    fn pow_with_flags(&self, exp: &Self) -> (Self, Flags) {
+        let (power, flags) = self.0.pow_unsigned_with_flags(&exp.0);
+        (Unsigned(power), flags)
+    }
+
+    fn pow_using(&self, exp: &Self, multiplier: &dyn Multiplier, _divider: &dyn Divider) -> (Self, Flags, usize) {
+        let (power, flags, additions) = self.0.pow_unsigned_using(&exp.0, multiplier);
+        (Unsigned(power), flags, additions)
+    }
+
+    fn and_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let (result, flags) = self.0.and_with_flags(&other.0);
+        (Unsigned(result), flags)
+    }
+
+    fn or_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let (result, flags) = self.0.or_with_flags(&other.0);
+        (Unsigned(result), flags)
+    }
+
+    fn xor_with_flags(&self, other: &Self) -> (Self, Flags) {
+        let (result, flags) = self.0.xor_with_flags(&other.0);
+        (Unsigned(result), flags)
+    }
+
+    fn not_with_flags(&self) -> (Self, Flags) {
+        let (result, flags) = self.0.not_with_flags();
+        (Unsigned(result), flags)
+    }
+
+    fn shift_with_flags(&self, amount: &Self, shift: Shift) -> (Self, Flags) {
+        let (shifted, flags) = self.0.shift_with_flags(&amount.0, shift);
+        (Unsigned(shifted), flags)
+    }
+
+    fn sqrt(&self) -> Self {
+        Unsigned(self.0.sqrt_unsigned())
+    }
+
+    fn root(&self, degree: &Self) -> Self {
+        Unsigned(self.0.root_unsigned(&degree.0))
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        Unsigned(Binary::from_bit(bit))
+    }
+
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        self.0.compare_unsigned(&other.0)
+    }
+}
+
+/// Without a fixed width only results below zero overflow, and since no
+/// operand is ever negative the signed circuits give the right answer for
+/// everything else
+impl Number for Unsigned<BigBinary> {
+    const SIGNED: bool = false;
+
+    type Wide = BigBinary;
+
+    fn from_literal(n: &BigBinary) -> (Self, Flags) {
+        Unsigned::below_zero(BigBinary::from_literal(n))
+    }
+
+    fn sign(&self) -> Bit {
+        Bit::Off
+    }
+
+    fn low_bit(&self) -> Bit {
+        self.0.low_bit()
+    }
+
+    fn to_integer(&self, fraction: usize) -> Option<BigBinary> {
+        self.0.to_integer(fraction)
+    }
+
+    fn saturated(_sign: Bit) -> Option<Self> {
+        None
+    }
+
+    fn widen(&self) -> Option<BigBinary> {
+        Some(self.0.clone())
+    }
+
+    fn narrow_saturating(wide: &BigBinary) -> Option<Self> {
+        number::saturating(wide)
+    }
+
+    fn add_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.add_using(&other.0, adder))
+    }
+
+    fn sub_using(&self, other: &Self, adder: &dyn Adder) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.sub_using(&other.0, adder))
+    }
+
+    fn mul_using(&self, other: &Self, multiplier: &dyn Multiplier) -> (Self, Flags, usize) {
+        let (product, flags, additions) = self.0.mul_using(&other.0, multiplier);
+        let (product, flags) = Unsigned::below_zero((product, flags));
+
+        (product, flags, additions)
+    }
+
+    fn div_rem_using(&self, other: &Self, divider: &dyn Divider) -> (Self, Self, Flags) {
+        let (quotient, remainder, flags) = self.0.div_rem_using(&other.0, divider);
+        (Unsigned(quotient), Unsigned(remainder), flags)
+    }
+
+    fn pow_with_flags(&self, exp: &Self) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.pow_with_flags(&exp.0))
+    }
+
+    fn pow_using(&self, exp: &Self, multiplier: &dyn Multiplier, divider: &dyn Divider) -> (Self, Flags, usize) {
+        let (power, flags, additions) = self.0.pow_using(&exp.0, multiplier, divider);
+        let (power, flags) = Unsigned::below_zero((power, flags));
+
+        (power, flags, additions)
+    }
+
+    fn and_with_flags(&self, other: &Self) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.and_with_flags(&other.0))
+    }
+
+    fn or_with_flags(&self, other: &Self) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.or_with_flags(&other.0))
+    }
+
+    fn xor_with_flags(&self, other: &Self) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.xor_with_flags(&other.0))
+    }
+
+    /// Without a fixed width there is no top Bit to stop at, so flipping
+    /// every Bit always leaves a negative result
+    fn not_with_flags(&self) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.not_with_flags())
+    }
+
+    fn shift_with_flags(&self, amount: &Self, shift: Shift) -> (Self, Flags) {
+        Unsigned::below_zero(self.0.shift_with_flags(&amount.0, shift))
+    }
+
+    fn sqrt(&self) -> Self {
+        Unsigned(self.0.sqrt())
+    }
+
+    fn root(&self, degree: &Self) -> Self {
+        Unsigned(self.0.root(&degree.0))
+    }
+
+    fn from_bit(bit: Bit) -> Self {
+        Unsigned(BigBinary::from_bit(bit))
+    }
+
+    fn compare(&self, other: &Self) -> (Bit, Bit, Bit) {
+        self.0.compare(&other.0)
+    }
+}
+
+impl Unsigned<BigBinary> {
+    /// Raises overflow for a negative result, on top of any the circuit
+    /// already raised
+    fn below_zero((result, flags): (BigBinary, Flags)) -> (Self, Flags) {
+        let flags = Flags {
+            overflow: flags.overflow | flags.negative,
+            ..flags
+        };
+
+        (Unsigned(result), flags)
+    }
+}
+
+impl<const N: usize> PartialOrd for Unsigned<Binary<N>> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<const N: usize> Ord for Unsigned<Binary<N>> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.cmp_unsigned(&other.0)
+    }
+}
+
+impl<const N: usize> fmt::Display for Unsigned<Binary<N>> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0.clone().to_uint::<u128>() {
+            Ok(n) => write!(f, "{}", n),
+            Err(_) => write!(f, "{:?}", self.0),
+        }
+    }
+}
+
+impl fmt::Display for Unsigned<BigBinary> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigBinary, Binary, Number, Unsigned};
+    use super::super::Bit::{Off, On};
+
+    #[test]
+    fn test_8_bit_circuits() {
+        let load = |n: u8| Unsigned(Binary::<8>::from_uint(n));
+        let read = |n: Unsigned<Binary<8>>| n.0.to_uint::<u8>().unwrap();
+
+        for x in u8::MIN..=u8::MAX {
+            for y in u8::MIN..=u8::MAX {
+                let (a, b) = (load(x), load(y));
+
+                let (sum, flags) = a.add_with_flags(&b);
+                assert_eq!(read(sum), x.wrapping_add(y), "{} + {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_add(y).is_none(), "{} + {}", x, y);
+
+                let (difference, flags) = a.sub_with_flags(&b);
+                assert_eq!(read(difference), x.wrapping_sub(y), "{} - {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_sub(y).is_none(), "{} - {}", x, y);
+
+                let (product, flags) = a.mul_with_flags(&b);
+                assert_eq!(read(product), x.wrapping_mul(y), "{} * {}", x, y);
+                assert_eq!(flags.overflow == On, x.checked_mul(y).is_none(), "{} * {}", x, y);
+
+                if let (Some(q), Some(r)) = (x.checked_div(y), x.checked_rem(y)) {
+                    let (quotient, remainder, flags) = a.div_rem_with_flags(&b);
+                    assert_eq!(read(quotient), q, "{} / {}", x, y);
+                    assert_eq!(read(remainder), r, "{} % {}", x, y);
+                    assert_eq!(flags.overflow, Off, "{} / {}", x, y);
+                }
+            }
+
+            let (negated, flags) = load(x).neg_with_flags();
+            assert_eq!(read(negated), x.wrapping_neg(), "-{}", x);
+            assert_eq!(flags.overflow == On, x != 0, "-{}", x);
+        }
+    }
+
+    #[test]
+    fn test_pow() {
+        let load = |n: u64| Unsigned(Binary::<64>::from_uint(n));
+
+        let (power, flags) = load(2).pow_with_flags(&load(63));
+        assert_eq!(power.0.to_uint(), Ok(1u64 << 63));
+        assert_eq!(flags.overflow, Off);
+
+        let (power, flags) = load(2).pow_with_flags(&load(64));
+        assert_eq!(power.0.to_uint(), Ok(0u64));
+        assert_eq!(flags.overflow, On);
+
+        let (power, flags) = load(3).pow_with_flags(&load(40));
+        assert_eq!(power.0.to_uint(), Ok(3u64.pow(40)));
+        assert_eq!(flags.overflow, Off);
+    }
+
+    #[test]
+    fn test_literal() {
+        let max = BigBinary::from_decimal("18446744073709551615").unwrap();
+        let (loaded, flags) = Unsigned::<Binary>::from_literal(&max);
+        assert_eq!(loaded.0.to_uint(), Ok(u64::MAX));
+        assert_eq!(flags.overflow, Off);
+
+        let (_, flags) = Unsigned::<Binary>::from_literal(&(&max + &BigBinary::one()));
+        assert_eq!(flags.overflow, On);
+
+        let (_, flags) = Unsigned::<Binary>::from_literal(&BigBinary::from_int(-1));
+        assert_eq!(flags.overflow, On);
+    }
+
+    #[test]
+    fn test_big() {
+        let big = |n: i64| Unsigned(BigBinary::from_int(n));
+
+        let (difference, flags) = big(5).sub_with_flags(&big(3));
+        assert_eq!(difference, big(2));
+        assert_eq!(flags.overflow, Off);
+
+        let (_, flags) = big(3).sub_with_flags(&big(5));
+        assert_eq!(flags.overflow, On);
+
+        let (_, flags) = big(3).neg_with_flags();
+        assert_eq!(flags.overflow, On);
+
+        let (_, flags) = big(0).neg_with_flags();
+        assert_eq!(flags.overflow, Off);
+    }
+
+    #[test]
+    fn test_ord() {
+        let load = |n: u8| Unsigned(Binary::<8>::from_uint(n));
+
+        assert!(load(255) > load(127));
+        assert!(load(128) > load(1));
+        assert!(load(0) < load(1));
+        assert_eq!(load(200).cmp(&load(200)), std::cmp::Ordering::Equal);
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(Unsigned(Binary::<64>::from_uint(u64::MAX)).to_string(), "18446744073709551615");
+        assert_eq!(Unsigned(Binary::<128>::from_uint(u128::MAX)).to_string(), u128::MAX.to_string());
+        assert_eq!(Unsigned(Binary::<8>::from_int(-1)).to_string(), "255");
+    }
+}
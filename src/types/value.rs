@@ -0,0 +1,57 @@
+use std::fmt;
+
+use super::{BigBinary, Binary, Fixed, Rational, Unsigned, F32, F64};
+
+/// Value: Result of a calculation, held in the register it was evaluated with
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    B8(Binary<8>),
+    B16(Binary<16>),
+    B32(Binary<32>),
+    B64(Binary<64>),
+    B128(Binary<128>),
+    Big(BigBinary),
+    U8(Unsigned<Binary<8>>),
+    U16(Unsigned<Binary<16>>),
+    U32(Unsigned<Binary<32>>),
+    U64(Unsigned<Binary<64>>),
+    U128(Unsigned<Binary<128>>),
+    F32(F32),
+    F64(F64),
+    Fixed(Fixed),
+    Rational(Rational<BigBinary>),
+    /// The real and imaginary parts, each in the same representation
+    Complex(Box<Value>, Box<Value>),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use self::Value::*;
+
+        match self {
+            B8(n) => n.fmt(f),
+            B16(n) => n.fmt(f),
+            B32(n) => n.fmt(f),
+            B64(n) => n.fmt(f),
+            B128(n) => n.fmt(f),
+            Big(n) => n.fmt(f),
+            U8(n) => n.fmt(f),
+            U16(n) => n.fmt(f),
+            U32(n) => n.fmt(f),
+            U64(n) => n.fmt(f),
+            U128(n) => n.fmt(f),
+            F32(n) => n.fmt(f),
+            F64(n) => n.fmt(f),
+            Fixed(n) => n.fmt(f),
+            Rational(n) => n.fmt(f),
+            Complex(re, im) => {
+                let im = im.to_string();
+
+                match im.strip_prefix('-') {
+                    Some(magnitude) => write!(f, "{}-{}i", re, magnitude),
+                    None => write!(f, "{}+{}i", re, im),
+                }
+            }
+        }
+    }
+}